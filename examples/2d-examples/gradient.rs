@@ -2,11 +2,13 @@
 // https://github.com/oberzs/duku
 
 // This example creates a gradient and draws a circle with
-// circles of gradient colors
+// circles of gradient colors around a gradient filled circle
 
 use duku::Duku;
 use duku::Gradient;
+use duku::RadialGradient;
 use duku::Result;
+use duku::Rgb;
 use duku::Rgbf;
 use std::f32::consts::PI;
 
@@ -23,6 +25,18 @@ fn main() -> Result<()> {
         Rgbf::from("#D16BA5"),
     ]);
 
+    // create radial gradient for filling
+    // the center circle
+    let radial = RadialGradient {
+        center: [0.0, 0.0].into(),
+        radius: 100.0,
+        stops: Gradient::new(vec![
+            Rgb::from("#5FFBF1"),
+            Rgb::from("#86A8E7"),
+            Rgb::from("#D16BA5"),
+        ]),
+    };
+
     let big_r = 150.0;
     let small_r = 35.0;
     let count = 20;
@@ -52,6 +66,11 @@ fn main() -> Result<()> {
                 // draw a circle with that color
                 t.circle([x, y], small_r);
             }
+
+            // set the fill to the radial gradient
+            // and draw the center circle
+            t.fill_gradient(radial.clone());
+            t.circle([0.0, 0.0], 200.0);
         });
     });
 
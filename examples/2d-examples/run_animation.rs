@@ -4,23 +4,9 @@
 // This example splits a sprite sheet texture into
 // parts and draws it as an animation.
 
+use duku::Animation;
 use duku::Duku;
-use duku::Handle;
 use duku::Result;
-use duku::Texture;
-use duku::Vec2;
-
-struct Animation {
-    frames: Vec<Frame>,
-    current: f32,
-    speed: f32,
-}
-
-#[derive(Copy, Clone)]
-struct Frame {
-    offset: Vec2,
-    size: Vec2,
-}
 
 fn main() -> Result<()> {
     // create duku context and window
@@ -29,8 +15,8 @@ fn main() -> Result<()> {
     // load sprite sheet into texture
     let run = duku.create_texture_png("examples/textures/run.png", None)?;
 
-    // split texture into animation parts
-    let mut animation = Animation::new(&run, 10.0, 8);
+    // split texture into 8 frames playing at 10 fps
+    let mut animation = Animation::grid(&run, 8, 1, 10.0);
 
     // start window loop
     window.while_open(move |_| {
@@ -39,40 +25,9 @@ fn main() -> Result<()> {
 
         // start drawing on window
         duku.draw(None, |t| {
-            let Frame { offset, size } = animation.get();
-            t.texture_part(&run, [0.0, 0.0], size, offset, size);
+            t.animation(&animation, [0.0, 0.0]);
         });
     });
 
     Ok(())
 }
-
-impl Animation {
-    fn new(texture: &Handle<Texture>, speed: f32, frame_count: u32) -> Self {
-        let (width, height) = {
-            let tex = texture.read();
-            (tex.width() / frame_count, tex.height())
-        };
-
-        let frames: Vec<_> = (0..frame_count)
-            .map(|i| Frame {
-                offset: Vec2::new((i * width) as f32, 0.0),
-                size: Vec2::new(width as f32, height as f32),
-            })
-            .collect();
-
-        Self {
-            current: 0.0,
-            speed,
-            frames,
-        }
-    }
-
-    fn update(&mut self, dt: f32) {
-        self.current += self.speed * dt;
-    }
-
-    fn get(&self) -> Frame {
-        self.frames[self.current as usize % self.frames.len()]
-    }
-}
@@ -13,6 +13,8 @@ use duku::Result;
 use duku::Rgb;
 use duku::ShapeMode;
 use duku::Target;
+use duku::TextAlign;
+use duku::TextStyle;
 
 fn main() -> Result<()> {
     // create duku context and window
@@ -45,6 +47,10 @@ fn main() -> Result<()> {
 
         // start drawing on window
         duku.draw(None, |t| {
+            // use fontawesome for icons
+            // missing from the default font
+            t.font_fallbacks(&[&fontawesome]);
+
             if is_textbox_open {
                 // translate scene forwards to
                 // allow room for UI in front
@@ -60,10 +66,16 @@ fn main() -> Result<()> {
                 // set shape drawing mode to draw from top-left
                 t.shape_mode(ShapeMode::Center);
 
-                // draw textbox text
-                t.text(
-                    "This is a textbox\nthat you can close\nwith the 'x' button on\nthe top right!",
+                // draw textbox text wrapped
+                // to fit inside the textbox
+                t.text_box(
+                    "This is a textbox that you can close with the 'x' button on the top right!",
                     [0.0, 0.0],
+                    260.0,
+                    TextStyle {
+                        align: TextAlign::Center,
+                        ..Default::default()
+                    },
                 );
 
                 // translate scene back to
@@ -80,8 +92,7 @@ fn main() -> Result<()> {
                 // draw the rest in front
                 t.translate_z(-1.0);
 
-                // set font to fontawesome and draw 'x' icon
-                t.font(&fontawesome);
+                // draw 'x' icon
                 t.text("\u{f00d}", [125.0, 75.0]);
             } else {
                 // translate scene forwards to
@@ -96,7 +107,6 @@ fn main() -> Result<()> {
                 t.translate_z(-1.0);
 
                 // draw 'Open' label
-                t.font(&fontawesome);
                 t.text("\u{f27a}", [0.0, 0.0]);
             }
         });
@@ -4,8 +4,18 @@
 // default vertex shader
 
 void vertex() {
+    // deform vertex by skin joints
+    mat4 local_to_world = object.local_to_world;
+    if (in_weights != vec4(0.0)) {
+        mat4 skin_matrix = in_weights.x * skin.joints[in_joints.x]
+            + in_weights.y * skin.joints[in_joints.y]
+            + in_weights.z * skin.joints[in_joints.z]
+            + in_weights.w * skin.joints[in_joints.w];
+        local_to_world = local_to_world * skin_matrix;
+    }
+
     vec4 local_position = vec4(in_local_position, 1.0);
-    vec4 world_position = object.local_to_world * local_position;
+    vec4 world_position = local_to_world * local_position;
     vec4 view_position = world.world_to_view * world_position;
     vec4 clip_position = world.view_to_clip * view_position;
 
@@ -19,7 +29,7 @@ void vertex() {
     out_shadow_position[2] = world.world_to_shadow[2] * world_position;
     out_shadow_position[3] = world.world_to_shadow[3] * world_position;
 
-    mat3 local_to_normal = mat3(transpose(inverse(object.local_to_world)));
+    mat3 local_to_normal = mat3(transpose(inverse(local_to_world)));
     vec3 normal = normalize(local_to_normal * in_normal);
     vec3 tangent = normalize(local_to_normal * in_tangent);
     tangent = normalize(tangent - dot(tangent, normal) * normal);
@@ -35,7 +45,9 @@ void vertex() {
     out_tbn = mat3(tangent, bitangent, normal);
     out_color = in_color;
     out_uv = in_uv;
+    out_second_uv = in_second_uv;
     out_texture = in_texture;
 
     gl_Position = {{out_position}};
+    gl_PointSize = 1.0;
 }
@@ -12,9 +12,10 @@ layout(location = 5) in vec3 in_view_position;
 layout(location = 6) in vec4 in_clip_position;
 layout(location = 7) in vec4 in_shadow_position[4];
 layout(location = 11) in mat3 in_tbn;
+layout(location = 14) in vec2 in_second_uv;
 
 layout(set = 2, binding = 0) uniform texture2D textures[100];
-layout(set = 2, binding = 1) uniform sampler samplers[6];
+layout(set = 2, binding = 1) uniform sampler samplers[8];
 layout(set = 2, binding = 2) uniform textureCube cubemaps[100];
 layout(set = 3, binding = 0) uniform texture2D shadow_maps[4];
 
@@ -25,10 +26,17 @@ layout(set = 3, binding = 0) uniform texture2D shadow_maps[4];
 #define sampler_nr samplers[3] // nearest, repeat
 #define sampler_nb samplers[4] // nearest, border
 #define sampler_ne samplers[5] // nearest, edge
+#define sampler_lm samplers[6] // linear, mirrored
+#define sampler_nm samplers[7] // nearest, mirrored
+
+// texture indices of 100 and above have their own sampler
+uint tex_sampler(uint index) {
+    return index < 100 ? object.sampler_index : index / 100 - 1;
+}
 
 // texture lookup functions
 vec4 tex(uint index, vec2 uv) {
-    return texture(sampler2D(textures[index], samplers[object.sampler_index]), uv);
+    return texture(sampler2D(textures[index % 100], samplers[tex_sampler(index)]), uv);
 }
 
 vec4 cub(uint index, vec3 dir) {
@@ -36,5 +44,5 @@ vec4 cub(uint index, vec3 dir) {
 }
 
 vec2 tex_size(uint index) {
-    return textureSize(sampler2D(textures[index], samplers[object.sampler_index]), 0);
+    return textureSize(sampler2D(textures[index % 100], samplers[tex_sampler(index)]), 0);
 }
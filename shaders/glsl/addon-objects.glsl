@@ -7,6 +7,16 @@ struct Light {
     vec3 coords;
     int type;
     vec4 color;
+    vec3 direction;
+    float inner_cone;
+    float outer_cone;
+    float range;
+};
+
+struct UvTransform {
+    vec2 offset;
+    vec2 scale;
+    float rotation;
 };
 
 layout(set = 0, binding = 0) uniform World {
@@ -35,8 +45,13 @@ layout(set = 1, binding = 0) uniform Material {
     vec4 f;
     vec4 g;
     vec4 h;
+    UvTransform transforms[5];
 } material;
 
+layout(set = 4, binding = 0) uniform Skin {
+    mat4 joints[128];
+} skin;
+
 layout(push_constant) uniform Constants {
     mat4 local_to_world;
     vec3 tint_color;
@@ -45,3 +60,4 @@ layout(push_constant) uniform Constants {
 
 #define LIGHT_TYPE_DIRECTIONAL 0
 #define LIGHT_TYPE_POINT 1
+#define LIGHT_TYPE_SPOT 2
@@ -0,0 +1,25 @@
+// Oliver Berzs
+// https://github.com/oberzs/duku
+
+// material texture slot UVs
+
+// applies the texture transform of a slot
+// zero scale means no transform
+vec2 transform_uv(vec2 uv, uint slot) {
+    UvTransform t = material.transforms[slot];
+    if (t.scale == vec2(0.0)) {
+        return uv;
+    }
+    float c = cos(t.rotation);
+    float s = sin(t.rotation);
+    vec2 scaled = uv * t.scale;
+    return vec2(c * scaled.x + s * scaled.y, c * scaled.y - s * scaled.x) + t.offset;
+}
+
+// picks the UV set for a texture slot based on
+// the material's UV set mask and transforms it
+vec2 slot_uv(uint slot) {
+    uint bit = 1 << slot;
+    vec2 uv = (uint(material.e.r) & bit) == 0 ? in_uv : in_second_uv;
+    return transform_uv(uv, slot);
+}
@@ -9,6 +9,9 @@ layout(location = 2) in vec3 in_tangent;
 layout(location = 3) in vec2 in_uv;
 layout(location = 4) in vec4 in_color;
 layout(location = 5) in uint in_texture;
+layout(location = 6) in uvec4 in_joints;
+layout(location = 7) in vec4 in_weights;
+layout(location = 8) in vec2 in_second_uv;
 
 layout(location = 0) out vec2 out_uv;
 layout(location = 1) out vec4 out_color;
@@ -19,3 +22,4 @@ layout(location = 5) out vec3 out_view_position;
 layout(location = 6) out vec4 out_clip_position;
 layout(location = 7) out vec4 out_shadow_position[4];
 layout(location = 11) out mat3 out_tbn;
+layout(location = 14) out vec2 out_second_uv;
@@ -9,6 +9,7 @@
 
 #define SHADOW
 #define SRGB
+#define UV
 
 layout(location = 0) out vec4 out_color;
 
@@ -35,6 +36,16 @@ vec3 tone_map(vec3 color) {
     return change_luminance(color, new_lum);
 }
 
+// discards fragments below alpha cutoff
+// returns alpha used for blending
+float material_alpha(float alpha) {
+    uint mode = uint(material.c.a);
+    if (mode == 1 && alpha < material.c.b) {
+        discard;
+    }
+    return mode == 2 ? alpha : 1.0;
+}
+
 // uses Fresnel-Schlick approximation
 // calculates the reflected lights contribution (also Fresnel effect)
 vec3 specular_part(float h_dot_v, vec3 base_refl) {
@@ -65,20 +76,62 @@ float self_shadowing(float n_dot_v, float n_dot_l, float roughness) {
     return ggx1 * ggx2;
 }
 
+// smoothly fades point and spot lights
+// to zero at their range, if they have one
+float range_falloff(float distance, float range) {
+    if (range <= 0.0) {
+        return 1.0;
+    }
+    float ratio = distance / range;
+    return clamp(1.0 - ratio * ratio * ratio * ratio, 0.0, 1.0);
+}
+
+// calculates the specular light reflected
+// by the clearcoat layer and its Fresnel
+vec3 clearcoat_part(vec3 normal, vec3 view_dir, vec3 light_dir, float roughness, out vec3 fresnel) {
+    vec3 half_dir = normalize(view_dir + light_dir);
+    float h_dot_v = max(dot(half_dir, view_dir), 0.0);
+    float n_dot_l = max(dot(normal, light_dir), 0.0);
+    float n_dot_v = max(dot(normal, view_dir), 0.0);
+    float n_dot_h = max(dot(normal, half_dir), 0.0);
+
+    // clearcoat is a dielectric layer
+    fresnel = specular_part(h_dot_v, vec3(0.04));
+    float nd = normal_distribution(n_dot_h, roughness);
+    float ss = self_shadowing(n_dot_v, n_dot_l, roughness);
+
+    return (fresnel * nd * ss) / max(0.001, 4.0 * n_dot_v * n_dot_l) * n_dot_l;
+}
+
 void fragment() {
-    vec4 albedo_tex = tex(int(material.a.a), in_uv);
-    vec4 met_rough_tex = tex(int(material.b.b), in_uv);
-    float ambient_occlusion = tex(int(material.b.a), in_uv).r;
-    vec3 emissive = tex(int(material.c.g), in_uv).rgb * material.d.rgb;
+    vec4 albedo_tex = tex(int(material.a.a), slot_uv(0));
+    vec4 met_rough_tex = tex(int(material.b.b), slot_uv(1));
+    float ambient_occlusion = tex(int(material.b.a), slot_uv(2)).r;
+    vec3 emissive = tex(int(material.c.g), slot_uv(4)).rgb * material.d.rgb * material.d.a;
     vec3 albedo = material.a.rgb * albedo_tex.rgb * object.tint_color;
     float metalness = material.b.r * met_rough_tex.b;
     float roughness = material.b.g * met_rough_tex.g;
 
+    // discard masked fragments
+    float alpha = material_alpha(albedo_tex.a);
+
     // calculate normal and view direction
-    vec3 normal = tex(int(material.c.r), in_uv).xyz * (255.0 / 128.0) - 1.0;
+    vec3 normal = tex(int(material.c.r), slot_uv(3)).xyz * (255.0 / 128.0) - 1.0;
     normal = normalize(in_tbn * normal);
+    if (!gl_FrontFacing) {
+        // back faces of double-sided materials
+        normal = -normal;
+    }
     vec3 view_dir = normalize(world.camera_position - in_world_position);
 
+    // clearcoat uses the surface normal
+    float clearcoat = material.e.g;
+    float clearcoat_roughness = material.e.b;
+    vec3 clearcoat_normal = normalize(in_tbn[2]);
+    if (!gl_FrontFacing) {
+        clearcoat_normal = -clearcoat_normal;
+    }
+
     // calculate how much surface reflects
     // when looking directly at it
     // non-metallics get constant 0.04, metalics get their albedo
@@ -104,10 +157,20 @@ void fragment() {
             radiance = light.color.xyz;
         } else if (light.type == LIGHT_TYPE_POINT) {
             float distance = length(light.coords.xyz - in_world_position);
-            float attenuation = 1.0 / (distance * distance);
+            float attenuation = range_falloff(distance, light.range) / (distance * distance);
 
             radiance = light.color.xyz * attenuation;
             light_dir = normalize(light.coords.xyz - in_world_position);
+        } else if (light.type == LIGHT_TYPE_SPOT) {
+            float distance = length(light.coords.xyz - in_world_position);
+            light_dir = normalize(light.coords.xyz - in_world_position);
+
+            // fade out between inner and outer cones
+            float angle = dot(-light_dir, light.direction);
+            float cone = smoothstep(light.outer_cone, light.inner_cone, angle);
+            float attenuation = cone * range_falloff(distance, light.range) / (distance * distance);
+
+            radiance = light.color.xyz * attenuation;
         }
 
         vec3 half_dir = normalize(view_dir + light_dir);
@@ -131,7 +194,16 @@ void fragment() {
         vec3 specular = (s_part * nd * ss) / max(0.001, 4.0 * n_dot_v * n_dot_l);
         vec3 diffuse = d_part * albedo / PI;
 
-        light_amount += (diffuse + specular) * radiance * n_dot_l;
+        vec3 base = (diffuse + specular) * n_dot_l;
+
+        // layer clearcoat on top of the base
+        if (clearcoat > 0.0) {
+            vec3 fresnel;
+            vec3 coat = clearcoat_part(clearcoat_normal, view_dir, light_dir, clearcoat_roughness, fresnel);
+            base = base * (1.0 - clearcoat * fresnel) + clearcoat * coat;
+        }
+
+        light_amount += base * radiance;
     }
 
     vec3 ambient = world.ambient_color * albedo;
@@ -143,5 +215,5 @@ void fragment() {
     // convert to sRGB
     color = to_srgb(color);
 
-    out_color = vec4(color, 1.0);
+    out_color = vec4(color, alpha);
 }
@@ -0,0 +1,21 @@
+// Oliver Berzs
+// https://github.com/oberzs/duku
+
+// signed distance field font shader
+
+#define DEPTH write
+#define CULL disabled
+#define SHAPE filled_triangles 
+
+layout(location = 0) out vec4 frag_color;
+
+void fragment() {
+    // tint color stores the edge and softness
+    float edge = object.tint_color.x;
+    float softness = object.tint_color.y;
+
+    float dist = tex(in_texture, in_uv).r;
+    float width = fwidth(dist) + softness;
+    float value = smoothstep(edge - width, edge + width, dist);
+    frag_color = in_color * vec4(1.0, 1.0, 1.0, value);
+}
@@ -8,11 +8,19 @@
 #define SHAPE filled_triangles
 
 #define SRGB
+#define UV
 
 layout(location = 0) out vec4 out_color;
 
 void fragment() {
     vec4 color = vec4(material.a.rgb * object.tint_color, 1.0);
     uint texture = uint(material.a.w);
-    out_color = to_srgb(tex(texture, in_uv)) * in_color * color;
+    vec2 uv = slot_uv(0);
+    out_color = to_srgb(tex(texture, uv)) * in_color * color;
+
+    // discard masked fragments
+    uint mode = uint(material.c.a);
+    if (mode == 1 && out_color.a < material.c.b) {
+        discard;
+    }
 }
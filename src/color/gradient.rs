@@ -2,6 +2,8 @@
 // https://github.com/oberzs/duku
 
 use super::Mix;
+use super::Rgb;
+use crate::math::Vec2;
 
 /// Color gradient.
 ///
@@ -26,6 +28,63 @@ pub struct Gradient<C> {
     part: f32,
 }
 
+/// Linear gradient for filling shapes.
+///
+/// Colors change along the line from
+/// `start` to `end`.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use duku::Duku;
+/// # use duku::Gradient;
+/// # use duku::LinearGradient;
+/// # let (mut d, _) = Duku::windowed(1, 1).unwrap();
+/// let gradient = LinearGradient {
+///     start: [-50.0, 0.0].into(),
+///     end: [50.0, 0.0].into(),
+///     stops: Gradient::new(vec!["#D16BA5".into(), "#86A8E7".into()]),
+/// };
+///
+/// # d.draw(None, |t| {
+/// // when drawing
+/// t.fill_gradient(gradient.clone());
+/// t.rect([0.0, 0.0], [100.0, 100.0]);
+/// # });
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearGradient {
+    /// point where the first color is
+    pub start: Vec2,
+    /// point where the last color is
+    pub end: Vec2,
+    /// colors of the gradient
+    pub stops: Gradient<Rgb>,
+}
+
+/// Radial gradient for filling shapes.
+///
+/// Colors change from the `center` outwards
+/// until reaching the `radius`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RadialGradient {
+    /// point where the first color is
+    pub center: Vec2,
+    /// distance from the center where the last color is
+    pub radius: f32,
+    /// colors of the gradient
+    pub stops: Gradient<Rgb>,
+}
+
+/// Gradient used for shape fills and borders.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShapeGradient {
+    /// linear gradient
+    Linear(LinearGradient),
+    /// radial gradient
+    Radial(RadialGradient),
+}
+
 impl<C: Mix + Default + Copy> Gradient<C> {
     /// Create gradient from colors
     ///
@@ -53,7 +112,7 @@ impl<C: Mix + Default + Copy> Gradient<C> {
             C::default()
         } else {
             let i = (p / self.part).floor();
-            let ip = (p - (self.part * i)) * self.values.len() as f32;
+            let ip = (p - (self.part * i)) / self.part;
 
             let c1 = self.values[i as usize];
             let c2 = self.values.get(i as usize + 1).copied().unwrap_or(c1);
@@ -61,4 +120,115 @@ impl<C: Mix + Default + Copy> Gradient<C> {
             C::mix(c1, c2, ip)
         }
     }
+
+    pub(crate) fn len(&self) -> usize {
+        self.values.len()
+    }
+}
+
+impl ShapeGradient {
+    /// Get color at position
+    ///
+    /// Positions outside of the gradient get
+    /// the closest color.
+    pub fn color_at(&self, pos: impl Into<Vec2>) -> Rgb {
+        let (p, stops) = match self {
+            Self::Linear(g) => (g.progress(pos.into()), &g.stops),
+            Self::Radial(g) => (g.progress(pos.into()), &g.stops),
+        };
+        stops.get(p.max(0.0).min(1.0))
+    }
+
+    pub(crate) fn opaque(&self) -> bool {
+        let stops = match self {
+            Self::Linear(g) => &g.stops,
+            Self::Radial(g) => &g.stops,
+        };
+        stops.values.iter().all(|c| c.a == 255)
+    }
+
+    pub(crate) fn visible(&self) -> bool {
+        let stops = match self {
+            Self::Linear(g) => &g.stops,
+            Self::Radial(g) => &g.stops,
+        };
+        stops.values.iter().any(|c| c.a > 0)
+    }
+}
+
+impl LinearGradient {
+    pub(crate) fn progress(&self, pos: Vec2) -> f32 {
+        let dir = self.end - self.start;
+        let sqr_length = dir.sqr_length();
+        if sqr_length == 0.0 {
+            0.0
+        } else {
+            (pos - self.start).dot(dir) / sqr_length
+        }
+    }
+}
+
+impl RadialGradient {
+    pub(crate) fn progress(&self, pos: Vec2) -> f32 {
+        if self.radius == 0.0 {
+            1.0
+        } else {
+            (pos - self.center).length() / self.radius
+        }
+    }
+}
+
+impl From<LinearGradient> for ShapeGradient {
+    fn from(g: LinearGradient) -> Self {
+        Self::Linear(g)
+    }
+}
+
+impl From<RadialGradient> for ShapeGradient {
+    fn from(g: RadialGradient) -> Self {
+        Self::Radial(g)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Gradient;
+    use super::LinearGradient;
+    use super::RadialGradient;
+    use super::Rgb;
+    use super::ShapeGradient;
+    use crate::math::Vec2;
+
+    #[test]
+    fn get() {
+        let g = Gradient::new(vec![Rgb::gray(0), Rgb::gray(100), Rgb::gray(200)]);
+        assert_eq!(g.get(0.0), Rgb::gray(0));
+        assert_eq!(g.get(0.25), Rgb::gray(50));
+        assert_eq!(g.get(0.5), Rgb::gray(100));
+        assert_eq!(g.get(1.0), Rgb::gray(200));
+    }
+
+    #[test]
+    fn linear_color_at() {
+        let g = ShapeGradient::from(LinearGradient {
+            start: Vec2::new(0.0, 0.0),
+            end: Vec2::new(100.0, 0.0),
+            stops: Gradient::new(vec![Rgb::gray(0), Rgb::gray(200)]),
+        });
+        assert_eq!(g.color_at([50.0, 30.0]), Rgb::gray(100));
+        assert_eq!(g.color_at([-10.0, 0.0]), Rgb::gray(0));
+        assert_eq!(g.color_at([200.0, 0.0]), Rgb::gray(200));
+    }
+
+    #[test]
+    fn radial_color_at() {
+        let g = ShapeGradient::from(RadialGradient {
+            center: Vec2::new(10.0, 10.0),
+            radius: 10.0,
+            stops: Gradient::new(vec![Rgb::gray(0), Rgb::gray(200)]),
+        });
+        assert_eq!(g.color_at([10.0, 10.0]), Rgb::gray(0));
+        assert_eq!(g.color_at([10.0, 15.0]), Rgb::gray(100));
+        assert_eq!(g.color_at([30.0, 10.0]), Rgb::gray(200));
+    }
 }
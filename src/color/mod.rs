@@ -10,6 +10,9 @@ mod rgbf;
 use mix::mixf;
 
 pub use gradient::Gradient;
+pub use gradient::LinearGradient;
+pub use gradient::RadialGradient;
+pub use gradient::ShapeGradient;
 pub use hsb::Hsb;
 pub use mix::Mix;
 pub use rgb::Rgb;
@@ -188,11 +188,17 @@ impl Commands {
     }
 
     pub(crate) fn bind_shader(&self, shader: &Shader) {
+        self.bind_shader_variant(shader, false, false);
+    }
+
+    pub(crate) fn bind_shader_variant(&self, shader: &Shader, double_sided: bool, blended: bool) {
+        let handle = shader.variant(double_sided, blended);
+
         // update stats
         let mut stats = self.stats.get();
         let mut used = self.used_shaders.borrow_mut();
-        if !used.contains(&shader.handle()) {
-            used.insert(shader.handle());
+        if !used.contains(&handle) {
+            used.insert(handle);
             stats.shaders_used += 1;
         }
         stats.shader_rebinds += 1;
@@ -200,11 +206,7 @@ impl Commands {
 
         // bind shader
         unsafe {
-            vk::cmd_bind_pipeline(
-                self.buffer.get(),
-                vk::PIPELINE_BIND_POINT_GRAPHICS,
-                shader.handle(),
-            );
+            vk::cmd_bind_pipeline(self.buffer.get(), vk::PIPELINE_BIND_POINT_GRAPHICS, handle);
         }
     }
 
@@ -1,6 +1,7 @@
 // Oliver Berzs
 // https://github.com/oberzs/duku
 
+use std::collections::HashMap;
 use std::convert::TryInto;
 use std::fs;
 use std::path::Path;
@@ -9,19 +10,31 @@ use std::time::Instant;
 use crate::device::pick_gpu;
 use crate::device::Device;
 use crate::device::Stats;
+use crate::error::Error;
 use crate::error::Result;
 use crate::font::Font;
 use crate::font::FontData;
+#[cfg(feature = "otf")]
+use crate::font::GlyphSource;
+use crate::image;
 use crate::image::Canvas;
 use crate::image::Cubemap;
 use crate::image::CubemapSides;
 use crate::image::Format;
 use crate::image::Mips;
 use crate::image::Msaa;
+use crate::image::Sprite;
 use crate::image::Texture;
+use crate::image::TextureAtlas;
+use crate::image::TextureAtlasBuilder;
 use crate::instance::Instance;
+use crate::mesh;
+use crate::mesh::AnimationPlayer;
 use crate::mesh::Mesh;
 use crate::mesh::Model;
+use crate::mesh::Skin;
+use crate::mesh::Tilemap;
+use crate::mesh::Tileset;
 use crate::pipeline::Material;
 use crate::pipeline::Shader;
 use crate::pipeline::ShaderConfig;
@@ -127,12 +140,14 @@ impl Duku {
             self.begin_draw();
         }
 
-        // let user record draw calls
-        let mut target = Target::new(&self.builtins);
-        draw_fn(&mut target);
         let canvas = &self.window_canvases[self.swapchain.current()];
         let cam = get_camera(camera, canvas.width, canvas.height);
 
+        // let user record draw calls
+        let mut target = Target::new(&self.builtins, &cam, canvas.height);
+        draw_fn(&mut target);
+        self.resources.upload_fonts(&self.device);
+
         // render
         self.forward_renderer
             .render(&self.device, canvas, &cam, &self.uniforms, target);
@@ -166,13 +181,18 @@ impl Duku {
             self.begin_draw();
         }
 
+        let (cam, height) = {
+            let cnv = canvas.read();
+            (get_camera(camera, cnv.width, cnv.height), cnv.height)
+        };
+
         // let user record draw calls
-        let mut target = Target::new(&self.builtins);
+        let mut target = Target::new(&self.builtins, &cam, height);
         draw_fn(&mut target);
+        self.resources.upload_fonts(&self.device);
 
         {
             let cnv = canvas.read();
-            let cam = get_camera(camera, cnv.width, cnv.height);
 
             // render
             self.forward_renderer
@@ -201,6 +221,73 @@ impl Duku {
         Ok(self.resources.add_texture(tex))
     }
 
+    /// Create a texture atlas by packing images
+    ///
+    /// Images with zero width or height
+    /// are rejected with `Error::EmptyImage`.
+    pub fn create_texture_atlas(&mut self, builder: TextureAtlasBuilder) -> Result<TextureAtlas> {
+        let size = builder.size;
+        if let Some(image) = builder
+            .images
+            .iter()
+            .find(|i| i.width == 0 || i.height == 0)
+        {
+            return Err(Error::EmptyImage(image.name.clone()));
+        }
+        let sizes: Vec<_> = builder.images.iter().map(|i| (i.width, i.height)).collect();
+
+        let placements = match image::pack(&sizes, size, builder.padding) {
+            Some(p) => p,
+            None => {
+                let image = builder
+                    .images
+                    .iter()
+                    .find(|i| {
+                        i.width + builder.padding * 2 > size
+                            || i.height + builder.padding * 2 > size
+                    })
+                    .map(|i| i.name.clone())
+                    .unwrap_or_default();
+                return Err(Error::ImageTooLarge(image));
+            }
+        };
+        let page_count = placements.iter().map(|p| p.0 + 1).max().unwrap_or(0);
+
+        // copy images into pages
+        let mut pages = vec![vec![0; (size * size * 4) as usize]; page_count];
+        let mut sprites = HashMap::new();
+        for (image, (page, x, y)) in builder.images.iter().zip(placements) {
+            let row = image.width as usize * 4;
+            for (i, line) in image
+                .data
+                .chunks(row)
+                .take(image.height as usize)
+                .enumerate()
+            {
+                let start = ((y as usize + i) * size as usize + x as usize) * 4;
+                pages[page][start..start + line.len()].copy_from_slice(line);
+            }
+
+            sprites.insert(
+                image.name.clone(),
+                Sprite {
+                    texture: page,
+                    width: image.width,
+                    height: image.height,
+                    x,
+                    y,
+                },
+            );
+        }
+
+        let textures = pages
+            .into_iter()
+            .map(|data| self.create_texture(data, Format::Srgba, Mips::Zero, size, size))
+            .collect::<Result<_>>()?;
+
+        Ok(TextureAtlas::new(textures, sprites))
+    }
+
     /// Create a cubemap from byte data
     pub fn create_cubemap(
         &mut self,
@@ -241,10 +328,39 @@ impl Duku {
 
     /// Create a model
     pub fn create_model(&mut self) -> Handle<Model> {
-        let model = Model { nodes: vec![] };
+        let model = Model {
+            nodes: vec![],
+            skins: vec![],
+            animations: vec![],
+            player: AnimationPlayer::new(),
+        };
         self.resources.add_model(model)
     }
 
+    /// Create a skin with no joints
+    pub fn create_skin(&mut self) -> Result<Handle<Skin>> {
+        let skin = Skin::new(&self.device, &mut self.uniforms)?;
+        Ok(self.resources.add_skin(skin))
+    }
+
+    /// Create an empty tilemap
+    ///
+    /// `width` and `height` are in tiles
+    pub fn create_tilemap(
+        &mut self,
+        tileset: Tileset,
+        width: u32,
+        height: u32,
+        layers: usize,
+    ) -> Handle<Tilemap> {
+        let chunks = mesh::chunk_count(width) * mesh::chunk_count(height);
+        let meshes = (0..layers)
+            .map(|_| (0..chunks).map(|_| self.create_mesh()).collect())
+            .collect();
+        let tilemap = Tilemap::new(tileset, width, height, meshes);
+        self.resources.add_tilemap(tilemap)
+    }
+
     /// Create a material
     pub fn create_material(&mut self) -> Result<Handle<Material>> {
         let mat = Material::new(&self.device, &mut self.uniforms)?;
@@ -349,6 +465,15 @@ impl Duku {
         Ok(self.resources.add_font(font))
     }
 
+    #[cfg(feature = "otf")]
+    pub(crate) fn create_font_dynamic(
+        &mut self,
+        source: Box<dyn GlyphSource>,
+    ) -> Result<Handle<Font>> {
+        let font = Font::dynamic(&self.device, &mut self.uniforms, source)?;
+        Ok(self.resources.add_font(font))
+    }
+
     /// Get last render's statistics
     pub fn stats(&self) -> Stats {
         self.device.stats()
@@ -26,6 +26,14 @@ pub enum Error {
     MaterialLimit,
     /// Canvas limit has been reached (max 100)
     CanvasLimit,
+    /// Skin limit has been reached (max 100)
+    SkinLimit,
+    /// Image is too large for the texture atlas
+    ImageTooLarge(String),
+    /// Image has zero width or height
+    EmptyImage(String),
+    /// Font has no usable glyphs
+    InvalidFont,
 
     /// Unsupported PNG or JPEG color format
     #[cfg(any(feature = "png", feature = "jpeg"))]
@@ -52,6 +60,9 @@ pub enum Error {
     /// Unsupported GLTF primitive mode
     #[cfg(feature = "gltf")]
     UnsupportedPrimitive,
+    /// Model couldn't be exported to GLTF
+    #[cfg(feature = "gltf")]
+    GltfExport(String),
 
     /// Invalid OTF data
     #[cfg(feature = "otf")]
@@ -59,6 +70,31 @@ pub enum Error {
     /// Unsupported character
     #[cfg(feature = "otf")]
     UnsupportedChar(char),
+
+    /// Invalid Aseprite JSON data
+    #[cfg(feature = "aseprite")]
+    InvalidAseprite,
+    /// Aseprite tag not found
+    #[cfg(feature = "aseprite")]
+    UnsupportedTag(String),
+
+    /// Invalid SVG data
+    #[cfg(feature = "svg")]
+    InvalidSvg,
+
+    /// Invalid Tiled map data
+    #[cfg(feature = "tiled")]
+    InvalidTiled,
+    /// Unsupported Tiled map option
+    #[cfg(feature = "tiled")]
+    UnsupportedTiled(String),
+
+    /// Invalid BMFont data
+    #[cfg(feature = "bmfont")]
+    InvalidBmfont,
+    /// Unsupported BMFont option
+    #[cfg(feature = "bmfont")]
+    UnsupportedBmfont(String),
 }
 
 impl error::Error for Error {}
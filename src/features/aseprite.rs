@@ -0,0 +1,196 @@
+// Oliver Berzs
+// https://github.com/oberzs/duku
+
+//! Optional feature `aseprite` module for Aseprite sprite sheet support.
+
+#![cfg(feature = "aseprite")]
+
+use json_dep::Value;
+use std::fs;
+use std::path::Path;
+
+use crate::error::Error;
+use crate::error::Result;
+use crate::image::Animation;
+use crate::image::AnimationFrame;
+use crate::image::AnimationMode;
+use crate::image::Texture;
+use crate::math::Vec2;
+use crate::resources::Handle;
+
+impl Animation {
+    /// Create animation from an Aseprite JSON file
+    ///
+    /// If `tag` is `None`, all frames are used.
+    pub fn aseprite(
+        texture: &Handle<Texture>,
+        path: impl AsRef<Path>,
+        tag: Option<&str>,
+    ) -> Result<Self> {
+        let bytes = fs::read(path.as_ref())?;
+        Self::aseprite_bytes(texture, &bytes, tag)
+    }
+
+    /// Create animation from Aseprite JSON bytes
+    ///
+    /// If `tag` is `None`, all frames are used.
+    pub fn aseprite_bytes(
+        texture: &Handle<Texture>,
+        bytes: &[u8],
+        tag: Option<&str>,
+    ) -> Result<Self> {
+        let (frames, mode) = load_frames(bytes, tag)?;
+        let mut animation = Self::new(texture, frames);
+        animation.mode(mode);
+        Ok(animation)
+    }
+}
+
+// parses frames and the tag's playback mode
+fn load_frames(bytes: &[u8], tag: Option<&str>) -> Result<(Vec<AnimationFrame>, AnimationMode)> {
+    let json: Value = json_dep::from_slice(bytes).map_err(|_| Error::InvalidAseprite)?;
+
+    // frames can be exported as an array or a map
+    let mut frames = match &json["frames"] {
+        Value::Array(values) => values.iter().map(load_frame).collect::<Result<Vec<_>>>()?,
+        Value::Object(map) => {
+            let mut named: Vec<_> = map
+                .iter()
+                .map(|(name, value)| Ok((frame_number(name), load_frame(value)?)))
+                .collect::<Result<_>>()?;
+            named.sort_by_key(|(number, _)| *number);
+            named.into_iter().map(|(_, frame)| frame).collect()
+        }
+        _ => return Err(Error::InvalidAseprite),
+    };
+
+    // use only frames from the tag
+    let mut mode = AnimationMode::Loop;
+    if let Some(name) = tag {
+        let tag = json["meta"]["frameTags"]
+            .as_array()
+            .and_then(|tags| tags.iter().find(|t| t["name"] == name))
+            .ok_or_else(|| Error::UnsupportedTag(name.to_string()))?;
+        let from = tag["from"].as_u64().ok_or(Error::InvalidAseprite)? as usize;
+        let to = tag["to"].as_u64().ok_or(Error::InvalidAseprite)? as usize;
+        if from > to || to >= frames.len() {
+            return Err(Error::InvalidAseprite);
+        }
+
+        frames = frames[from..=to].to_vec();
+        match tag["direction"].as_str() {
+            Some("reverse") => frames.reverse(),
+            Some("pingpong") => mode = AnimationMode::PingPong,
+            _ => (),
+        }
+    }
+
+    Ok((frames, mode))
+}
+
+fn load_frame(value: &Value) -> Result<AnimationFrame> {
+    let rect = &value["frame"];
+    let get = |v: &Value| v.as_f64().map(|n| n as f32).ok_or(Error::InvalidAseprite);
+
+    Ok(AnimationFrame {
+        pos: Vec2::new(get(&rect["x"])?, get(&rect["y"])?),
+        size: Vec2::new(get(&rect["w"])?, get(&rect["h"])?),
+        // duration is in milliseconds
+        duration: value["duration"].as_f64().unwrap_or(100.0) as f32 / 1000.0,
+    })
+}
+
+// gets the last number in the frame name
+// e.g. "run 12.aseprite" -> 12
+fn frame_number(name: &str) -> u64 {
+    let stem = name.rsplitn(2, '.').last().unwrap_or(name);
+    let digits: String = stem
+        .chars()
+        .rev()
+        .skip_while(|c| !c.is_ascii_digit())
+        .take_while(char::is_ascii_digit)
+        .collect();
+    digits
+        .chars()
+        .rev()
+        .collect::<String>()
+        .parse()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::frame_number;
+    use super::load_frames;
+    use crate::image::AnimationMode;
+    use crate::math::Vec2;
+
+    const ARRAY: &[u8] = br#"{
+        "frames": [
+            { "frame": { "x": 0, "y": 0, "w": 16, "h": 8 }, "duration": 100 },
+            { "frame": { "x": 16, "y": 0, "w": 16, "h": 8 }, "duration": 200 },
+            { "frame": { "x": 32, "y": 0, "w": 16, "h": 8 } }
+        ],
+        "meta": {
+            "frameTags": [
+                { "name": "back", "from": 0, "to": 1, "direction": "reverse" },
+                { "name": "bounce", "from": 1, "to": 2, "direction": "pingpong" },
+                { "name": "broken", "from": 2, "to": 5, "direction": "forward" }
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn array_frames() {
+        let (frames, mode) = load_frames(ARRAY, None).unwrap();
+        assert_eq!(mode, AnimationMode::Loop);
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[1].pos, Vec2::new(16.0, 0.0));
+        assert_eq!(frames[1].size, Vec2::new(16.0, 8.0));
+        assert_eq_delta!(frames[1].duration, 0.2);
+        assert_eq_delta!(frames[2].duration, 0.1);
+    }
+
+    #[test]
+    fn map_frames() {
+        let json = br#"{
+            "frames": {
+                "run 10.aseprite": { "frame": { "x": 20, "y": 0, "w": 10, "h": 10 } },
+                "run 2.aseprite": { "frame": { "x": 10, "y": 0, "w": 10, "h": 10 } },
+                "run 1.aseprite": { "frame": { "x": 0, "y": 0, "w": 10, "h": 10 } }
+            }
+        }"#;
+        let (frames, _) = load_frames(json, None).unwrap();
+        let xs: Vec<_> = frames.iter().map(|f| f.pos.x).collect();
+        assert_eq!(xs, vec![0.0, 10.0, 20.0]);
+    }
+
+    #[test]
+    fn tags() {
+        let (frames, mode) = load_frames(ARRAY, Some("back")).unwrap();
+        assert_eq!(mode, AnimationMode::Loop);
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].pos, Vec2::new(16.0, 0.0));
+
+        let (frames, mode) = load_frames(ARRAY, Some("bounce")).unwrap();
+        assert_eq!(mode, AnimationMode::PingPong);
+        assert_eq!(frames[0].pos, Vec2::new(16.0, 0.0));
+
+        assert!(load_frames(ARRAY, Some("broken")).is_err());
+        assert!(load_frames(ARRAY, Some("missing")).is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(load_frames(b"{}", None).is_err());
+        assert!(load_frames(b"not json", None).is_err());
+        assert!(load_frames(br#"{ "frames": [{ "frame": {} }] }"#, None).is_err());
+    }
+
+    #[test]
+    fn frame_numbers() {
+        assert_eq!(frame_number("run 12.aseprite"), 12);
+        assert_eq!(frame_number("walk_3.png"), 3);
+        assert_eq!(frame_number("idle"), 0);
+    }
+}
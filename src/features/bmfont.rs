@@ -0,0 +1,381 @@
+// Oliver Berzs
+// https://github.com/oberzs/duku
+
+//! Optional feature `bmfont` module for AngelCode BMFont support.
+
+#![cfg(feature = "bmfont")]
+
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::fs;
+use std::path::Path;
+
+use super::png::load_png;
+use crate::duku::Duku;
+use crate::error::Error;
+use crate::error::Result;
+use crate::font::CharData;
+use crate::font::Font;
+use crate::font::FontData;
+use crate::image::ColorSpace;
+use crate::image::Format;
+use crate::math::Vec2;
+use crate::math::Vec4;
+use crate::resources::Handle;
+
+// channel values of the common block
+const CHANNEL_ZERO: u8 = 3;
+const CHANNEL_ONE: u8 = 4;
+
+#[derive(Default)]
+struct BmFont {
+    size: i32,
+    line_height: u32,
+    base: u32,
+    width: u32,
+    height: u32,
+    packed: bool,
+    alpha_channel: u8,
+    pages: Vec<String>,
+    chars: Vec<BmChar>,
+    kerning: Vec<(u32, u32, i32)>,
+}
+
+struct BmChar {
+    id: u32,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    x_offset: i32,
+    y_offset: i32,
+    x_advance: i32,
+    page: u32,
+}
+
+impl Duku {
+    /// Create font from BMFont file
+    ///
+    /// Supports text and binary `.fnt` files
+    /// with PNG pages.
+    pub fn create_font_bmfont(&mut self, path: impl AsRef<Path>) -> Result<Handle<Font>> {
+        let bytes = fs::read(path.as_ref())?;
+        let root = path.as_ref().parent().unwrap_or_else(|| Path::new("./"));
+        self.create_font_bmfont_bytes(&bytes, root)
+    }
+
+    /// Create font from BMFont bytes
+    ///
+    /// `root` is used to find the page files.
+    pub fn create_font_bmfont_bytes(
+        &mut self,
+        bytes: &[u8],
+        root: impl AsRef<Path>,
+    ) -> Result<Handle<Font>> {
+        let font = load_font(bytes)?;
+
+        if font.packed {
+            return Err(Error::UnsupportedBmfont("packed channels".to_string()));
+        }
+        if font.chars.is_empty() || font.line_height == 0 {
+            return Err(Error::InvalidBmfont);
+        }
+
+        // glyph coverage of each page
+        let mut pages = vec![];
+        for page in &font.pages {
+            let png_bytes = fs::read(root.as_ref().join(page))?;
+            let png = load_png(&png_bytes, ColorSpace::Linear)?;
+            if png.width != font.width || png.height != font.height {
+                return Err(Error::InvalidBmfont);
+            }
+            pages.push(coverage(&png.data, png.format, font.alpha_channel));
+        }
+
+        // pixel sizes are converted to ems
+        let px = if font.size == 0 {
+            font.line_height as f32
+        } else {
+            font.size.abs() as f32
+        };
+        let width = font.width as f32;
+        let height = font.height as f32;
+
+        let mut char_glyphs = HashMap::new();
+        let mut glyph_data = HashMap::new();
+        for c in &font.chars {
+            if c.page as usize >= pages.len() {
+                return Err(Error::InvalidBmfont);
+            }
+
+            let u = c.x as f32 / width;
+            let v = c.y as f32 / height;
+            let bottom = c.y_offset + c.height as i32 - font.base as i32;
+            glyph_data.insert(
+                c.id,
+                CharData {
+                    uvs: Vec4::new(
+                        u,
+                        v,
+                        u + c.width as f32 / width,
+                        v + c.height as f32 / height,
+                    ),
+                    bounds: Vec2::new(c.width as f32, c.height as f32) / px,
+                    bearing: Vec2::new(c.x_offset as f32, bottom as f32) / px,
+                    advance: c.x_advance as f32 / px,
+                    page: c.page,
+                },
+            );
+            if let Some(ch) = std::char::from_u32(c.id) {
+                char_glyphs.insert(ch, c.id);
+            }
+        }
+
+        let kerning = font
+            .kerning
+            .iter()
+            .map(|(first, second, amount)| ((*first, *second), *amount as f32 / px))
+            .collect();
+
+        self.create_font(FontData {
+            height: font.line_height as f32 / px,
+            line_gap: 0.0,
+            ascender: font.base as f32 / px,
+            descender: (font.base as f32 - font.line_height as f32) / px,
+            texture_data: pages.iter().map(|p| p.as_slice()).collect(),
+            texture_width: font.width,
+            texture_height: font.height,
+            ligatures: HashMap::new(),
+            sdf_range: None,
+            char_glyphs,
+            glyph_data,
+            kerning,
+        })
+    }
+}
+
+fn load_font(bytes: &[u8]) -> Result<BmFont> {
+    if bytes.starts_with(b"BMF") {
+        load_binary(bytes).ok_or(Error::InvalidBmfont)
+    } else {
+        let text = std::str::from_utf8(bytes).map_err(|_| Error::InvalidBmfont)?;
+        load_text(text).ok_or(Error::InvalidBmfont)
+    }
+}
+
+fn load_text(text: &str) -> Option<BmFont> {
+    let mut font = BmFont::default();
+
+    for line in text.lines() {
+        let mut tokens = tokenize(line).into_iter();
+        let tag = match tokens.next() {
+            Some(t) => t,
+            None => continue,
+        };
+        let values: HashMap<_, _> = tokens
+            .filter_map(|t| {
+                let i = t.find('=')?;
+                Some((t[..i].to_string(), t[i + 1..].trim_matches('"').to_string()))
+            })
+            .collect();
+        let int = |key: &str| values.get(key).and_then(|v| v.parse::<i32>().ok());
+        let uint = |key: &str| int(key).map(|v| v.max(0) as u32);
+
+        match tag.as_str() {
+            "info" => font.size = int("size").unwrap_or(0),
+            "common" => {
+                font.line_height = uint("lineHeight")?;
+                font.base = uint("base")?;
+                font.width = uint("scaleW")?;
+                font.height = uint("scaleH")?;
+                font.packed = int("packed") == Some(1);
+                font.alpha_channel = uint("alphaChnl").unwrap_or(0) as u8;
+            }
+            "page" => {
+                let id = uint("id")? as usize;
+                if font.pages.len() <= id {
+                    font.pages.resize(id + 1, String::new());
+                }
+                font.pages[id] = values.get("file")?.clone();
+            }
+            "char" => font.chars.push(BmChar {
+                id: uint("id")?,
+                x: uint("x")?,
+                y: uint("y")?,
+                width: uint("width")?,
+                height: uint("height")?,
+                x_offset: int("xoffset")?,
+                y_offset: int("yoffset")?,
+                x_advance: int("xadvance")?,
+                page: uint("page").unwrap_or(0),
+            }),
+            "kerning" => font
+                .kerning
+                .push((uint("first")?, uint("second")?, int("amount")?)),
+            _ => (),
+        }
+    }
+
+    Some(font)
+}
+
+// splits line on spaces outside of quotes
+fn tokenize(line: &str) -> Vec<String> {
+    let mut tokens = vec![];
+    let mut token = String::new();
+    let mut quoted = false;
+
+    for c in line.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                token.push(c);
+            }
+            ' ' | '\t' if !quoted => {
+                if !token.is_empty() {
+                    tokens.push(token);
+                    token = String::new();
+                }
+            }
+            _ => token.push(c),
+        }
+    }
+    if !token.is_empty() {
+        tokens.push(token);
+    }
+
+    tokens
+}
+
+// binary format version 3
+fn load_binary(bytes: &[u8]) -> Option<BmFont> {
+    if bytes.get(3) != Some(&3) {
+        return None;
+    }
+
+    let mut font = BmFont::default();
+    let mut rest = &bytes[4..];
+    while !rest.is_empty() {
+        let kind = rest[0];
+        let size = u32_at(rest, 1)? as usize;
+        let block = rest.get(5..5 + size)?;
+        rest = &rest[5 + size..];
+
+        match kind {
+            // info
+            1 => font.size = i32::from(i16_at(block, 0)?),
+            // common
+            2 => {
+                font.line_height = u32::from(u16_at(block, 0)?);
+                font.base = u32::from(u16_at(block, 2)?);
+                font.width = u32::from(u16_at(block, 4)?);
+                font.height = u32::from(u16_at(block, 6)?);
+                font.packed = block.get(10)? & 1 != 0;
+                font.alpha_channel = *block.get(11)?;
+            }
+            // null terminated page names
+            3 => {
+                font.pages = block
+                    .split(|b| *b == 0)
+                    .filter(|name| !name.is_empty())
+                    .map(|name| String::from_utf8_lossy(name).into_owned())
+                    .collect();
+            }
+            // chars
+            4 => {
+                for c in block.chunks_exact(20) {
+                    font.chars.push(BmChar {
+                        id: u32_at(c, 0)?,
+                        x: u32::from(u16_at(c, 4)?),
+                        y: u32::from(u16_at(c, 6)?),
+                        width: u32::from(u16_at(c, 8)?),
+                        height: u32::from(u16_at(c, 10)?),
+                        x_offset: i32::from(i16_at(c, 12)?),
+                        y_offset: i32::from(i16_at(c, 14)?),
+                        x_advance: i32::from(i16_at(c, 16)?),
+                        page: u32::from(c[18]),
+                    });
+                }
+            }
+            // kerning pairs
+            5 => {
+                for k in block.chunks_exact(10) {
+                    font.kerning
+                        .push((u32_at(k, 0)?, u32_at(k, 4)?, i32::from(i16_at(k, 8)?)));
+                }
+            }
+            _ => return None,
+        }
+    }
+
+    Some(font)
+}
+
+fn u32_at(bytes: &[u8], i: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(i..i + 4)?.try_into().ok()?))
+}
+
+fn u16_at(bytes: &[u8], i: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(bytes.get(i..i + 2)?.try_into().ok()?))
+}
+
+fn i16_at(bytes: &[u8], i: usize) -> Option<i16> {
+    Some(i16::from_le_bytes(bytes.get(i..i + 2)?.try_into().ok()?))
+}
+
+// uses the alpha channel if it has glyphs
+// and the red channel otherwise
+fn coverage(data: &[u8], format: Format, alpha_channel: u8) -> Vec<u8> {
+    let alpha = alpha_channel != CHANNEL_ZERO && alpha_channel != CHANNEL_ONE;
+    match format {
+        Format::Rgba | Format::Srgba if alpha => data.chunks(4).map(|p| p[3]).collect(),
+        Format::Rgba | Format::Srgba => data.chunks(4).map(|p| p[0]).collect(),
+        Format::Rgb | Format::Srgb => data.chunks(3).map(|p| p[0]).collect(),
+        _ => data.to_vec(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::load_binary;
+    use super::load_text;
+
+    #[test]
+    fn text_and_binary() {
+        let text = load_text(
+            "info face=\"Pixel Font\" size=-8\n\
+             common lineHeight=10 base=8 scaleW=64 scaleH=64 pages=1 packed=0\n\
+             page id=0 file=\"font 0.png\"\n\
+             char id=65 x=1 y=2 width=5 height=7 xoffset=0 yoffset=1 xadvance=6 page=0 chnl=15\n\
+             kerning first=65 second=65 amount=-1\n",
+        )
+        .unwrap();
+
+        let mut bytes = b"BMF\x03".to_vec();
+        let mut block = |kind: u8, data: &[u8]| {
+            bytes.push(kind);
+            bytes.extend(&(data.len() as u32).to_le_bytes());
+            bytes.extend(data);
+        };
+        block(1, &[0xf8, 0xff]);
+        block(2, &[10, 0, 8, 0, 64, 0, 64, 0, 1, 0, 0, 0, 0, 0, 0]);
+        block(3, b"font 0.png\0");
+        block(
+            4,
+            &[65, 0, 0, 0, 1, 0, 2, 0, 5, 0, 7, 0, 0, 0, 1, 0, 6, 0, 0, 15],
+        );
+        block(5, &[65, 0, 0, 0, 65, 0, 0, 0, 0xff, 0xff]);
+        let binary = load_binary(&bytes).unwrap();
+
+        for font in &[text, binary] {
+            assert_eq!(font.size, -8);
+            assert_eq!((font.line_height, font.base), (10, 8));
+            assert_eq!(font.pages, vec!["font 0.png".to_string()]);
+            assert_eq!(font.chars.len(), 1);
+            assert_eq!(font.chars[0].id, 65);
+            assert_eq!((font.chars[0].x, font.chars[0].y), (1, 2));
+            assert_eq!(font.chars[0].y_offset, 1);
+            assert_eq!(font.kerning, vec![(65, 65, -1)]);
+        }
+    }
+}
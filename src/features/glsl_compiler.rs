@@ -41,6 +41,7 @@ pub(crate) fn compile(src: &str) -> Result<(Vec<u8>, Vec<u8>, [u8; 4])> {
             "lined_triangles" => 0,
             "filled_triangles" => 1,
             "lines" => 2,
+            "points" => 3,
             "" => {
                 return Err(Error::InvalidGlsl(
                     "shape mode not set. set with '#define SHAPE <mode>'".to_string(),
@@ -125,6 +126,7 @@ fn compile_frag(src: &str, defines: &Defines) -> Result<Vec<u8>> {
     let objects_glsl = include_str!("../../shaders/glsl/addon-objects.glsl");
     let shadow_glsl = include_str!("../../shaders/glsl/addon-shadow.glsl");
     let srgb_glsl = include_str!("../../shaders/glsl/addon-srgb.glsl");
+    let uv_glsl = include_str!("../../shaders/glsl/addon-uv.glsl");
 
     // create real glsl code
     let mut real_src = "#version 450\n".to_string();
@@ -140,6 +142,9 @@ fn compile_frag(src: &str, defines: &Defines) -> Result<Vec<u8>> {
     if defines.exists("SHADOW") {
         real_src.push_str(shadow_glsl);
     }
+    if defines.exists("UV") {
+        real_src.push_str(uv_glsl);
+    }
 
     let pre_line_count = real_src.lines().count();
 
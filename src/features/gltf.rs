@@ -5,32 +5,69 @@
 
 #![cfg(feature = "gltf")]
 
+use gltf_dep::animation;
+use gltf_dep::animation::util::ReadOutputs;
 use gltf_dep::buffer;
+use gltf_dep::camera::Projection as GltfProjection;
 use gltf_dep::image;
+use gltf_dep::json;
+use gltf_dep::json::Value;
+use gltf_dep::khr_lights_punctual::Kind;
+use gltf_dep::material::AlphaMode as GltfAlphaMode;
 use gltf_dep::mesh::Mode;
+use gltf_dep::texture::MagFilter;
+use gltf_dep::texture::MinFilter;
+use gltf_dep::texture::WrappingMode;
+use gltf_dep::Glb;
 use gltf_dep::Gltf;
 use gltf_dep::Material as GltfMaterial;
 use gltf_dep::Node;
+use gltf_dep::Primitive as GltfPrimitive;
+use gltf_dep::Scene;
+use gltf_dep::Texture as GltfTexture;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
 
+use crate::color::Rgbf;
 use crate::duku::Duku;
 use crate::error::Error;
 use crate::error::Result;
 use crate::image::ColorSpace;
+use crate::image::Filter;
 use crate::image::Mips;
 use crate::image::Texture;
+use crate::image::Wrap;
 use crate::math::Mat4;
 use crate::math::Quat;
 use crate::math::Vec2;
 use crate::math::Vec3;
+use crate::math::Vec4;
+use crate::mesh::AnimationChannel;
+use crate::mesh::AnimationClip;
+use crate::mesh::ChannelValues;
+use crate::mesh::Interpolation;
+use crate::mesh::Joint;
 use crate::mesh::Mesh;
 use crate::mesh::Model;
 use crate::mesh::ModelNode;
+use crate::mesh::MorphTarget;
+use crate::mesh::Pose;
+use crate::mesh::Primitive;
+use crate::mesh::Skin;
+use crate::pipeline::AlphaMode;
 use crate::pipeline::Material;
+use crate::pipeline::TextureSlot;
+use crate::renderer::Camera;
+use crate::renderer::Light;
+use crate::renderer::Projection;
 use crate::resources::Handle;
 
+// mesh and primitive index, with the node
+// index for meshes copied for a node
+type MeshKey = (usize, usize, Option<usize>);
+
 /// Y axis for the gltf model
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum YAxis {
@@ -42,33 +79,85 @@ pub enum YAxis {
 
 impl Duku {
     /// Create a model from a GLTF file
+    ///
+    /// Loads the file's default scene. Textures
+    /// use the sampler's S wrap mode for both axes.
     pub fn create_model_gltf(
         &mut self,
         path: impl AsRef<Path>,
         options: Option<YAxis>,
     ) -> Result<Handle<Model>> {
-        let p = path.as_ref();
-        let bytes = fs::read(p)?;
-        self.create_model_gltf_bytes(
+        self.load_model_gltf(path.as_ref(), options, None)
+    }
+
+    /// Create a model from a scene in a GLTF file
+    ///
+    /// Only the meshes and materials used
+    /// in the scene are loaded.
+    pub fn create_model_gltf_scene(
+        &mut self,
+        path: impl AsRef<Path>,
+        options: Option<YAxis>,
+        scene: usize,
+    ) -> Result<Handle<Model>> {
+        self.load_model_gltf(path.as_ref(), options, Some(scene))
+    }
+
+    /// Create a model from GLTF bytes
+    ///
+    /// `root` is used for relative file path loading
+    pub fn create_model_gltf_bytes(
+        &mut self,
+        bytes: &[u8],
+        root: &str,
+        options: Option<YAxis>,
+    ) -> Result<Handle<Model>> {
+        self.load_model_gltf_bytes(bytes, root, options, None)
+    }
+
+    /// Create a model from a scene in GLTF bytes
+    ///
+    /// `root` is used for relative file path loading
+    pub fn create_model_gltf_scene_bytes(
+        &mut self,
+        bytes: &[u8],
+        root: &str,
+        options: Option<YAxis>,
+        scene: usize,
+    ) -> Result<Handle<Model>> {
+        self.load_model_gltf_bytes(bytes, root, options, Some(scene))
+    }
+
+    fn load_model_gltf(
+        &mut self,
+        path: &Path,
+        options: Option<YAxis>,
+        scene: Option<usize>,
+    ) -> Result<Handle<Model>> {
+        let bytes = fs::read(path)?;
+        self.load_model_gltf_bytes(
             &bytes,
-            p.parent()
+            path.parent()
                 .unwrap_or_else(|| Path::new("./"))
                 .to_str()
                 .expect("bad path"),
             options,
+            scene,
         )
     }
 
-    /// Create a model from GLTF bytes
-    ///
-    /// `root` is used for relative file path loading
-    pub fn create_model_gltf_bytes(
+    fn load_model_gltf_bytes(
         &mut self,
         bytes: &[u8],
         root: &str,
         options: Option<YAxis>,
+        scene: Option<usize>,
     ) -> Result<Handle<Model>> {
         let gltf = Gltf::from_slice(bytes).map_err(|_| Error::InvalidGltf)?;
+        let raw = raw_json(bytes)?;
+
+        let scene = pick_scene(&gltf, scene)?;
+        let (mesh_users, used_materials) = used_in_scene(&gltf, scene.as_ref());
 
         // load buffers
         let mut buffers = vec![];
@@ -125,10 +214,17 @@ impl Duku {
         let mut has_normal_map = false;
         let mut materials = HashMap::new();
         for material in gltf.materials() {
+            if !used_materials.contains(&material.index().unwrap_or(0)) {
+                continue;
+            }
             if material.normal_texture().is_some() {
                 has_normal_map = true;
             }
-            let mat = self.load_material(&mut texture_data, &material)?;
+            let raw_material = match material.index() {
+                Some(i) => &raw["materials"][i],
+                None => &Value::Null,
+            };
+            let mat = self.load_material(&mut texture_data, &material, raw_material)?;
             materials.insert(material.index().unwrap_or(0), mat);
         }
 
@@ -140,68 +236,123 @@ impl Duku {
 
         let mut meshes = HashMap::new();
         for mesh in gltf.meshes() {
+            let users = match mesh_users.get(&mesh.index()) {
+                Some(u) => u,
+                None => continue,
+            };
             for primitive in mesh.primitives() {
-                let reader = primitive.reader(|buffer| Some(&buffers[buffer.index()]));
-
-                if primitive.mode() != Mode::Triangles {
-                    return Err(Error::UnsupportedPrimitive);
-                }
-
-                let mut vertices = vec![];
-                let mut normals = vec![];
-                let mut uvs = vec![];
-                let mut indices = vec![];
-
-                if let Some(ps) = reader.read_positions() {
-                    vertices.extend(ps.map(|p| Vec3::new(p[0], sy * p[1], -p[2])));
-                }
-
-                if let Some(ns) = reader.read_normals() {
-                    normals.extend(ns.map(|n| Vec3::new(n[0], sy * n[1], -n[2])));
-                }
-
-                if let Some(ts) = reader.read_tex_coords(0) {
-                    uvs.extend(ts.into_f32().map(|t| Vec2::new(t[0], t[1])));
-                }
-
-                if let Some(is) = reader.read_indices() {
-                    let ccw: Vec<_> = is.into_u32().collect();
-                    for chunk in ccw.chunks(3) {
-                        indices.extend(&[chunk[0], chunk[2], chunk[1]]);
-                    }
-                }
+                let data = read_primitive(&primitive, &buffers, sy);
 
                 let msh = self.create_mesh();
                 {
                     let mut m = msh.write();
-                    m.vertices = vertices;
-                    m.uvs = uvs;
-                    m.indices = indices;
+                    m.vertices = data.vertices;
+                    m.uvs = data.uvs;
+                    m.second_uvs = data.second_uvs;
+                    m.joints = data.joints;
+                    m.weights = data.weights;
+                    m.indices = data.indices;
+                    m.primitive = data.primitive;
+                    m.morph_weights = mesh.weights().map(|w| w.to_vec()).unwrap_or_default();
+                    m.morph_weights.resize(data.morph_targets.len(), 0.0);
+                    m.morph_targets = data.morph_targets;
 
-                    if normals.is_empty() {
+                    // only triangles have surfaces for normals
+                    if !data.normals.is_empty() {
+                        m.normals = data.normals;
+                    } else if data.primitive == Primitive::Triangles {
                         m.calculate_normals();
-                    } else {
-                        m.normals = normals;
                     }
-                    if has_normal_map {
+                    if has_normal_map && data.primitive == Primitive::Triangles {
                         m.calculate_tangents();
                     }
                 }
 
-                meshes.insert((mesh.index(), primitive.index()), msh);
+                // nodes get their own copy of morphed
+                // meshes to have separate weights
+                if !msh.read().morph_targets.is_empty() && users.len() > 1 {
+                    for node in &users[1..] {
+                        let copy = self.copy_mesh(&msh);
+                        meshes.insert((mesh.index(), primitive.index(), Some(*node)), copy);
+                    }
+                }
+                meshes.insert((mesh.index(), primitive.index(), None), msh);
             }
         }
 
-        // load scenes
+        // find node parents
+        let mut parents = HashMap::new();
+        for node in gltf.nodes() {
+            for child in node.children() {
+                parents.insert(child.index(), node.index());
+            }
+        }
+        let node_matrices: Vec<_> = gltf.nodes().map(|n| node_pose(&n).matrix()).collect();
+        let world_matrix = |mut index: usize| {
+            let mut matrix = node_matrices[index];
+            while let Some(parent) = parents.get(&index) {
+                matrix = node_matrices[*parent] * matrix;
+                index = *parent;
+            }
+            matrix
+        };
+
+        // load skins
+        let mut skins = vec![];
+        for skin in gltf.skins() {
+            let reader = skin.reader(|buffer| Some(&buffers[buffer.index()]));
+            let inverse_binds: Vec<_> = match reader.read_inverse_bind_matrices() {
+                Some(ms) => ms.map(convert_matrix).collect(),
+                None => vec![],
+            };
+            let nodes: Vec<_> = skin.joints().map(|j| j.index()).collect();
+
+            let joints = skin
+                .joints()
+                .enumerate()
+                .map(|(i, node)| {
+                    let parent_node = parents.get(&node.index());
+                    let parent = parent_node.and_then(|p| nodes.iter().position(|n| n == p));
+                    let pose = node_pose(&node);
+
+                    let mut joint = Joint::new(node.index(), parent);
+                    joint.pose = pose;
+                    joint.rest = pose;
+                    joint.inverse_bind =
+                        inverse_binds.get(i).copied().unwrap_or_else(Mat4::identity);
+                    if let (None, Some(p)) = (parent, parent_node) {
+                        joint.root = world_matrix(*p);
+                    }
+                    joint
+                })
+                .collect();
+
+            let skn = self.create_skin()?;
+            skn.write().joints = joints;
+            skins.push(skn);
+        }
+
+        // load animations
+        let animations = gltf
+            .animations()
+            .map(|a| load_animation(&a, &buffers))
+            .collect::<Result<_>>()?;
+
+        // load scene
         let mut nodes = vec![];
-        for scene in gltf.scenes() {
-            for node in scene.nodes() {
-                nodes.push(load_node(&node, &meshes, &materials)?);
+        if let Some(s) = &scene {
+            for node in s.nodes() {
+                nodes.push(load_node(&node, &meshes, &materials, &skins)?);
             }
         }
 
         let model = self.create_model();
-        model.write().nodes = nodes;
+        {
+            let mut m = model.write();
+            m.nodes = nodes;
+            m.skins = skins;
+            m.animations = animations;
+        }
 
         Ok(model)
     }
@@ -210,6 +361,7 @@ impl Duku {
         &mut self,
         texture_data: &mut HashMap<usize, (&str, Vec<u8>)>,
         material: &GltfMaterial<'_>,
+        raw: &Value,
     ) -> Result<Handle<Material>> {
         let mut textures = HashMap::new();
         let pbr = material.pbr_metallic_roughness();
@@ -220,66 +372,103 @@ impl Duku {
         let roughness = pbr.roughness_factor();
         let emissive = material.emissive_factor();
 
+        let extensions = MaterialExtensions::new(raw);
+        let raw_albedo = &raw["pbrMetallicRoughness"]["baseColorTexture"];
+        let raw_met_rough = &raw["pbrMetallicRoughness"]["metallicRoughnessTexture"];
+        let raw_normal = &raw["normalTexture"];
+        let raw_occ = &raw["occlusionTexture"];
+        let raw_emissive = &raw["emissiveTexture"];
+
         // textures
+        let mut uv_sets = vec![];
         let albedo_tex = if let Some(info) = pbr.base_color_texture() {
+            uv_sets.push((TextureSlot::Albedo, tex_coord(raw_albedo, info.tex_coord())));
             Some(self.load_texture(
                 &mut textures,
                 texture_data,
                 ColorSpace::Srgb,
-                info.texture().index(),
+                &info.texture(),
             )?)
         } else {
             None
         };
         let emissive_tex = if let Some(info) = material.emissive_texture() {
+            uv_sets.push((
+                TextureSlot::Emissive,
+                tex_coord(raw_emissive, info.tex_coord()),
+            ));
             Some(self.load_texture(
                 &mut textures,
                 texture_data,
                 ColorSpace::Srgb,
-                info.texture().index(),
+                &info.texture(),
             )?)
         } else {
             None
         };
         let met_rough_tex = if let Some(info) = pbr.metallic_roughness_texture() {
+            uv_sets.push((
+                TextureSlot::MetalnessRoughness,
+                tex_coord(raw_met_rough, info.tex_coord()),
+            ));
             Some(self.load_texture(
                 &mut textures,
                 texture_data,
                 ColorSpace::Linear,
-                info.texture().index(),
+                &info.texture(),
             )?)
         } else {
             None
         };
         let normal_tex = if let Some(info) = material.normal_texture() {
+            uv_sets.push((TextureSlot::Normal, tex_coord(raw_normal, info.tex_coord())));
             Some(self.load_texture(
                 &mut textures,
                 texture_data,
                 ColorSpace::Linear,
-                info.texture().index(),
+                &info.texture(),
             )?)
         } else {
             None
         };
         let occ_tex = if let Some(info) = material.occlusion_texture() {
+            uv_sets.push((
+                TextureSlot::AmbientOcclusion,
+                tex_coord(raw_occ, info.tex_coord()),
+            ));
             Some(self.load_texture(
                 &mut textures,
                 texture_data,
                 ColorSpace::Linear,
-                info.texture().index(),
+                &info.texture(),
             )?)
         } else {
             None
         };
 
+        // alpha mode
+        let alpha_mode = match material.alpha_mode() {
+            GltfAlphaMode::Opaque => AlphaMode::Opaque,
+            GltfAlphaMode::Mask => AlphaMode::Mask(material.alpha_cutoff()),
+            GltfAlphaMode::Blend => AlphaMode::Blend,
+        };
+
         // build material
         let mat = self.create_material()?;
         {
             let mut m = mat.write();
+            m.alpha_mode(alpha_mode);
+            m.double_sided(material.double_sided());
             m.albedo_color(albedo);
             m.metalness(metalness);
             m.roughness(roughness);
             m.emissive(emissive);
+            m.emissive_strength(extensions.emissive_strength);
+            m.clearcoat(extensions.clearcoat, extensions.clearcoat_roughness);
+            m.unshaded(material.unlit());
+            for (slot, offset, rotation, scale) in extensions.transforms {
+                m.texture_transform(slot, offset, rotation, scale);
+            }
             if let Some(tex) = albedo_tex {
                 m.albedo_texture(tex);
             }
@@ -295,6 +484,9 @@ impl Duku {
             if let Some(tex) = occ_tex {
                 m.ambient_occlusion_texture(tex);
             }
+            for (slot, set) in uv_sets {
+                m.uv_set(slot, set);
+            }
         }
         Ok(mat)
     }
@@ -304,8 +496,9 @@ impl Duku {
         textures: &mut HashMap<usize, Handle<Texture>>,
         texture_data: &mut HashMap<usize, (&str, Vec<u8>)>,
         color_space: ColorSpace,
-        index: usize,
+        texture: &GltfTexture<'_>,
     ) -> Result<Handle<Texture>> {
+        let index = texture.index();
         if let Some(tex) = textures.get(&index) {
             Ok(tex.clone())
         } else {
@@ -319,38 +512,301 @@ impl Duku {
                 }
                 _ => return Err(Error::UnsupportedMimeType(mime_type.to_string())),
             };
+
+            // set sampler
+            let sampler = texture.sampler();
+            let filter = match (sampler.mag_filter(), sampler.min_filter()) {
+                (Some(MagFilter::Nearest), _)
+                | (None, Some(MinFilter::Nearest))
+                | (None, Some(MinFilter::NearestMipmapNearest))
+                | (None, Some(MinFilter::NearestMipmapLinear)) => Filter::Nearest,
+                _ => Filter::Linear,
+            };
+            // samplers have one wrap mode for both
+            // axes, so wrap T is ignored
+            let wrap = match sampler.wrap_s() {
+                WrappingMode::ClampToEdge => Wrap::ClampEdge,
+                WrappingMode::MirroredRepeat => Wrap::MirroredRepeat,
+                WrappingMode::Repeat => Wrap::Repeat,
+            };
+            tex.write().set_sampler(filter, wrap);
+
             textures.insert(index, tex.clone());
             Ok(tex)
         }
     }
+
+    fn copy_mesh(&mut self, mesh: &Handle<Mesh>) -> Handle<Mesh> {
+        let copy = self.create_mesh();
+        {
+            let src = mesh.read();
+            let mut m = copy.write();
+            m.vertices = src.vertices.clone();
+            m.uvs = src.uvs.clone();
+            m.second_uvs = src.second_uvs.clone();
+            m.normals = src.normals.clone();
+            m.tangents = src.tangents.clone();
+            m.colors = src.colors.clone();
+            m.textures = src.textures.clone();
+            m.joints = src.joints.clone();
+            m.weights = src.weights.clone();
+            m.morph_targets = src.morph_targets.clone();
+            m.morph_weights = src.morph_weights.clone();
+            m.indices = src.indices.clone();
+            m.primitive = src.primitive;
+        }
+        copy
+    }
+}
+
+// material extensions the gltf crate doesn't read
+struct MaterialExtensions {
+    emissive_strength: f32,
+    clearcoat: f32,
+    clearcoat_roughness: f32,
+    transforms: Vec<(TextureSlot, Vec2, f32, Vec2)>,
+}
+
+impl MaterialExtensions {
+    fn new(raw: &Value) -> Self {
+        let extensions = &raw["extensions"];
+        let emissive_strength = extensions["KHR_materials_emissive_strength"]["emissiveStrength"]
+            .as_f64()
+            .unwrap_or(1.0) as f32;
+        let clearcoat = &extensions["KHR_materials_clearcoat"];
+
+        // each texture has its own transform
+        let pbr = &raw["pbrMetallicRoughness"];
+        let transforms = [
+            (TextureSlot::Albedo, &pbr["baseColorTexture"]),
+            (
+                TextureSlot::MetalnessRoughness,
+                &pbr["metallicRoughnessTexture"],
+            ),
+            (TextureSlot::Normal, &raw["normalTexture"]),
+            (TextureSlot::AmbientOcclusion, &raw["occlusionTexture"]),
+            (TextureSlot::Emissive, &raw["emissiveTexture"]),
+        ]
+        .iter()
+        .map(|(slot, info)| (*slot, &info["extensions"]["KHR_texture_transform"]))
+        .filter(|(_, t)| t.is_object())
+        .map(|(slot, t)| {
+            let offset = number_pair(&t["offset"], 0.0);
+            let scale = number_pair(&t["scale"], 1.0);
+            let rotation = t["rotation"].as_f64().unwrap_or(0.0) as f32;
+            (slot, offset, rotation, scale)
+        })
+        .collect();
+
+        Self {
+            emissive_strength,
+            clearcoat: clearcoat["clearcoatFactor"].as_f64().unwrap_or(0.0) as f32,
+            clearcoat_roughness: clearcoat["clearcoatRoughnessFactor"]
+                .as_f64()
+                .unwrap_or(0.0) as f32,
+            transforms,
+        }
+    }
+}
+
+// picks scene, falling back to the default or first one
+fn pick_scene(gltf: &Gltf, index: Option<usize>) -> Result<Option<Scene<'_>>> {
+    Ok(match index {
+        Some(i) => Some(gltf.scenes().nth(i).ok_or(Error::InvalidGltf)?),
+        None => gltf.default_scene().or_else(|| gltf.scenes().next()),
+    })
+}
+
+// finds meshes with the nodes that use
+// them and materials used in the scene
+fn used_in_scene(
+    gltf: &Gltf,
+    scene: Option<&Scene<'_>>,
+) -> (HashMap<usize, Vec<usize>>, HashSet<usize>) {
+    let mut meshes = HashMap::new();
+    if let Some(s) = scene {
+        for node in s.nodes() {
+            collect_meshes(&node, &mut meshes);
+        }
+    }
+    let materials = gltf
+        .meshes()
+        .filter(|m| meshes.contains_key(&m.index()))
+        .flat_map(|m| m.primitives())
+        .map(|p| p.material().index().unwrap_or(0))
+        .collect();
+    (meshes, materials)
+}
+
+// vertex data read from a glTF primitive
+struct PrimitiveData {
+    vertices: Vec<Vec3>,
+    normals: Vec<Vec3>,
+    uvs: Vec<Vec2>,
+    second_uvs: Vec<Vec2>,
+    joints: Vec<[u32; 4]>,
+    weights: Vec<Vec4>,
+    morph_targets: Vec<MorphTarget>,
+    indices: Vec<u32>,
+    primitive: Primitive,
+}
+
+fn read_primitive(primitive: &GltfPrimitive<'_>, buffers: &[Vec<u8>], sy: f32) -> PrimitiveData {
+    let reader = primitive.reader(|buffer| Some(&buffers[buffer.index()]));
+    let convert = |o: [f32; 3]| Vec3::new(o[0], sy * o[1], -o[2]);
+
+    let vertices: Vec<_> = match reader.read_positions() {
+        Some(ps) => ps.map(convert).collect(),
+        None => vec![],
+    };
+    let normals = match reader.read_normals() {
+        Some(ns) => ns.map(convert).collect(),
+        None => vec![],
+    };
+    let uvs = match reader.read_tex_coords(0) {
+        Some(ts) => ts.into_f32().map(|t| Vec2::new(t[0], t[1])).collect(),
+        None => vec![],
+    };
+    let second_uvs = match reader.read_tex_coords(1) {
+        Some(ts) => ts.into_f32().map(|t| Vec2::new(t[0], t[1])).collect(),
+        None => vec![],
+    };
+    let joints = match reader.read_joints(0) {
+        Some(js) => js
+            .into_u16()
+            .map(|j| {
+                [
+                    u32::from(j[0]),
+                    u32::from(j[1]),
+                    u32::from(j[2]),
+                    u32::from(j[3]),
+                ]
+            })
+            .collect(),
+        None => vec![],
+    };
+    let weights = match reader.read_weights(0) {
+        Some(ws) => ws.into_f32().map(Vec4::from).collect(),
+        None => vec![],
+    };
+
+    let morph_targets = reader
+        .read_morph_targets()
+        .map(|(ps, ns, ts)| MorphTarget {
+            positions: ps.map(|i| i.map(convert).collect()).unwrap_or_default(),
+            normals: ns.map(|i| i.map(convert).collect()).unwrap_or_default(),
+            tangents: ts.map(|i| i.map(convert).collect()).unwrap_or_default(),
+        })
+        .collect();
+
+    // non-indexed primitives use the vertices in order
+    let ccw: Vec<_> = match reader.read_indices() {
+        Some(is) => is.into_u32().collect(),
+        None => (0..vertices.len() as u32).collect(),
+    };
+    let (primitive_type, list) = assemble_indices(primitive.mode(), &ccw);
+    let mut indices = vec![];
+    if primitive_type == Primitive::Triangles {
+        for chunk in list.chunks(3) {
+            indices.extend(&[chunk[0], chunk[2], chunk[1]]);
+        }
+    } else {
+        indices = list;
+    }
+
+    PrimitiveData {
+        vertices,
+        normals,
+        uvs,
+        second_uvs,
+        joints,
+        weights,
+        morph_targets,
+        indices,
+        primitive: primitive_type,
+    }
 }
 
 fn load_node(
     node: &Node<'_>,
-    meshes: &HashMap<(usize, usize), Handle<Mesh>>,
+    meshes: &HashMap<MeshKey, Handle<Mesh>>,
     materials: &HashMap<usize, Handle<Material>>,
+    skins: &[Handle<Skin>],
 ) -> Result<ModelNode> {
-    // get transform matrix
-    let matrix = {
-        let (t, r, s) = node.transform().decomposed();
-        Mat4::translation([t[0], t[1], -t[2]])
-            * Mat4::from(Quat::new(r[0], r[1], -r[2], -r[3]))
-            * Mat4::scale(s)
+    // get transform
+    let pose = node_pose(node);
+
+    // get skin
+    let skin = match node.skin() {
+        Some(s) => Some(skins.get(s.index()).cloned().ok_or(Error::InvalidGltf)?),
+        None => None,
     };
 
+    // get morph weights
+    let morph_weights = node
+        .weights()
+        .or_else(|| node.mesh().and_then(|m| m.weights()))
+        .map(|w| w.to_vec())
+        .unwrap_or_default();
+
+    // get light, shining forward in local space
+    let light = node.light().map(|l| {
+        let [r, g, b] = l.color();
+        let color = Rgbf::new(r, g, b);
+        let mut light = match l.kind() {
+            Kind::Directional => Light::directional(color, Vec3::forward()),
+            Kind::Point => Light::point(color, Vec3::default()),
+            Kind::Spot {
+                inner_cone_angle,
+                outer_cone_angle,
+            } => Light::spot(
+                color,
+                Vec3::default(),
+                Vec3::forward(),
+                inner_cone_angle.to_degrees(),
+                outer_cone_angle.to_degrees(),
+            ),
+        };
+        // intensity is in candela or lux,
+        // which the shaders use as is
+        light.brightness = l.intensity();
+        light.range = l.range().unwrap_or(0.0);
+        light
+    });
+
+    // get camera
+    let camera = node.camera().map(|c| match c.projection() {
+        GltfProjection::Perspective(p) => {
+            let mut camera = Camera::perspective(p.yfov().to_degrees().round() as u32);
+            camera.depth = p.zfar().unwrap_or(camera.depth);
+            camera
+        }
+        GltfProjection::Orthographic(o) => Camera::new(
+            Projection::Orthographic,
+            o.xmag() * 2.0,
+            o.ymag() * 2.0,
+            o.zfar(),
+            0,
+        ),
+    });
+
     // get mesh and material
     let mut ms = vec![];
     let mut mats = vec![];
     if let Some(mesh) = node.mesh() {
         for primitive in mesh.primitives() {
             let m = meshes
-                .get(&(mesh.index(), primitive.index()))
+                .get(&(mesh.index(), primitive.index(), Some(node.index())))
+                .or_else(|| meshes.get(&(mesh.index(), primitive.index(), None)))
                 .cloned()
                 .ok_or(Error::InvalidGltf)?;
             let mat = materials
                 .get(&primitive.material().index().unwrap_or(0))
                 .cloned()
                 .ok_or(Error::InvalidGltf)?;
+            if node.weights().is_some() {
+                m.write().morph_weights = morph_weights.clone();
+            }
             ms.push(m);
             mats.push(mat);
         }
@@ -359,12 +815,361 @@ fn load_node(
     // get children
     let children: Vec<_> = node
         .children()
-        .map(|n| load_node(&n, meshes, materials))
+        .map(|n| load_node(&n, meshes, materials, skins))
         .collect::<Result<_>>()?;
     Ok(ModelNode {
+        name: node.name().unwrap_or_default().to_string(),
+        mesh_name: node
+            .mesh()
+            .and_then(|m| m.name())
+            .unwrap_or_default()
+            .to_string(),
+        visible: true,
         meshes: ms,
         materials: mats,
+        index: node.index(),
+        rest: pose,
+        morph_weights,
         children,
-        matrix,
+        camera,
+        light,
+        pose,
+        skin,
     })
 }
+
+fn load_animation(
+    animation: &animation::Animation<'_>,
+    buffers: &[Vec<u8>],
+) -> Result<AnimationClip> {
+    let mut channels = vec![];
+    let mut duration: f32 = 0.0;
+
+    for channel in animation.channels() {
+        let reader = channel.reader(|buffer| Some(&buffers[buffer.index()]));
+        let times: Vec<_> = reader.read_inputs().ok_or(Error::InvalidGltf)?.collect();
+        let values = match reader.read_outputs().ok_or(Error::InvalidGltf)? {
+            ReadOutputs::Translations(ts) => {
+                ChannelValues::Translation(ts.map(|t| Vec3::new(t[0], t[1], -t[2])).collect())
+            }
+            ReadOutputs::Rotations(rs) => ChannelValues::Rotation(
+                rs.into_f32()
+                    .map(|r| Quat::new(r[0], r[1], -r[2], -r[3]))
+                    .collect(),
+            ),
+            ReadOutputs::Scales(ss) => ChannelValues::Scale(ss.map(Vec3::from).collect()),
+            ReadOutputs::MorphTargetWeights(ws) => {
+                ChannelValues::MorphWeights(ws.into_f32().collect())
+            }
+        };
+        let interpolation = match channel.sampler().interpolation() {
+            animation::Interpolation::Step => Interpolation::Step,
+            animation::Interpolation::Linear => Interpolation::Linear,
+            animation::Interpolation::CubicSpline => Interpolation::CubicSpline,
+        };
+
+        if let Some(last) = times.last() {
+            duration = duration.max(*last);
+        }
+
+        channels.push(AnimationChannel {
+            node: channel.target().node().index(),
+            interpolation,
+            times,
+            values,
+        });
+    }
+
+    Ok(AnimationClip {
+        name: animation.name().unwrap_or_default().to_string(),
+        channels,
+        duration,
+    })
+}
+
+// parses the JSON for extensions
+// the gltf crate doesn't read
+fn raw_json(bytes: &[u8]) -> Result<Value> {
+    let json = if bytes.starts_with(b"glTF") {
+        Glb::from_slice(bytes)
+            .map_err(|_| Error::InvalidGltf)?
+            .json
+            .into_owned()
+    } else {
+        bytes.to_vec()
+    };
+    json::deserialize::from_slice(&json).map_err(|_| Error::InvalidGltf)
+}
+
+// texture transforms can override the UV set
+fn tex_coord(info: &Value, default: u32) -> u32 {
+    info["extensions"]["KHR_texture_transform"]["texCoord"]
+        .as_u64()
+        .map(|t| t as u32)
+        .unwrap_or(default)
+}
+
+fn number_pair(value: &Value, default: f32) -> Vec2 {
+    let x = value[0].as_f64().map(|v| v as f32).unwrap_or(default);
+    let y = value[1].as_f64().map(|v| v as f32).unwrap_or(default);
+    Vec2::new(x, y)
+}
+
+fn collect_meshes(node: &Node<'_>, meshes: &mut HashMap<usize, Vec<usize>>) {
+    if let Some(mesh) = node.mesh() {
+        meshes.entry(mesh.index()).or_default().push(node.index());
+    }
+    for child in node.children() {
+        collect_meshes(&child, meshes);
+    }
+}
+
+// converts strips, fans and loops to lists
+fn assemble_indices(mode: Mode, indices: &[u32]) -> (Primitive, Vec<u32>) {
+    let n = indices.len();
+    match mode {
+        Mode::Triangles => (Primitive::Triangles, indices.to_vec()),
+        Mode::TriangleStrip => {
+            let mut list = vec![];
+            for i in 0..n.saturating_sub(2) {
+                // every other triangle is flipped to keep the winding
+                if i % 2 == 0 {
+                    list.extend(&[indices[i], indices[i + 1], indices[i + 2]]);
+                } else {
+                    list.extend(&[indices[i + 1], indices[i], indices[i + 2]]);
+                }
+            }
+            (Primitive::Triangles, list)
+        }
+        Mode::TriangleFan => {
+            let mut list = vec![];
+            for i in 1..n.saturating_sub(1) {
+                list.extend(&[indices[0], indices[i], indices[i + 1]]);
+            }
+            (Primitive::Triangles, list)
+        }
+        Mode::Lines => (Primitive::Lines, indices.to_vec()),
+        Mode::LineStrip | Mode::LineLoop => {
+            let mut list = vec![];
+            for i in 0..n.saturating_sub(1) {
+                list.extend(&[indices[i], indices[i + 1]]);
+            }
+            if mode == Mode::LineLoop && n > 2 {
+                list.extend(&[indices[n - 1], indices[0]]);
+            }
+            (Primitive::Lines, list)
+        }
+        Mode::Points => (Primitive::Points, indices.to_vec()),
+    }
+}
+
+fn node_pose(node: &Node<'_>) -> Pose {
+    let (t, r, s) = node.transform().decomposed();
+    Pose {
+        translation: Vec3::new(t[0], t[1], -t[2]),
+        rotation: Quat::new(r[0], r[1], -r[2], -r[3]),
+        scale: Vec3::from(s),
+    }
+}
+
+// converts matrix to the flipped z axis
+fn convert_matrix(m: [[f32; 4]; 4]) -> Mat4 {
+    let mut result = Mat4::columns(m[0], m[1], m[2], m[3]);
+    for col in 0..4 {
+        for row in 0..4 {
+            if (col == 2) != (row == 2) {
+                result[col][row] = -result[col][row];
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::assemble_indices;
+    use super::number_pair;
+    use super::read_primitive;
+    use super::tex_coord;
+    use super::used_in_scene;
+    use super::MaterialExtensions;
+    use crate::math::Vec2;
+    use crate::mesh::Primitive;
+    use crate::pipeline::TextureSlot;
+    use gltf_dep::json;
+    use gltf_dep::json::Value;
+    use gltf_dep::mesh::Mode;
+    use gltf_dep::Gltf;
+
+    #[test]
+    fn triangle_strip() {
+        let (primitive, list) = assemble_indices(Mode::TriangleStrip, &[0, 1, 2, 3]);
+        assert_eq!(primitive, Primitive::Triangles);
+        assert_eq!(list, vec![0, 1, 2, 2, 1, 3]);
+    }
+
+    #[test]
+    fn triangle_fan() {
+        let (primitive, list) = assemble_indices(Mode::TriangleFan, &[0, 1, 2, 3]);
+        assert_eq!(primitive, Primitive::Triangles);
+        assert_eq!(list, vec![0, 1, 2, 0, 2, 3]);
+    }
+
+    #[test]
+    fn line_loop() {
+        let (primitive, list) = assemble_indices(Mode::LineLoop, &[0, 1, 2]);
+        assert_eq!(primitive, Primitive::Lines);
+        assert_eq!(list, vec![0, 1, 1, 2, 2, 0]);
+    }
+
+    #[test]
+    fn texture_transform() {
+        let info: Value = json::deserialize::from_str(
+            r#"{
+                "index": 0,
+                "extensions": {
+                    "KHR_texture_transform": { "offset": [0.5, 0.25], "texCoord": 1 }
+                }
+            }"#,
+        )
+        .unwrap();
+        let transform = &info["extensions"]["KHR_texture_transform"];
+
+        let offset = number_pair(&transform["offset"], 0.0);
+        let scale = number_pair(&transform["scale"], 1.0);
+        assert_eq_delta!(offset.x, 0.5);
+        assert_eq_delta!(offset.y, 0.25);
+        assert_eq_delta!(scale.x, 1.0);
+        assert_eq!(tex_coord(&info, 0), 1);
+        assert_eq!(tex_coord(&Value::Null, 0), 0);
+    }
+
+    #[test]
+    fn material_extensions() {
+        let raw: Value = json::deserialize::from_str(
+            r#"{
+                "pbrMetallicRoughness": {
+                    "baseColorTexture": {
+                        "index": 0,
+                        "extensions": {
+                            "KHR_texture_transform": { "offset": [0.5, 0.25] }
+                        }
+                    },
+                    "metallicRoughnessTexture": { "index": 1 }
+                },
+                "normalTexture": {
+                    "index": 2,
+                    "extensions": {
+                        "KHR_texture_transform": { "rotation": 1.5, "scale": [2.0, 4.0] }
+                    }
+                },
+                "extensions": {
+                    "KHR_materials_emissive_strength": { "emissiveStrength": 3.0 },
+                    "KHR_materials_clearcoat": { "clearcoatFactor": 0.5 }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let extensions = MaterialExtensions::new(&raw);
+        assert_eq_delta!(extensions.emissive_strength, 3.0);
+        assert_eq_delta!(extensions.clearcoat, 0.5);
+        assert_eq_delta!(extensions.clearcoat_roughness, 0.0);
+        assert_eq!(extensions.transforms.len(), 2);
+
+        let (slot, offset, rotation, scale) = extensions.transforms[0];
+        assert_eq!(slot, TextureSlot::Albedo);
+        assert_eq!(offset, Vec2::new(0.5, 0.25));
+        assert_eq_delta!(rotation, 0.0);
+        assert_eq!(scale, Vec2::new(1.0, 1.0));
+
+        let (slot, offset, rotation, scale) = extensions.transforms[1];
+        assert_eq!(slot, TextureSlot::Normal);
+        assert_eq!(offset, Vec2::new(0.0, 0.0));
+        assert_eq_delta!(rotation, 1.5);
+        assert_eq!(scale, Vec2::new(2.0, 4.0));
+    }
+
+    #[test]
+    fn material_extensions_default() {
+        let extensions = MaterialExtensions::new(&Value::Null);
+        assert_eq_delta!(extensions.emissive_strength, 1.0);
+        assert_eq_delta!(extensions.clearcoat, 0.0);
+        assert!(extensions.transforms.is_empty());
+    }
+
+    #[test]
+    fn second_uv_set() {
+        let gltf = Gltf::from_slice(
+            br#"{
+                "asset": { "version": "2.0" },
+                "buffers": [{ "uri": "data.bin", "byteLength": 84 }],
+                "bufferViews": [{ "buffer": 0, "byteLength": 84 }],
+                "accessors": [
+                    {
+                        "bufferView": 0, "byteOffset": 0, "componentType": 5126,
+                        "count": 3, "type": "VEC3", "min": [0, 0, 0], "max": [1, 1, 0]
+                    },
+                    {
+                        "bufferView": 0, "byteOffset": 36, "componentType": 5126,
+                        "count": 3, "type": "VEC2"
+                    },
+                    {
+                        "bufferView": 0, "byteOffset": 60, "componentType": 5126,
+                        "count": 3, "type": "VEC2"
+                    }
+                ],
+                "meshes": [{
+                    "primitives": [{
+                        "attributes": { "POSITION": 0, "TEXCOORD_0": 1, "TEXCOORD_1": 2 }
+                    }]
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        let positions = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+        let uvs = [0.0, 0.0, 1.0, 0.0, 0.0, 1.0];
+        let second_uvs = [0.5, 0.5, 0.75, 0.5, 0.5, 0.75];
+        let buffer: Vec<_> = positions
+            .iter()
+            .chain(&uvs)
+            .chain(&second_uvs)
+            .flat_map(|f: &f32| f.to_le_bytes().to_vec())
+            .collect();
+
+        let primitive = gltf.meshes().next().unwrap().primitives().next().unwrap();
+        let data = read_primitive(&primitive, &[buffer], 1.0);
+
+        assert_eq!(data.primitive, Primitive::Triangles);
+        assert_eq!(data.uvs.len(), 3);
+        assert_eq!(data.second_uvs.len(), 3);
+        assert_eq_delta!(data.uvs[1].x, 1.0);
+        assert_eq_delta!(data.second_uvs[1].x, 0.75);
+        assert_eq_delta!(data.second_uvs[2].y, 0.75);
+    }
+
+    #[test]
+    fn shared_mesh_users() {
+        let gltf = Gltf::from_slice(
+            br#"{
+                "asset": { "version": "2.0" },
+                "scenes": [{ "nodes": [0, 2] }],
+                "nodes": [
+                    { "mesh": 0, "children": [1] },
+                    { "mesh": 0, "weights": [1.0] },
+                    { "mesh": 1 },
+                    { "mesh": 1 }
+                ],
+                "meshes": [{ "primitives": [] }, { "primitives": [] }]
+            }"#,
+        )
+        .unwrap();
+
+        let scene = gltf.scenes().next();
+        let (users, materials) = used_in_scene(&gltf, scene.as_ref());
+        assert_eq!(users[&0], vec![0, 1]);
+        assert_eq!(users[&1], vec![2]);
+        assert!(materials.is_empty());
+    }
+}
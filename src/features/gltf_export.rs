@@ -0,0 +1,740 @@
+// Oliver Berzs
+// https://github.com/oberzs/duku
+
+// glTF model exporting
+
+#![cfg(feature = "gltf")]
+
+use gltf_dep::binary::Header;
+use gltf_dep::json;
+use gltf_dep::json::accessor::ComponentType;
+use gltf_dep::json::accessor::GenericComponentType;
+use gltf_dep::json::accessor::Type;
+use gltf_dep::json::buffer::Target;
+use gltf_dep::json::mesh::Semantic;
+use gltf_dep::json::validation::Checked;
+use gltf_dep::json::Index;
+use gltf_dep::json::Value;
+use gltf_dep::Glb;
+use png_dep::BitDepth;
+use png_dep::ColorType;
+use png_dep::Encoder;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+
+use crate::color::Rgbf;
+use crate::error::Error;
+use crate::error::Result;
+use crate::image::Filter;
+use crate::image::Format;
+use crate::image::Texture;
+use crate::image::Wrap;
+use crate::math::Mat4;
+use crate::math::Vec2;
+use crate::math::Vec3;
+use crate::mesh::Mesh;
+use crate::mesh::Model;
+use crate::mesh::ModelNode;
+use crate::mesh::Primitive;
+use crate::pipeline::AlphaMode;
+use crate::pipeline::Material;
+use crate::pipeline::TextureSlot;
+use crate::resources::Handle;
+
+#[derive(Default)]
+struct Exporter {
+    root: json::Root,
+    bin: Vec<u8>,
+    meshes: Vec<(Handle<Mesh>, json::mesh::Primitive)>,
+    materials: Vec<(Handle<Material>, Index<json::Material>)>,
+    textures: Vec<(Handle<Texture>, Index<json::Texture>)>,
+}
+
+// exported parts of a mesh
+struct MeshData<'a> {
+    vertices: &'a [Vec3],
+    normals: &'a [Vec3],
+    tangents: &'a [Vec3],
+    uvs: &'a [Vec2],
+    second_uvs: &'a [Vec2],
+    colors: &'a [Rgbf],
+    indices: &'a [u32],
+    primitive: Primitive,
+}
+
+// exported parts of a material
+struct MaterialData<'a> {
+    albedo: [f32; 3],
+    metalness: f32,
+    roughness: f32,
+    emissive: [f32; 3],
+    alpha: AlphaMode,
+    double_sided: bool,
+    unshaded: bool,
+    // textures with their UV sets
+    textures: Vec<(TextureSlot, &'a Handle<Texture>, u32)>,
+}
+
+impl Model {
+    /// Save model to a glTF file
+    ///
+    /// Mesh data and textures are saved
+    /// to a .bin file next to it.
+    pub fn save_gltf(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let bin_path = path.with_extension("bin");
+        let (mut root, bin) = self.gltf_data()?;
+
+        if let Some(buffer) = root.buffers.first_mut() {
+            let uri = bin_path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string());
+            buffer.uri = uri;
+            fs::write(&bin_path, bin)?;
+        }
+
+        let json = root.to_string_pretty().map_err(export_error)?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Save model to a binary glTF file
+    pub fn save_glb(&self, path: impl AsRef<Path>) -> Result<()> {
+        let (root, bin) = self.gltf_data()?;
+        fs::write(path, glb_bytes(&root, bin)?)?;
+        Ok(())
+    }
+
+    fn gltf_data(&self) -> Result<(json::Root, Vec<u8>)> {
+        let mut exporter = Exporter::default();
+
+        let nodes = self
+            .nodes
+            .iter()
+            .map(|n| exporter.add_node(n))
+            .collect::<Result<_>>()?;
+        exporter.root.scenes.push(json::Scene {
+            extensions: None,
+            extras: Default::default(),
+            name: None,
+            nodes,
+        });
+        exporter.root.scene = Some(Index::new(0));
+        Ok(exporter.finish())
+    }
+}
+
+impl Exporter {
+    fn finish(mut self) -> (json::Root, Vec<u8>) {
+        self.root.asset = json::Asset {
+            generator: Some(format!("duku {}", env!("CARGO_PKG_VERSION"))),
+            version: "2.0".to_string(),
+            copyright: None,
+            extensions: None,
+            extras: Default::default(),
+            min_version: None,
+        };
+
+        if !self.bin.is_empty() {
+            self.root.buffers.push(json::Buffer {
+                byte_length: self.bin.len() as u32,
+                name: None,
+                uri: None,
+                extensions: None,
+                extras: Default::default(),
+            });
+        }
+
+        (self.root, self.bin)
+    }
+
+    fn add_node(&mut self, node: &ModelNode) -> Result<Index<json::Node>> {
+        let children = node
+            .children
+            .iter()
+            .map(|c| self.add_node(c))
+            .collect::<Result<Vec<_>>>()?;
+
+        // export meshes as primitives of one mesh
+        let mut primitives = vec![];
+        for (mesh, material) in node.orders() {
+            let mut primitive = self.add_mesh(mesh);
+            primitive.material = Some(self.add_material(material)?);
+            primitives.push(primitive);
+        }
+        let mesh = self.push_mesh(&node.mesh_name, primitives);
+
+        self.root.nodes.push(json::Node {
+            camera: None,
+            children: if children.is_empty() {
+                None
+            } else {
+                Some(children)
+            },
+            extensions: None,
+            extras: Default::default(),
+            matrix: Some(matrix_data(node.matrix())),
+            name: optional_name(&node.name),
+            rotation: None,
+            scale: None,
+            translation: None,
+            skin: None,
+            weights: None,
+            mesh,
+        });
+        Ok(Index::new(self.root.nodes.len() as u32 - 1))
+    }
+
+    fn push_mesh(
+        &mut self,
+        name: &str,
+        primitives: Vec<json::mesh::Primitive>,
+    ) -> Option<Index<json::Mesh>> {
+        if primitives.is_empty() {
+            return None;
+        }
+
+        self.root.meshes.push(json::Mesh {
+            extensions: None,
+            extras: Default::default(),
+            name: optional_name(name),
+            weights: None,
+            primitives,
+        });
+        Some(Index::new(self.root.meshes.len() as u32 - 1))
+    }
+
+    fn add_mesh(&mut self, mesh: &Handle<Mesh>) -> json::mesh::Primitive {
+        if let Some((_, primitive)) = self.meshes.iter().find(|(m, _)| m == mesh) {
+            return primitive.clone();
+        }
+
+        let m = mesh.read();
+        let primitive = self.push_primitive(&MeshData {
+            vertices: &m.vertices,
+            normals: &m.normals,
+            tangents: &m.tangents,
+            uvs: &m.uvs,
+            second_uvs: &m.second_uvs,
+            colors: &m.colors,
+            indices: &m.indices,
+            primitive: m.primitive,
+        });
+        self.meshes.push((mesh.clone(), primitive.clone()));
+        primitive
+    }
+
+    fn push_primitive(&mut self, m: &MeshData<'_>) -> json::mesh::Primitive {
+        let count = m.vertices.len();
+        let mut attributes = HashMap::new();
+
+        // positions with bounds
+        let positions: Vec<_> = m.vertices.iter().map(|v| convert_vector(*v)).collect();
+        let mut min = [f32::MAX; 3];
+        let mut max = [f32::MIN; 3];
+        for p in &positions {
+            for i in 0..3 {
+                min[i] = min[i].min(p[i]);
+                max[i] = max[i].max(p[i]);
+            }
+        }
+        let accessor = self.add_accessor(
+            &positions.concat(),
+            count,
+            ComponentType::F32,
+            Type::Vec3,
+            Some((min.to_vec(), max.to_vec())),
+        );
+        attributes.insert(Checked::Valid(Semantic::Positions), accessor);
+
+        if m.normals.len() == count {
+            let normals: Vec<_> = m.normals.iter().map(|n| convert_vector(*n)).collect();
+            let accessor = self.add_accessor(
+                &normals.concat(),
+                count,
+                ComponentType::F32,
+                Type::Vec3,
+                None,
+            );
+            attributes.insert(Checked::Valid(Semantic::Normals), accessor);
+        }
+
+        if m.tangents.len() == count {
+            let tangents: Vec<_> = m
+                .tangents
+                .iter()
+                .map(|t| {
+                    let [x, y, z] = convert_vector(*t);
+                    [x, y, z, 1.0]
+                })
+                .collect();
+            let accessor = self.add_accessor(
+                &tangents.concat(),
+                count,
+                ComponentType::F32,
+                Type::Vec4,
+                None,
+            );
+            attributes.insert(Checked::Valid(Semantic::Tangents), accessor);
+        }
+
+        if m.uvs.len() == count {
+            let uvs: Vec<_> = m.uvs.iter().map(|uv| [uv.x, uv.y]).collect();
+            let accessor =
+                self.add_accessor(&uvs.concat(), count, ComponentType::F32, Type::Vec2, None);
+            attributes.insert(Checked::Valid(Semantic::TexCoords(0)), accessor);
+        }
+
+        if m.second_uvs.len() == count {
+            let uvs: Vec<_> = m.second_uvs.iter().map(|uv| [uv.x, uv.y]).collect();
+            let accessor =
+                self.add_accessor(&uvs.concat(), count, ComponentType::F32, Type::Vec2, None);
+            attributes.insert(Checked::Valid(Semantic::TexCoords(1)), accessor);
+        }
+
+        if m.colors.len() == count {
+            let colors: Vec<_> = m.colors.iter().map(|c| [c.r, c.g, c.b, c.a]).collect();
+            let accessor = self.add_accessor(
+                &colors.concat(),
+                count,
+                ComponentType::F32,
+                Type::Vec4,
+                None,
+            );
+            attributes.insert(Checked::Valid(Semantic::Colors(0)), accessor);
+        }
+
+        // flip winding order of triangles
+        let indices: Vec<_> = match m.primitive {
+            Primitive::Triangles => m
+                .indices
+                .chunks(3)
+                .flat_map(|c| match *c {
+                    [a, b, c] => vec![a, c, b],
+                    _ => vec![],
+                })
+                .collect(),
+            Primitive::Lines | Primitive::Points => m.indices.to_vec(),
+        };
+        let mode = match m.primitive {
+            Primitive::Triangles => json::mesh::Mode::Triangles,
+            Primitive::Lines => json::mesh::Mode::Lines,
+            Primitive::Points => json::mesh::Mode::Points,
+        };
+        let view = self.add_view(
+            indices.iter().flat_map(|i| i.to_le_bytes().to_vec()),
+            Some(Target::ElementArrayBuffer),
+        );
+        let indices =
+            self.push_accessor(view, indices.len(), ComponentType::U32, Type::Scalar, None);
+
+        json::mesh::Primitive {
+            extensions: None,
+            extras: Default::default(),
+            indices: Some(indices),
+            material: None,
+            mode: Checked::Valid(mode),
+            targets: None,
+            attributes,
+        }
+    }
+
+    fn add_material(&mut self, material: &Handle<Material>) -> Result<Index<json::Material>> {
+        if let Some((_, index)) = self.materials.iter().find(|(m, _)| m == material) {
+            return Ok(*index);
+        }
+
+        let m = material.read();
+        let index = self.push_material(&MaterialData {
+            albedo: [m.a[0], m.a[1], m.a[2]],
+            metalness: m.b[0],
+            roughness: m.b[1],
+            emissive: [m.d[0], m.d[1], m.d[2]],
+            alpha: m.alpha,
+            double_sided: m.double_sided,
+            unshaded: m.unshaded,
+            textures: m
+                .slot_textures()
+                .map(|(slot, tex)| (slot, tex, m.uv_set_of(slot)))
+                .collect(),
+        })?;
+        self.materials.push((material.clone(), index));
+        Ok(index)
+    }
+
+    fn push_material(&mut self, m: &MaterialData<'_>) -> Result<Index<json::Material>> {
+        let mut infos = vec![];
+        for (slot, tex, tex_coord) in &m.textures {
+            if let Some(index) = self.add_texture(tex)? {
+                let info = json::texture::Info {
+                    tex_coord: *tex_coord,
+                    extensions: None,
+                    extras: Default::default(),
+                    index,
+                };
+                infos.push((*slot, info));
+            }
+        }
+        let texture = |slot: TextureSlot| {
+            infos
+                .iter()
+                .find(|(s, _)| *s == slot)
+                .map(|(_, info)| info.clone())
+        };
+
+        let (alpha_mode, alpha_cutoff) = match m.alpha {
+            AlphaMode::Opaque => (json::material::AlphaMode::Opaque, 0.5),
+            AlphaMode::Mask(cutoff) => (json::material::AlphaMode::Mask, cutoff),
+            AlphaMode::Blend => (json::material::AlphaMode::Blend, 0.5),
+        };
+
+        // unshaded materials are exported as unlit
+        let unlit = if m.unshaded {
+            let used = "KHR_materials_unlit".to_string();
+            if !self.root.extensions_used.contains(&used) {
+                self.root.extensions_used.push(used);
+            }
+            Some(json::extensions::material::Material {
+                unlit: Some(json::extensions::material::Unlit {}),
+            })
+        } else {
+            None
+        };
+
+        let json_material = json::Material {
+            alpha_cutoff: json::material::AlphaCutoff(alpha_cutoff),
+            alpha_mode: Checked::Valid(alpha_mode),
+            double_sided: m.double_sided,
+            pbr_metallic_roughness: json::material::PbrMetallicRoughness {
+                base_color_factor: json::material::PbrBaseColorFactor([
+                    m.albedo[0],
+                    m.albedo[1],
+                    m.albedo[2],
+                    1.0,
+                ]),
+                base_color_texture: texture(TextureSlot::Albedo),
+                metallic_factor: json::material::StrengthFactor(m.metalness),
+                roughness_factor: json::material::StrengthFactor(m.roughness),
+                metallic_roughness_texture: texture(TextureSlot::MetalnessRoughness),
+                extensions: None,
+                extras: Default::default(),
+            },
+            normal_texture: texture(TextureSlot::Normal).map(|info| {
+                json::material::NormalTexture {
+                    index: info.index,
+                    scale: 1.0,
+                    tex_coord: info.tex_coord,
+                    extensions: None,
+                    extras: Default::default(),
+                }
+            }),
+            occlusion_texture: texture(TextureSlot::AmbientOcclusion).map(|info| {
+                json::material::OcclusionTexture {
+                    index: info.index,
+                    strength: json::material::StrengthFactor(1.0),
+                    tex_coord: info.tex_coord,
+                    extensions: None,
+                    extras: Default::default(),
+                }
+            }),
+            emissive_texture: texture(TextureSlot::Emissive),
+            emissive_factor: json::material::EmissiveFactor(m.emissive),
+            name: None,
+            extensions: unlit,
+            extras: Default::default(),
+        };
+
+        self.root.materials.push(json_material);
+        Ok(Index::new(self.root.materials.len() as u32 - 1))
+    }
+
+    fn add_texture(&mut self, texture: &Handle<Texture>) -> Result<Option<Index<json::Texture>>> {
+        if let Some((_, index)) = self.textures.iter().find(|(t, _)| t == texture) {
+            return Ok(Some(*index));
+        }
+
+        let t = texture.read();
+        let color_type = match t.format() {
+            Format::Rgba | Format::Srgba => ColorType::RGBA,
+            Format::Gray => ColorType::Grayscale,
+            _ => return Ok(None),
+        };
+
+        // encode texture as PNG
+        let mut png = vec![];
+        {
+            let mut encoder = Encoder::new(&mut png, t.width(), t.height());
+            encoder.set_color(color_type);
+            encoder.set_depth(BitDepth::Eight);
+            let mut writer = encoder.write_header().map_err(export_error)?;
+            writer.write_image_data(&t.data).map_err(export_error)?;
+        }
+
+        let view = self.add_view(png, None);
+        self.root.images.push(json::Image {
+            buffer_view: Some(view),
+            mime_type: Some(json::image::MimeType("image/png".to_string())),
+            name: None,
+            uri: None,
+            extensions: None,
+            extras: Default::default(),
+        });
+
+        // add sampler if texture has one
+        let sampler = t.sampler().map(|(filter, wrap)| {
+            let filter = match filter {
+                Filter::Linear => json::texture::MagFilter::Linear,
+                Filter::Nearest => json::texture::MagFilter::Nearest,
+            };
+            let wrap = match wrap {
+                Wrap::Repeat => json::texture::WrappingMode::Repeat,
+                Wrap::MirroredRepeat => json::texture::WrappingMode::MirroredRepeat,
+                Wrap::ClampEdge | Wrap::ClampBorder => json::texture::WrappingMode::ClampToEdge,
+            };
+            self.root.samplers.push(json::texture::Sampler {
+                mag_filter: Some(Checked::Valid(filter)),
+                min_filter: None,
+                wrap_s: Checked::Valid(wrap),
+                wrap_t: Checked::Valid(wrap),
+                name: None,
+                extensions: None,
+                extras: Default::default(),
+            });
+            Index::new(self.root.samplers.len() as u32 - 1)
+        });
+
+        self.root.textures.push(json::Texture {
+            source: Index::new(self.root.images.len() as u32 - 1),
+            name: None,
+            extensions: None,
+            extras: Default::default(),
+            sampler,
+        });
+        let index = Index::new(self.root.textures.len() as u32 - 1);
+        self.textures.push((texture.clone(), index));
+        Ok(Some(index))
+    }
+
+    fn add_accessor(
+        &mut self,
+        data: &[f32],
+        count: usize,
+        component: ComponentType,
+        kind: Type,
+        bounds: Option<(Vec<f32>, Vec<f32>)>,
+    ) -> Index<json::Accessor> {
+        let view = self.add_view(
+            data.iter().flat_map(|f| f.to_le_bytes().to_vec()),
+            Some(Target::ArrayBuffer),
+        );
+        self.push_accessor(view, count, component, kind, bounds)
+    }
+
+    fn push_accessor(
+        &mut self,
+        view: Index<json::buffer::View>,
+        count: usize,
+        component: ComponentType,
+        kind: Type,
+        bounds: Option<(Vec<f32>, Vec<f32>)>,
+    ) -> Index<json::Accessor> {
+        let (min, max) = match bounds {
+            Some((min, max)) => (Some(Value::from(min)), Some(Value::from(max))),
+            None => (None, None),
+        };
+
+        self.root.accessors.push(json::Accessor {
+            buffer_view: Some(view),
+            byte_offset: 0,
+            count: count as u32,
+            component_type: Checked::Valid(GenericComponentType(component)),
+            extensions: None,
+            extras: Default::default(),
+            type_: Checked::Valid(kind),
+            name: None,
+            normalized: false,
+            sparse: None,
+            min,
+            max,
+        });
+        Index::new(self.root.accessors.len() as u32 - 1)
+    }
+
+    fn add_view(
+        &mut self,
+        data: impl IntoIterator<Item = u8>,
+        target: Option<Target>,
+    ) -> Index<json::buffer::View> {
+        let offset = self.bin.len();
+        self.bin.extend(data);
+        let length = self.bin.len() - offset;
+
+        // keep views aligned to 4 bytes
+        while self.bin.len() % 4 != 0 {
+            self.bin.push(0);
+        }
+
+        self.root.buffer_views.push(json::buffer::View {
+            buffer: Index::new(0),
+            byte_length: length as u32,
+            byte_offset: Some(offset as u32),
+            byte_stride: None,
+            name: None,
+            target: target.map(Checked::Valid),
+            extensions: None,
+            extras: Default::default(),
+        });
+        Index::new(self.root.buffer_views.len() as u32 - 1)
+    }
+}
+
+// packs glTF JSON and binary data into a GLB file
+fn glb_bytes(root: &json::Root, bin: Vec<u8>) -> Result<Vec<u8>> {
+    let glb = Glb {
+        header: Header {
+            magic: *b"glTF",
+            version: 2,
+            // calculated when writing
+            length: 0,
+        },
+        json: Cow::Owned(root.to_vec().map_err(export_error)?),
+        bin: if bin.is_empty() {
+            None
+        } else {
+            Some(Cow::Owned(bin))
+        },
+    };
+    glb.to_vec().map_err(export_error)
+}
+
+fn export_error(e: impl Display) -> Error {
+    Error::GltfExport(e.to_string())
+}
+
+fn convert_vector(v: Vec3) -> [f32; 3] {
+    [v.x, v.y, -v.z]
+}
+
+// flip Z axis, column-major order
+fn matrix_data(m: Mat4) -> [f32; 16] {
+    let mut data = [0.0; 16];
+    for col in 0..4 {
+        for row in 0..4 {
+            let value = m[col][row];
+            data[col * 4 + row] = if (col == 2) != (row == 2) {
+                -value
+            } else {
+                value
+            };
+        }
+    }
+    data
+}
+
+fn optional_name(name: &str) -> Option<String> {
+    if name.is_empty() {
+        None
+    } else {
+        Some(name.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use gltf_dep::material::AlphaMode as GltfAlphaMode;
+    use gltf_dep::Gltf;
+
+    use super::glb_bytes;
+    use super::matrix_data;
+    use super::Exporter;
+    use super::MaterialData;
+    use super::MeshData;
+    use crate::math::Mat4;
+    use crate::math::Vec2;
+    use crate::math::Vec3;
+    use crate::mesh::Primitive;
+    use crate::pipeline::AlphaMode;
+
+    #[test]
+    fn round_trip() {
+        let vertices = [
+            Vec3::new(0.0, 0.0, 1.0),
+            Vec3::new(1.0, 0.0, 1.0),
+            Vec3::new(0.0, 2.0, 1.0),
+        ];
+        let uvs = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(0.0, 1.0),
+        ];
+
+        let mut exporter = Exporter::default();
+        let mut primitive = exporter.push_primitive(&MeshData {
+            vertices: &vertices,
+            normals: &[],
+            tangents: &[],
+            uvs: &uvs,
+            second_uvs: &[],
+            colors: &[],
+            indices: &[0, 1, 2],
+            primitive: Primitive::Triangles,
+        });
+        let material = exporter
+            .push_material(&MaterialData {
+                albedo: [1.0, 0.5, 0.25],
+                metalness: 0.25,
+                roughness: 0.75,
+                emissive: [0.0, 1.0, 0.0],
+                alpha: AlphaMode::Mask(0.25),
+                double_sided: true,
+                unshaded: false,
+                textures: vec![],
+            })
+            .unwrap();
+        primitive.material = Some(material);
+        exporter.push_mesh("triangle", vec![primitive]);
+
+        let (root, bin) = exporter.finish();
+        let gltf = Gltf::from_slice(&glb_bytes(&root, bin).unwrap()).unwrap();
+        let blob = gltf.blob.clone().unwrap();
+        let mesh = gltf.meshes().next().unwrap();
+        assert_eq!(mesh.name(), Some("triangle"));
+
+        let primitive = mesh.primitives().next().unwrap();
+        let reader = primitive.reader(|_| Some(&blob));
+        let positions: Vec<_> = reader.read_positions().unwrap().collect();
+        let uvs: Vec<_> = reader.read_tex_coords(0).unwrap().into_f32().collect();
+        let indices: Vec<_> = reader.read_indices().unwrap().into_u32().collect();
+        assert_eq!(
+            positions,
+            vec![[0.0, 0.0, -1.0], [1.0, 0.0, -1.0], [0.0, 2.0, -1.0]]
+        );
+        assert_eq!(uvs, vec![[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]]);
+        assert_eq!(indices, vec![0, 2, 1]);
+
+        let position = primitive.get(&gltf_dep::Semantic::Positions).unwrap();
+        assert_eq!(position.count(), 3);
+
+        let material = primitive.material();
+        let pbr = material.pbr_metallic_roughness();
+        assert_eq!(pbr.base_color_factor(), [1.0, 0.5, 0.25, 1.0]);
+        assert_eq_delta!(pbr.metallic_factor(), 0.25);
+        assert_eq_delta!(pbr.roughness_factor(), 0.75);
+        assert_eq!(material.emissive_factor(), [0.0, 1.0, 0.0]);
+        assert_eq!(material.alpha_mode(), GltfAlphaMode::Mask);
+        assert_eq!(material.alpha_cutoff(), 0.25);
+        assert!(material.double_sided());
+    }
+
+    #[test]
+    fn matrix_flips_z() {
+        let data = matrix_data(Mat4::translation([1.0, 2.0, 3.0]));
+        assert_eq_delta!(data[12], 1.0);
+        assert_eq_delta!(data[13], 2.0);
+        assert_eq_delta!(data[14], -3.0);
+        assert_eq_delta!(data[15], 1.0);
+    }
+}
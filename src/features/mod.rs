@@ -4,8 +4,14 @@
 pub mod glsl;
 pub mod gltf;
 pub mod otf;
+pub mod svg;
 pub mod window;
 
+mod aseprite;
+mod bmfont;
 mod glsl_compiler;
+mod gltf_export;
 mod jpeg;
 mod png;
+mod shaping;
+mod tiled;
@@ -7,9 +7,11 @@
 
 use otf_dep::Font as _;
 use otf_dep::FontRef;
+use otf_dep::FontVec;
+use otf_dep::GlyphId;
+use otf_dep::OutlineCurve;
 use otf_dep::PxScale;
 use otf_dep::ScaleFont;
-use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
@@ -17,9 +19,18 @@ use std::path::Path;
 use crate::duku::Duku;
 use crate::error::Error;
 use crate::error::Result;
+#[cfg(feature = "shaping")]
+use crate::features::shaping;
 use crate::font::CharData;
 use crate::font::Font;
 use crate::font::FontData;
+use crate::font::FontMetrics;
+use crate::font::GlyphSource;
+use crate::font::KerningSource;
+use crate::font::RasterGlyph;
+use crate::font::MAX_ATLAS_SIZE;
+use crate::font::MIN_ATLAS_SIZE;
+use crate::image;
 use crate::math::Vec2;
 use crate::math::Vec4;
 use crate::resources::Handle;
@@ -33,6 +44,21 @@ pub enum CharSet<'a> {
     Custom(&'a str),
 }
 
+// page, x and y of a glyph in the atlas
+type Placement = (usize, u32, u32);
+
+// rasterizes glyphs of dynamic fonts
+struct OtfSource {
+    font: FontVec,
+    px_size: u32,
+}
+
+// reads kerning of static fonts
+struct OtfKerning {
+    font: FontVec,
+    px_size: u32,
+}
+
 impl Duku {
     /// Create font from OTF file
     pub fn create_font_otf(
@@ -51,6 +77,66 @@ impl Duku {
         bytes: &[u8],
         px_size: u32,
         options: Option<CharSet<'_>>,
+    ) -> Result<Handle<Font>> {
+        self.load_font_otf(bytes, px_size, options, false)
+    }
+
+    /// Create signed distance field font from OTF file
+    ///
+    /// SDF fonts stay sharp when drawn at
+    /// any font size and support text effects.
+    pub fn create_font_otf_sdf(
+        &mut self,
+        path: impl AsRef<Path>,
+        px_size: u32,
+        options: Option<CharSet<'_>>,
+    ) -> Result<Handle<Font>> {
+        let bytes = fs::read(path.as_ref())?;
+        self.create_font_otf_sdf_bytes(&bytes, px_size, options)
+    }
+
+    /// Create signed distance field font from OTF bytes
+    pub fn create_font_otf_sdf_bytes(
+        &mut self,
+        bytes: &[u8],
+        px_size: u32,
+        options: Option<CharSet<'_>>,
+    ) -> Result<Handle<Font>> {
+        self.load_font_otf(bytes, px_size, options, true)
+    }
+
+    /// Create dynamic font from OTF file
+    ///
+    /// Dynamic fonts load characters the first
+    /// time they are drawn, so any character
+    /// in the font can be used. Least recently
+    /// used characters are removed when the
+    /// atlas is full.
+    pub fn create_font_otf_dynamic(
+        &mut self,
+        path: impl AsRef<Path>,
+        px_size: u32,
+    ) -> Result<Handle<Font>> {
+        let bytes = fs::read(path.as_ref())?;
+        self.create_font_otf_dynamic_bytes(&bytes, px_size)
+    }
+
+    /// Create dynamic font from OTF bytes
+    pub fn create_font_otf_dynamic_bytes(
+        &mut self,
+        bytes: &[u8],
+        px_size: u32,
+    ) -> Result<Handle<Font>> {
+        let font = FontVec::try_from_vec(bytes.to_vec()).map_err(|_| Error::InvalidOtf)?;
+        self.create_font_dynamic(Box::new(OtfSource { font, px_size }))
+    }
+
+    fn load_font_otf(
+        &mut self,
+        bytes: &[u8],
+        px_size: u32,
+        options: Option<CharSet<'_>>,
+        sdf: bool,
     ) -> Result<Handle<Font>> {
         let charset = options.unwrap_or(CharSet::Ascii);
         let chars = match charset {
@@ -59,6 +145,9 @@ impl Duku {
         };
         let margin = 1;
 
+        // distance field spread around glyphs
+        let spread = if sdf { (px_size / 8).max(2) } else { 0 };
+
         let scale = PxScale::from(px_size as f32);
         let font = FontRef::try_from_slice(bytes)
             .map_err(|_| Error::InvalidOtf)?
@@ -71,28 +160,60 @@ impl Duku {
             }
         }
 
-        let texture_width = chars.chars().fold(0.0, |acc, c| {
-            let glyph = font.scaled_glyph(c);
-            let w = font
-                .outline_glyph(glyph)
-                .expect("bad font")
-                .px_bounds()
-                .width();
-            acc + w
-        }) as usize
-            + (chars.chars().count() as usize + 1) * margin;
-        let texture_height = chars
-            .chars()
-            .map(|c| {
-                let glyph = font.scaled_glyph(c);
-                font.outline_glyph(glyph)
-                    .expect("bad font")
-                    .px_bounds()
-                    .height()
+        // collect glyphs used by the characters
+        let mut char_glyphs = HashMap::new();
+        let mut glyph_ids = vec![];
+        for c in chars.chars() {
+            let id = font.glyph_id(c);
+            char_glyphs.insert(c, u32::from(id.0));
+            if !glyph_ids.contains(&id) {
+                glyph_ids.push(id);
+            }
+        }
+
+        // read ligatures from layout tables
+        #[cfg(feature = "shaping")]
+        let ligatures = {
+            let ids: Vec<_> = glyph_ids.iter().map(|g| g.0).collect();
+            let mut ligatures = shaping::ligatures(bytes, &ids);
+            for id in ligatures.values() {
+                let glyph = GlyphId(*id as u16);
+                if !glyph_ids.contains(&glyph)
+                    && font.outline_glyph(glyph.with_scale(scale)).is_some()
+                {
+                    glyph_ids.push(glyph);
+                }
+            }
+            ligatures.retain(|_, id| glyph_ids.contains(&GlyphId(*id as u16)));
+            ligatures
+        };
+        #[cfg(not(feature = "shaping"))]
+        let ligatures = HashMap::new();
+
+        let outlines: Vec<_> = glyph_ids
+            .iter()
+            .map(|id| font.outline_glyph(id.with_scale(scale)).expect("bad font"))
+            .collect();
+
+        // pack glyphs with the distance field spread
+        let sizes: Vec<_> = outlines
+            .iter()
+            .map(|o| {
+                let bounds = o.px_bounds();
+                (
+                    bounds.width() as u32 + spread * 2,
+                    bounds.height() as u32 + spread * 2,
+                )
             })
-            .max_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal))
-            .expect("bad font") as usize
-            + 2 * margin;
+            .collect();
+        let (page_size, packed) =
+            pack_glyphs(&sizes, margin).ok_or_else(|| Error::ImageTooLarge("glyph".to_string()))?;
+        let page_count = packed
+            .iter()
+            .map(|(page, _, _)| page + 1)
+            .max()
+            .unwrap_or(1);
+
         let height = font.height();
         let line_gap = font.line_gap();
         let ascender = font.ascent();
@@ -103,58 +224,271 @@ impl Duku {
             line_gap: line_gap / px_size as f32,
             ascender: ascender / px_size as f32,
             descender: descender / px_size as f32,
-            char_data: HashMap::new(),
-            texture_data: &[],
-            texture_width: texture_width as u32,
-            texture_height: texture_height as u32,
+            glyph_data: HashMap::new(),
+            texture_data: vec![],
+            texture_width: page_size,
+            texture_height: page_size,
+            sdf_range: if sdf {
+                Some((spread * 2) as f32 / px_size as f32)
+            } else {
+                None
+            },
+            kerning: HashMap::new(),
+            char_glyphs,
+            ligatures,
         };
 
-        let mut texture_data = vec![0; texture_width * texture_height];
-        let mut x_offset = margin;
-        for c in chars.chars() {
-            let glyph_id = font.glyph_id(c);
-            let glyph = font.scaled_glyph(c);
-            let outline = font.outline_glyph(glyph).expect("bad font");
+        let size = page_size as usize;
+        let mut pages = vec![vec![0; size * size]; page_count];
+        for ((glyph_id, outline), (page, x, y)) in glyph_ids.iter().zip(&outlines).zip(packed) {
             let bounds = outline.px_bounds();
+            let pad = spread as f32;
 
-            let x_advance = font.h_advance(glyph_id).round();
-            let x_bearing = font.h_side_bearing(glyph_id).round();
-            let y_bearing = bounds.max.y.round();
-            let x_bound = bounds.width().round();
-            let y_bound = bounds.height().round();
-
-            let v_margin = margin as f32 / texture_height as f32;
-            let u_offset = x_offset as f32 / texture_width as f32;
-            let u_bound = x_bound / texture_width as f32;
-            let v_bound = y_bound / texture_height as f32;
+            let x_advance = font.h_advance(*glyph_id).round();
+            let x_bearing = font.h_side_bearing(*glyph_id).round() - pad;
+            let y_bearing = bounds.max.y.round() + pad;
+            let x_bound = bounds.width().round() + pad * 2.0;
+            let y_bound = bounds.height().round() + pad * 2.0;
 
-            let u1 = u_offset;
-            let u2 = u_offset + u_bound;
-            let v1 = v_margin;
-            let v2 = v_margin + v_bound;
+            let u1 = x as f32 / page_size as f32;
+            let v1 = y as f32 / page_size as f32;
+            let u2 = u1 + x_bound / page_size as f32;
+            let v2 = v1 + y_bound / page_size as f32;
 
-            font_data.char_data.insert(
-                c,
+            font_data.glyph_data.insert(
+                u32::from(glyph_id.0),
                 CharData {
                     uvs: Vec4::new(u1, v1, u2, v2),
                     bounds: Vec2::new(x_bound / px_size as f32, y_bound / px_size as f32),
                     bearing: Vec2::new(x_bearing / px_size as f32, y_bearing / px_size as f32),
                     advance: x_advance / px_size as f32,
+                    page: page as u32,
                 },
             );
 
-            outline.draw(|x, y, v| {
-                let xx = x as usize + x_offset;
-                let yy = y as usize + margin;
-                let vv = (v * 255.0).round() as u8;
-                texture_data[xx + texture_width * yy] = vv;
-            });
+            let data = &mut pages[page];
+            let (x, y) = (x as usize, y as usize);
+            if sdf {
+                // glyph curves in padded bitmap space
+                let factor = font.scale_factor();
+                let offset = Vec2::new(pad - bounds.min.x, pad - bounds.min.y);
+                let to_bitmap =
+                    |p: Vec2| Vec2::new(p.x * factor.horizontal, -p.y * factor.vertical) + offset;
+                let curves = font
+                    .font()
+                    .outline(*glyph_id)
+                    .map(|o| o.curves)
+                    .unwrap_or_default();
+                let segments = flatten(&curves, to_bitmap);
 
-            x_offset += x_bound as usize + margin;
+                let (w, h) = (x_bound as usize, y_bound as usize);
+                for (i, v) in distance_field(&segments, w, h, pad).iter().enumerate() {
+                    let xx = i % w + x;
+                    let yy = i / w + y;
+                    data[xx + size * yy] = *v;
+                }
+            } else {
+                outline.draw(|ox, oy, v| {
+                    let xx = ox as usize + x;
+                    let yy = oy as usize + y;
+                    let vv = (v * 255.0).round() as u8;
+                    data[xx + size * yy] = vv;
+                });
+            }
+        }
+
+        font_data.texture_data = pages.iter().map(|p| p.as_slice()).collect();
+
+        // kerning is read when glyph pairs are first used
+        let kerning = OtfKerning {
+            font: FontVec::try_from_vec(bytes.to_vec()).map_err(|_| Error::InvalidOtf)?,
+            px_size,
+        };
+        let handle = self.create_font(font_data)?;
+        handle.write().set_kerning_source(Box::new(kerning));
+        Ok(handle)
+    }
+}
+
+impl GlyphSource for OtfSource {
+    fn metrics(&self) -> FontMetrics {
+        let font = self.font.as_scaled(PxScale::from(self.px_size as f32));
+        let px = self.px_size as f32;
+        FontMetrics {
+            height: font.height() / px,
+            line_gap: font.line_gap() / px,
+            ascender: font.ascent() / px,
+            descender: font.descent() / px,
+            space_width: 1.0 / 3.0,
         }
+    }
 
-        font_data.texture_data = &texture_data;
+    fn px_size(&self) -> u32 {
+        self.px_size
+    }
 
-        self.create_font(font_data)
+    fn glyph_id(&self, c: char) -> u32 {
+        u32::from(self.font.glyph_id(c).0)
     }
+
+    fn kerning(&self, first: u32, second: u32) -> f32 {
+        pair_kerning(&self.font, self.px_size, first, second)
+    }
+
+    fn rasterize(&self, id: u32) -> RasterGlyph {
+        let scale = PxScale::from(self.px_size as f32);
+        let font = self.font.as_scaled(scale);
+        let glyph_id = GlyphId(id as u16);
+        let advance = font.h_advance(glyph_id).round();
+
+        match font.outline_glyph(glyph_id.with_scale(scale)) {
+            Some(outline) => {
+                let bounds = outline.px_bounds();
+                let width = bounds.width() as u32;
+                let height = bounds.height() as u32;
+
+                let mut data = vec![0; (width * height) as usize];
+                outline.draw(|x, y, v| {
+                    data[(x + y * width) as usize] = (v * 255.0).round() as u8;
+                });
+
+                RasterGlyph {
+                    bearing: Vec2::new(font.h_side_bearing(glyph_id).round(), bounds.max.y.round()),
+                    width,
+                    height,
+                    data,
+                    advance,
+                }
+            }
+            None => RasterGlyph {
+                width: 0,
+                height: 0,
+                data: vec![],
+                bearing: Vec2::default(),
+                advance,
+            },
+        }
+    }
+}
+
+impl KerningSource for OtfKerning {
+    fn kerning(&self, first: u32, second: u32) -> f32 {
+        pair_kerning(&self.font, self.px_size, first, second)
+    }
+}
+
+// kerning between glyphs in ems, where the layout
+// table's kerning replaces the kern table's
+fn pair_kerning(font: &FontVec, px_size: u32, first: u32, second: u32) -> f32 {
+    let scaled = font.as_scaled(PxScale::from(px_size as f32));
+    let (first, second) = (GlyphId(first as u16), GlyphId(second as u16));
+
+    #[cfg(feature = "shaping")]
+    let kern = shaping::pair_kerning(font.as_slice(), first.0, second.0)
+        .map(|k| k * scaled.h_scale_factor())
+        .unwrap_or_else(|| scaled.kern(first, second));
+    #[cfg(not(feature = "shaping"))]
+    let kern = scaled.kern(first, second);
+
+    kern.round() / px_size as f32
+}
+
+// finds the smallest square power of two page
+// that fits all glyphs or uses multiple max size pages
+fn pack_glyphs(sizes: &[(u32, u32)], margin: u32) -> Option<(u32, Vec<Placement>)> {
+    let area: u32 = sizes.iter().map(|(w, h)| (w + margin) * (h + margin)).sum();
+    let mut size = ((area as f32).sqrt() as u32)
+        .next_power_of_two()
+        .max(MIN_ATLAS_SIZE)
+        .min(MAX_ATLAS_SIZE);
+
+    loop {
+        match image::pack(sizes, size, margin) {
+            Some(packed) if packed.iter().all(|(page, _, _)| *page == 0) => {
+                return Some((size, packed))
+            }
+            Some(packed) if size >= MAX_ATLAS_SIZE => return Some((size, packed)),
+            _ if size >= MAX_ATLAS_SIZE => return None,
+            _ => size *= 2,
+        }
+    }
+}
+
+// splits curves into line segments
+fn flatten(curves: &[OutlineCurve], transform: impl Fn(Vec2) -> Vec2) -> Vec<(Vec2, Vec2)> {
+    let steps = 8;
+    let mut segments = vec![];
+
+    for curve in curves {
+        let (points, count) = match *curve {
+            OutlineCurve::Line(p0, p1) => (vec![p0, p1], 1),
+            OutlineCurve::Quad(p0, p1, p2) => (vec![p0, p1, p2], steps),
+            OutlineCurve::Cubic(p0, p1, p2, p3) => (vec![p0, p1, p2, p3], steps),
+        };
+        let points: Vec<_> = points.iter().map(|p| Vec2::new(p.x, p.y)).collect();
+
+        for i in 0..count {
+            let start = bezier(&points, i as f32 / count as f32);
+            let end = bezier(&points, (i + 1) as f32 / count as f32);
+            segments.push((transform(start), transform(end)));
+        }
+    }
+
+    segments
+}
+
+// point on bezier curve with de Casteljau's algorithm
+fn bezier(points: &[Vec2], t: f32) -> Vec2 {
+    let mut p = points.to_vec();
+    while p.len() > 1 {
+        p = p.windows(2).map(|w| w[0] + (w[1] - w[0]) * t).collect();
+    }
+    p[0]
+}
+
+// signed distance from pixel centers to the segments
+// mapped to 0-255 with the edge at 128
+fn distance_field(segments: &[(Vec2, Vec2)], width: usize, height: usize, spread: f32) -> Vec<u8> {
+    let mut field = vec![0; width * height];
+
+    for y in 0..height {
+        for x in 0..width {
+            let p = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+            let mut min_dist = f32::MAX;
+            let mut winding = 0;
+
+            for (a, b) in segments {
+                // distance to segment
+                let ab = *b - *a;
+                let ap = p - *a;
+                let len = ab.sqr_length();
+                let t = if len > 0.0 {
+                    (ap.dot(ab) / len).max(0.0).min(1.0)
+                } else {
+                    0.0
+                };
+                min_dist = min_dist.min((ap - ab * t).sqr_length());
+
+                // non-zero winding for inside test
+                let cross = ab.x * ap.y - ab.y * ap.x;
+                if a.y <= p.y {
+                    if b.y > p.y && cross > 0.0 {
+                        winding += 1;
+                    }
+                } else if b.y <= p.y && cross < 0.0 {
+                    winding -= 1;
+                }
+            }
+
+            let dist = if winding == 0 {
+                -min_dist.sqrt()
+            } else {
+                min_dist.sqrt()
+            };
+            let value = 0.5 + dist / (spread * 2.0);
+            field[x + width * y] = (value.max(0.0).min(1.0) * 255.0).round() as u8;
+        }
+    }
+
+    field
 }
@@ -21,6 +21,7 @@ use crate::image::CubemapSides;
 use crate::image::Format;
 use crate::image::Mips;
 use crate::image::Texture;
+use crate::image::TextureAtlasBuilder;
 use crate::resources::Handle;
 
 pub(crate) struct PngData {
@@ -175,7 +176,37 @@ impl Duku {
     }
 }
 
-fn load_png(bytes: &[u8], color_space: ColorSpace) -> Result<PngData> {
+impl TextureAtlasBuilder {
+    /// Add image from a PNG file
+    pub fn png(self, name: impl AsRef<str>, path: impl AsRef<Path>) -> Result<Self> {
+        let bytes = fs::read(path.as_ref())?;
+        self.png_bytes(name, &bytes)
+    }
+
+    /// Add image from PNG bytes
+    pub fn png_bytes(self, name: impl AsRef<str>, bytes: &[u8]) -> Result<Self> {
+        let png_data = load_png(bytes, ColorSpace::Srgb)?;
+
+        // convert data to sRGBA
+        let data = match png_data.format {
+            Format::Srgb => png_data
+                .data
+                .chunks(3)
+                .flat_map(|p| vec![p[0], p[1], p[2], 255])
+                .collect(),
+            Format::Gray => png_data
+                .data
+                .iter()
+                .flat_map(|p| vec![*p, *p, *p, 255])
+                .collect(),
+            _ => png_data.data,
+        };
+
+        Ok(self.image(name, data, png_data.width, png_data.height))
+    }
+}
+
+pub(crate) fn load_png(bytes: &[u8], color_space: ColorSpace) -> Result<PngData> {
     let decoder = Decoder::new(bytes);
     let (info, mut reader) = decoder.read_info().map_err(|_| Error::InvalidPng)?;
 
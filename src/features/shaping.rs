@@ -0,0 +1,109 @@
+// Oliver Berzs
+// https://github.com/oberzs/duku
+
+//! Optional feature `shaping` module for OpenType layout support.
+
+#![cfg(feature = "shaping")]
+
+use std::collections::HashMap;
+use ttf_dep::gpos::PairAdjustment;
+use ttf_dep::gpos::PositioningSubtable;
+use ttf_dep::gsub::SubstitutionSubtable;
+use ttf_dep::opentype_layout::LayoutTable;
+use ttf_dep::opentype_layout::Lookup;
+use ttf_dep::Face;
+use ttf_dep::GlyphId;
+use ttf_dep::Tag;
+
+// reads standard ligatures from the GSUB table
+// that only use the given glyphs
+pub(crate) fn ligatures(bytes: &[u8], glyphs: &[u16]) -> HashMap<Vec<u32>, u32> {
+    let mut result = HashMap::new();
+    let table = match Face::parse(bytes, 0).ok().and_then(|f| f.tables().gsub) {
+        Some(t) => t,
+        None => return result,
+    };
+
+    for lookup in feature_lookups(&table, b"liga") {
+        for subtable in lookup.subtables.into_iter::<SubstitutionSubtable<'_>>() {
+            let sub = match subtable {
+                SubstitutionSubtable::Ligature(s) => s,
+                _ => continue,
+            };
+
+            for first in glyphs {
+                let set = match sub
+                    .coverage
+                    .get(GlyphId(*first))
+                    .and_then(|i| sub.ligature_sets.get(i))
+                {
+                    Some(s) => s,
+                    None => continue,
+                };
+
+                for ligature in set {
+                    let components: Vec<_> = ligature.components.into_iter().collect();
+                    if components.iter().all(|c| glyphs.contains(&c.0)) {
+                        let mut ids = vec![u32::from(*first)];
+                        ids.extend(components.iter().map(|c| u32::from(c.0)));
+                        result
+                            .entry(ids)
+                            .or_insert_with(|| u32::from(ligature.glyph.0));
+                    }
+                }
+            }
+        }
+    }
+
+    result
+}
+
+// reads kerning of a glyph pair in font units
+// from the GPOS table, if the font has it
+pub(crate) fn pair_kerning(bytes: &[u8], first: u16, second: u16) -> Option<f32> {
+    let table = Face::parse(bytes, 0).ok().and_then(|f| f.tables().gpos)?;
+
+    feature_lookups(&table, b"kern")
+        .into_iter()
+        .flat_map(|lookup| lookup.subtables.into_iter::<PositioningSubtable<'_>>())
+        .find_map(|subtable| match subtable {
+            PositioningSubtable::Pair(pair) => pair_advance(&pair, GlyphId(first), GlyphId(second)),
+            _ => None,
+        })
+        .map(f32::from)
+}
+
+fn pair_advance(pair: &PairAdjustment<'_>, first: GlyphId, second: GlyphId) -> Option<i16> {
+    match pair {
+        PairAdjustment::Format1 { coverage, sets } => {
+            let set = sets.get(coverage.get(first)?)?;
+            set.get(second).map(|(value, _)| value.x_advance)
+        }
+        PairAdjustment::Format2 {
+            coverage,
+            classes,
+            matrix,
+        } => {
+            coverage.get(first)?;
+            let class_pair = (classes.0.get(first), classes.1.get(second));
+            matrix.get(class_pair).map(|(value, _)| value.x_advance)
+        }
+    }
+}
+
+fn feature_lookups<'a>(table: &LayoutTable<'a>, tag: &[u8; 4]) -> Vec<Lookup<'a>> {
+    let tag = Tag::from_bytes(tag);
+    let mut indices: Vec<_> = table
+        .features
+        .into_iter()
+        .filter(|f| f.tag == tag)
+        .flat_map(|f| f.lookup_indices.into_iter())
+        .collect();
+    indices.sort_unstable();
+    indices.dedup();
+
+    indices
+        .into_iter()
+        .filter_map(|i| table.lookups.get(i))
+        .collect()
+}
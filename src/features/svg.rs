@@ -0,0 +1,767 @@
+// Oliver Berzs
+// https://github.com/oberzs/duku
+
+//! Optional feature `svg` module for SVG file support.
+
+#![cfg(feature = "svg")]
+
+use lyon_dep::math::point;
+use lyon_dep::path::Path as LyonPath;
+use lyon_dep::BuffersBuilder;
+use lyon_dep::FillOptions;
+use lyon_dep::FillRule as LyonFillRule;
+use lyon_dep::FillTessellator;
+use lyon_dep::FillVertex;
+use lyon_dep::LineCap as LyonLineCap;
+use lyon_dep::LineJoin as LyonLineJoin;
+use lyon_dep::StrokeOptions;
+use lyon_dep::StrokeTessellator;
+use lyon_dep::StrokeVertex;
+use lyon_dep::VertexBuffers;
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+use svg_dep::utils;
+use svg_dep::FillRule;
+use svg_dep::LineCap;
+use svg_dep::LineJoin;
+use svg_dep::Node;
+use svg_dep::NodeKind;
+use svg_dep::Options;
+use svg_dep::Paint;
+use svg_dep::PathData;
+use svg_dep::PathSegment;
+use svg_dep::Transform;
+use svg_dep::Tree;
+use svg_dep::Units;
+use svg_dep::Visibility;
+
+use crate::color::Mix;
+use crate::color::Rgb;
+use crate::duku::Duku;
+use crate::error::Error;
+use crate::error::Result;
+use crate::image::Format;
+use crate::image::Mips;
+use crate::image::Texture;
+use crate::math::Vec2;
+use crate::renderer::subdivide;
+use crate::resources::Handle;
+
+// subsamples per pixel side when rasterizing
+const SAMPLES: usize = 4;
+
+/// Vector image loaded from an SVG file.
+///
+/// Paths are tessellated into triangles
+/// with per-vertex colors when loading.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use duku::Duku;
+/// # let (mut d, _) = Duku::windowed(1, 1).unwrap();
+/// let icon = d.create_svg("icon.svg").unwrap();
+///
+/// # d.draw(None, |t| {
+/// // when drawing
+/// t.svg(&icon, [0.0, 0.0], [32.0, 32.0]);
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct Svg {
+    width: f32,
+    height: f32,
+    pub(crate) points: Vec<Vec2>,
+    pub(crate) colors: Vec<Rgb>,
+    shapes: Vec<Range<usize>>,
+}
+
+struct GradientPaint {
+    radial: bool,
+    start: Vec2,
+    end: Vec2,
+    bounding_box: bool,
+    transform: Transform,
+    stops: Vec<(f32, Rgb)>,
+}
+
+enum ShapePaint {
+    Solid(Rgb),
+    Gradient(GradientPaint),
+}
+
+impl Duku {
+    /// Create SVG image from file
+    pub fn create_svg(&mut self, path: impl AsRef<Path>) -> Result<Handle<Svg>> {
+        let bytes = fs::read(path.as_ref())?;
+        self.create_svg_bytes(&bytes)
+    }
+
+    /// Create SVG image from bytes
+    pub fn create_svg_bytes(&mut self, bytes: &[u8]) -> Result<Handle<Svg>> {
+        let svg = Svg::from_bytes(bytes)?;
+        Ok(Handle::new(svg))
+    }
+
+    /// Create texture by rasterizing an SVG file
+    pub fn create_texture_svg(
+        &mut self,
+        path: impl AsRef<Path>,
+        width: u32,
+        height: u32,
+    ) -> Result<Handle<Texture>> {
+        let bytes = fs::read(path.as_ref())?;
+        self.create_texture_svg_bytes(&bytes, width, height)
+    }
+
+    /// Create texture by rasterizing SVG bytes
+    pub fn create_texture_svg_bytes(
+        &mut self,
+        bytes: &[u8],
+        width: u32,
+        height: u32,
+    ) -> Result<Handle<Texture>> {
+        let data = Svg::from_bytes(bytes)?.rasterize(width, height);
+        self.create_texture(data, Format::Srgba, Mips::Zero, width, height)
+    }
+}
+
+impl Svg {
+    fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let tree = Tree::from_data(bytes, &Options::default()).map_err(|_| Error::InvalidSvg)?;
+
+        let (width, height, view_transform) = {
+            let node = tree.svg_node();
+            let transform =
+                utils::view_box_to_transform(node.view_box.rect, node.view_box.aspect, node.size);
+            (
+                node.size.width() as f32,
+                node.size.height() as f32,
+                transform,
+            )
+        };
+
+        let mut svg = Self {
+            width,
+            height,
+            points: vec![],
+            colors: vec![],
+            shapes: vec![],
+        };
+        svg.load_node(&tree, &tree.root(), view_transform, 1.0)?;
+
+        Ok(svg)
+    }
+
+    /// Get width of the SVG image
+    pub const fn width(&self) -> f32 {
+        self.width
+    }
+
+    /// Get height of the SVG image
+    pub const fn height(&self) -> f32 {
+        self.height
+    }
+
+    /// Rasterize SVG image into RGBA bytes
+    ///
+    /// The image is stretched to fit
+    /// `width` and `height`.
+    pub fn rasterize(&self, width: u32, height: u32) -> Vec<u8> {
+        let w = width as usize;
+        let h = height as usize;
+        let scale = Vec2::new(
+            width as f32 / self.width.max(1.0),
+            height as f32 / self.height.max(1.0),
+        );
+
+        // each shape is rasterized into a coverage layer
+        // so that shared triangle edges don't leave seams
+        let mut image = vec![[0.0f32; 4]; w * h];
+        let mut masks = vec![0u16; w * h];
+        let mut layer = vec![Rgb::clear(); w * h];
+
+        for shape in &self.shapes {
+            let mut min = (w, h);
+            let mut max = (0, 0);
+
+            for i in shape.clone().step_by(3) {
+                let to_pixels = |p: Vec2| Vec2::new(p.x * scale.x, p.y * scale.y);
+                let tri = [
+                    to_pixels(self.points[i]),
+                    to_pixels(self.points[i + 1]),
+                    to_pixels(self.points[i + 2]),
+                ];
+                let colors = [self.colors[i], self.colors[i + 1], self.colors[i + 2]];
+
+                let area = edge(tri[0], tri[1], tri[2]);
+                if area == 0.0 {
+                    continue;
+                }
+
+                // triangle bounds in pixels
+                let x0 = tri
+                    .iter()
+                    .fold(f32::MAX, |a, p| a.min(p.x))
+                    .floor()
+                    .max(0.0) as usize;
+                let y0 = tri
+                    .iter()
+                    .fold(f32::MAX, |a, p| a.min(p.y))
+                    .floor()
+                    .max(0.0) as usize;
+                let x1 = (tri.iter().fold(f32::MIN, |a, p| a.max(p.x)).ceil() as usize).min(w);
+                let y1 = (tri.iter().fold(f32::MIN, |a, p| a.max(p.y)).ceil() as usize).min(h);
+                min = (min.0.min(x0), min.1.min(y0));
+                max = (max.0.max(x1), max.1.max(y1));
+
+                for y in y0..y1 {
+                    for x in x0..x1 {
+                        let mut mask = 0;
+                        for sy in 0..SAMPLES {
+                            for sx in 0..SAMPLES {
+                                let p = Vec2::new(
+                                    x as f32 + (sx as f32 + 0.5) / SAMPLES as f32,
+                                    y as f32 + (sy as f32 + 0.5) / SAMPLES as f32,
+                                );
+                                if inside(tri, area, p) {
+                                    mask |= 1 << (sy * SAMPLES + sx);
+                                }
+                            }
+                        }
+
+                        if mask != 0 {
+                            let i = y * w + x;
+                            let center = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+                            masks[i] |= mask;
+                            layer[i] = interpolate(tri, area, colors, center);
+                        }
+                    }
+                }
+            }
+
+            // composite layer into image
+            for y in min.1..max.1 {
+                for x in min.0..max.0 {
+                    let i = y * w + x;
+                    if masks[i] == 0 {
+                        continue;
+                    }
+
+                    let coverage = masks[i].count_ones() as f32 / (SAMPLES * SAMPLES) as f32;
+                    let c = layer[i];
+                    let src_a = f32::from(c.a) / 255.0 * coverage;
+                    let dst = image[i];
+                    let out_a = src_a + dst[3] * (1.0 - src_a);
+                    if out_a > 0.0 {
+                        for (ch, src) in [c.r, c.g, c.b].iter().enumerate() {
+                            let src_c = f32::from(*src) / 255.0;
+                            image[i][ch] =
+                                (src_c * src_a + dst[ch] * dst[3] * (1.0 - src_a)) / out_a;
+                        }
+                    }
+                    image[i][3] = out_a;
+
+                    masks[i] = 0;
+                }
+            }
+        }
+
+        image
+            .iter()
+            .flat_map(|c| c.iter().map(|v| (v * 255.0).round() as u8))
+            .collect()
+    }
+
+    fn load_node(
+        &mut self,
+        tree: &Tree,
+        node: &Node,
+        transform: Transform,
+        opacity: f32,
+    ) -> Result<()> {
+        match *node.borrow() {
+            NodeKind::Svg(_) => {
+                for child in node.children() {
+                    self.load_node(tree, &child, transform, opacity)?;
+                }
+            }
+            NodeKind::Group(ref group) => {
+                let mut ts = transform;
+                ts.append(&group.transform);
+                let o = opacity * group.opacity.value() as f32;
+                for child in node.children() {
+                    self.load_node(tree, &child, ts, o)?;
+                }
+            }
+            NodeKind::Path(ref path) => {
+                if path.visibility != Visibility::Visible {
+                    return Ok(());
+                }
+
+                let mut ts = transform;
+                ts.append(&path.transform);
+
+                // scale tolerance with the transform
+                // so curves stay smooth when scaled up
+                let scale = (ts.a * ts.d - ts.b * ts.c).abs().sqrt().max(0.0001) as f32;
+                let tolerance = 0.1 / scale;
+                let lyon_path = build_path(&path.data);
+
+                if let Some(fill) = &path.fill {
+                    if let Some(paint) = load_paint(tree, &fill.paint) {
+                        let rule = match fill.rule {
+                            FillRule::NonZero => LyonFillRule::NonZero,
+                            FillRule::EvenOdd => LyonFillRule::EvenOdd,
+                        };
+                        let options = FillOptions::tolerance(tolerance).with_fill_rule(rule);
+
+                        let mut buffers: VertexBuffers<Vec2, u32> = VertexBuffers::new();
+                        FillTessellator::new()
+                            .tessellate_path(
+                                &lyon_path,
+                                &options,
+                                &mut BuffersBuilder::new(&mut buffers, |v: FillVertex<'_>| {
+                                    Vec2::new(v.position().x, v.position().y)
+                                }),
+                            )
+                            .map_err(|_| Error::InvalidSvg)?;
+
+                        let o = opacity * fill.opacity.value() as f32;
+                        self.add_shape(&path.data, &buffers, &paint, o, ts);
+                    }
+                }
+
+                if let Some(stroke) = &path.stroke {
+                    if let Some(paint) = load_paint(tree, &stroke.paint) {
+                        let cap = match stroke.linecap {
+                            LineCap::Butt => LyonLineCap::Butt,
+                            LineCap::Round => LyonLineCap::Round,
+                            LineCap::Square => LyonLineCap::Square,
+                        };
+                        let join = match stroke.linejoin {
+                            LineJoin::Miter => LyonLineJoin::Miter,
+                            LineJoin::Round => LyonLineJoin::Round,
+                            LineJoin::Bevel => LyonLineJoin::Bevel,
+                        };
+                        let options = StrokeOptions::tolerance(tolerance)
+                            .with_line_width(stroke.width.value() as f32)
+                            .with_line_cap(cap)
+                            .with_line_join(join)
+                            .with_miter_limit(stroke.miterlimit.value() as f32);
+
+                        let mut buffers: VertexBuffers<Vec2, u32> = VertexBuffers::new();
+                        StrokeTessellator::new()
+                            .tessellate_path(
+                                &lyon_path,
+                                &options,
+                                &mut BuffersBuilder::new(
+                                    &mut buffers,
+                                    |v: StrokeVertex<'_, '_>| {
+                                        Vec2::new(v.position().x, v.position().y)
+                                    },
+                                ),
+                            )
+                            .map_err(|_| Error::InvalidSvg)?;
+
+                        let o = opacity * stroke.opacity.value() as f32;
+                        self.add_shape(&path.data, &buffers, &paint, o, ts);
+                    }
+                }
+            }
+            // definitions, clip paths, masks and images are not drawn
+            _ => (),
+        }
+
+        Ok(())
+    }
+
+    fn add_shape(
+        &mut self,
+        data: &PathData,
+        buffers: &VertexBuffers<Vec2, u32>,
+        paint: &ShapePaint,
+        opacity: f32,
+        transform: Transform,
+    ) {
+        // gradient triangles are split relative to the path size
+        let size = data
+            .bbox()
+            .map(|r| r.width().max(r.height()) as f32)
+            .unwrap_or(0.0);
+
+        let mut tris = vec![];
+        for chunk in buffers.indices.chunks(3) {
+            let tri = [
+                buffers.vertices[chunk[0] as usize],
+                buffers.vertices[chunk[1] as usize],
+                buffers.vertices[chunk[2] as usize],
+            ];
+
+            // split gradient triangles, so
+            // that colors can change inside
+            match paint {
+                ShapePaint::Gradient(_) if size > 0.0 => subdivide(tri, size / 16.0, 8, &mut tris),
+                _ => tris.push(tri),
+            }
+        }
+
+        let start = self.points.len();
+        for point in tris.iter().flatten() {
+            let color = match paint {
+                ShapePaint::Solid(c) => *c,
+                ShapePaint::Gradient(g) => g.color_at(*point, data),
+            };
+            let (x, y) = transform.apply(f64::from(point.x), f64::from(point.y));
+            self.points.push(Vec2::new(x as f32, y as f32));
+            self.colors
+                .push(color.alpha((f32::from(color.a) * opacity) as u8));
+        }
+        self.shapes.push(start..self.points.len());
+    }
+}
+
+impl GradientPaint {
+    fn color_at(&self, pos: Vec2, data: &PathData) -> Rgb {
+        // move position into gradient space
+        let mut p = pos;
+        if self.bounding_box {
+            if let Some(r) = data.bbox() {
+                p = Vec2::new(
+                    (p.x - r.x() as f32) / r.width().max(0.0001) as f32,
+                    (p.y - r.y() as f32) / r.height().max(0.0001) as f32,
+                );
+            }
+        }
+        let ts = &self.transform;
+        let det = ts.a * ts.d - ts.b * ts.c;
+        if det != 0.0 {
+            let x = f64::from(p.x) - ts.e;
+            let y = f64::from(p.y) - ts.f;
+            p = Vec2::new(
+                ((ts.d * x - ts.c * y) / det) as f32,
+                ((ts.a * y - ts.b * x) / det) as f32,
+            );
+        }
+
+        // find progress along the gradient
+        let progress = if self.radial {
+            let radius = self.end.x;
+            if radius == 0.0 {
+                1.0
+            } else {
+                (p - self.start).length() / radius
+            }
+        } else {
+            let dir = self.end - self.start;
+            let sqr_length = dir.sqr_length();
+            if sqr_length == 0.0 {
+                0.0
+            } else {
+                (p - self.start).dot(dir) / sqr_length
+            }
+        };
+
+        // mix between surrounding stops
+        let first = self.stops[0];
+        let last = self.stops[self.stops.len() - 1];
+        if progress <= first.0 {
+            return first.1;
+        }
+        for pair in self.stops.windows(2) {
+            let (o1, c1) = pair[0];
+            let (o2, c2) = pair[1];
+            if progress <= o2 {
+                let p = if o2 > o1 {
+                    (progress - o1) / (o2 - o1)
+                } else {
+                    1.0
+                };
+                return Rgb::mix(c1, c2, p);
+            }
+        }
+        last.1
+    }
+}
+
+fn load_paint(tree: &Tree, paint: &Paint) -> Option<ShapePaint> {
+    match paint {
+        Paint::Color(c) => Some(ShapePaint::Solid(Rgb::new(c.red, c.green, c.blue))),
+        Paint::Link(id) => {
+            let node = tree.defs_by_id(id)?;
+            let kind = node.borrow();
+            let (radial, start, end, base) = match *kind {
+                NodeKind::LinearGradient(ref g) => (
+                    false,
+                    Vec2::new(g.x1 as f32, g.y1 as f32),
+                    Vec2::new(g.x2 as f32, g.y2 as f32),
+                    &g.base,
+                ),
+                // radius is stored in the end point,
+                // focal point is not supported
+                NodeKind::RadialGradient(ref g) => (
+                    true,
+                    Vec2::new(g.cx as f32, g.cy as f32),
+                    Vec2::new(g.r.value() as f32, 0.0),
+                    &g.base,
+                ),
+                // patterns are not supported
+                _ => return None,
+            };
+
+            let stops: Vec<_> = base
+                .stops
+                .iter()
+                .map(|s| {
+                    let a = (s.opacity.value() * 255.0) as u8;
+                    let color = Rgb::new(s.color.red, s.color.green, s.color.blue).alpha(a);
+                    (s.offset.value() as f32, color)
+                })
+                .collect();
+
+            if stops.is_empty() {
+                None
+            } else {
+                Some(ShapePaint::Gradient(GradientPaint {
+                    radial,
+                    start,
+                    end,
+                    bounding_box: base.units == Units::ObjectBoundingBox,
+                    transform: base.transform,
+                    stops,
+                }))
+            }
+        }
+    }
+}
+
+fn build_path(data: &PathData) -> LyonPath {
+    let mut builder = LyonPath::builder();
+    let mut open = false;
+
+    for segment in data.iter() {
+        match *segment {
+            PathSegment::MoveTo { x, y } => {
+                if open {
+                    builder.end(false);
+                }
+                builder.begin(point(x as f32, y as f32));
+                open = true;
+            }
+            PathSegment::LineTo { x, y } => {
+                builder.line_to(point(x as f32, y as f32));
+            }
+            PathSegment::CurveTo {
+                x1,
+                y1,
+                x2,
+                y2,
+                x,
+                y,
+            } => {
+                builder.cubic_bezier_to(
+                    point(x1 as f32, y1 as f32),
+                    point(x2 as f32, y2 as f32),
+                    point(x as f32, y as f32),
+                );
+            }
+            PathSegment::ClosePath => {
+                if open {
+                    builder.end(true);
+                }
+                open = false;
+            }
+        }
+    }
+
+    if open {
+        builder.end(false);
+    }
+
+    builder.build()
+}
+
+fn edge(a: Vec2, b: Vec2, p: Vec2) -> f32 {
+    (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+}
+
+fn inside(tri: [Vec2; 3], area: f32, p: Vec2) -> bool {
+    let w0 = edge(tri[1], tri[2], p) / area;
+    let w1 = edge(tri[2], tri[0], p) / area;
+    let w2 = edge(tri[0], tri[1], p) / area;
+    w0 >= 0.0 && w1 >= 0.0 && w2 >= 0.0
+}
+
+fn interpolate(tri: [Vec2; 3], area: f32, colors: [Rgb; 3], p: Vec2) -> Rgb {
+    let w0 = (edge(tri[1], tri[2], p) / area).max(0.0);
+    let w1 = (edge(tri[2], tri[0], p) / area).max(0.0);
+    let w2 = (edge(tri[0], tri[1], p) / area).max(0.0);
+    let sum = (w0 + w1 + w2).max(0.0001);
+
+    let mix = |a: u8, b: u8, c: u8| {
+        ((f32::from(a) * w0 + f32::from(b) * w1 + f32::from(c) * w2) / sum).round() as u8
+    };
+
+    Rgb::new(
+        mix(colors[0].r, colors[1].r, colors[2].r),
+        mix(colors[0].g, colors[1].g, colors[2].g),
+        mix(colors[0].b, colors[1].b, colors[2].b),
+    )
+    .alpha(mix(colors[0].a, colors[1].a, colors[2].a))
+}
+
+#[cfg(test)]
+mod test {
+    use lyon_dep::path::Event;
+    use lyon_dep::VertexBuffers;
+    use svg_dep::PathData;
+    use svg_dep::Transform;
+
+    use super::build_path;
+    use super::GradientPaint;
+    use super::ShapePaint;
+    use super::Svg;
+    use crate::color::Rgb;
+    use crate::math::Vec2;
+
+    const SQUARE: &[u8] = br##"<svg xmlns="http://www.w3.org/2000/svg" width="4" height="4">
+        <rect x="0" y="0" width="2" height="4" fill="#ff0000"/>
+    </svg>"##;
+
+    #[test]
+    fn from_bytes() {
+        let svg = Svg::from_bytes(SQUARE).unwrap();
+        assert_eq_delta!(svg.width(), 4.0);
+        assert_eq_delta!(svg.height(), 4.0);
+        assert_eq!(svg.points.len() % 3, 0);
+        assert!(svg.colors.iter().all(|c| *c == Rgb::new(255, 0, 0)));
+    }
+
+    #[test]
+    fn invalid_bytes() {
+        assert!(Svg::from_bytes(b"not svg").is_err());
+    }
+
+    #[test]
+    fn rasterize() {
+        let data = Svg::from_bytes(SQUARE).unwrap().rasterize(4, 4);
+        assert_eq!(data.len(), 4 * 4 * 4);
+
+        // left half is red, right half is clear
+        assert_eq!(&data[0..4], &[255, 0, 0, 255]);
+        assert_eq!(&data[4..8], &[255, 0, 0, 255]);
+        assert_eq!(data[8 + 3], 0);
+        assert_eq!(data[12 + 3], 0);
+    }
+
+    #[test]
+    fn rasterize_scaled() {
+        let data = Svg::from_bytes(SQUARE).unwrap().rasterize(8, 2);
+        assert_eq!(data.len(), 8 * 2 * 4);
+        assert_eq!(&data[12..16], &[255, 0, 0, 255]);
+        assert_eq!(data[16 + 3], 0);
+    }
+
+    #[test]
+    fn path_events() {
+        let mut data = PathData::new();
+        data.push_move_to(0.0, 0.0);
+        data.push_line_to(1.0, 0.0);
+        data.push_close_path();
+        data.push_move_to(2.0, 2.0);
+        data.push_curve_to(2.0, 3.0, 3.0, 3.0, 3.0, 2.0);
+
+        let events: Vec<_> = build_path(&data).iter().collect();
+        assert_eq!(events.len(), 6);
+        assert!(matches!(events[0], Event::Begin { .. }));
+        assert!(matches!(events[1], Event::Line { .. }));
+        assert!(matches!(events[2], Event::End { close: true, .. }));
+        assert!(matches!(events[3], Event::Begin { .. }));
+        assert!(matches!(events[4], Event::Cubic { .. }));
+        assert!(matches!(events[5], Event::End { close: false, .. }));
+    }
+
+    #[test]
+    fn linear_gradient() {
+        let gradient = GradientPaint {
+            radial: false,
+            start: Vec2::new(0.0, 0.0),
+            end: Vec2::new(10.0, 0.0),
+            bounding_box: false,
+            transform: Transform::default(),
+            stops: vec![(0.0, Rgb::new(0, 0, 0)), (1.0, Rgb::new(200, 100, 0))],
+        };
+        let data = PathData::new();
+
+        assert_eq!(
+            gradient.color_at(Vec2::new(-5.0, 0.0), &data),
+            Rgb::new(0, 0, 0)
+        );
+        assert_eq!(
+            gradient.color_at(Vec2::new(5.0, 3.0), &data),
+            Rgb::new(100, 50, 0)
+        );
+        assert_eq!(
+            gradient.color_at(Vec2::new(20.0, 0.0), &data),
+            Rgb::new(200, 100, 0)
+        );
+    }
+
+    #[test]
+    fn radial_bounding_box_gradient() {
+        let gradient = GradientPaint {
+            radial: true,
+            start: Vec2::new(0.5, 0.5),
+            end: Vec2::new(0.5, 0.0),
+            bounding_box: true,
+            transform: Transform::default(),
+            stops: vec![(0.0, Rgb::new(255, 255, 255)), (1.0, Rgb::new(0, 0, 0))],
+        };
+        let mut data = PathData::new();
+        data.push_move_to(10.0, 10.0);
+        data.push_line_to(20.0, 20.0);
+
+        let center = gradient.color_at(Vec2::new(15.0, 15.0), &data);
+        let edge = gradient.color_at(Vec2::new(20.0, 15.0), &data);
+        assert_eq!(center, Rgb::new(255, 255, 255));
+        assert_eq!(edge, Rgb::new(0, 0, 0));
+    }
+
+    #[test]
+    fn gradient_subdivision() {
+        let gradient = || {
+            ShapePaint::Gradient(GradientPaint {
+                radial: false,
+                start: Vec2::new(0.0, 0.0),
+                end: Vec2::new(1.0, 0.0),
+                bounding_box: false,
+                transform: Transform::default(),
+                stops: vec![(0.0, Rgb::new(0, 0, 0))],
+            })
+        };
+        let mut buffers: VertexBuffers<Vec2, u32> = VertexBuffers::new();
+        buffers.vertices = vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(16.0, 0.0),
+            Vec2::new(0.0, 16.0),
+        ];
+        buffers.indices = vec![0, 1, 2];
+
+        let mut svg = Svg::from_bytes(SQUARE).unwrap();
+        let start = svg.points.len();
+
+        // path without size is not subdivided
+        let mut point = PathData::new();
+        point.push_move_to(5.0, 5.0);
+        point.push_line_to(5.0, 5.0);
+        svg.add_shape(&point, &buffers, &gradient(), 1.0, Transform::default());
+        assert_eq!(svg.points.len() - start, 3);
+
+        let mut data = PathData::new();
+        data.push_move_to(0.0, 0.0);
+        data.push_line_to(16.0, 16.0);
+        svg.add_shape(&data, &buffers, &gradient(), 1.0, Transform::default());
+        assert!(svg.points.len() - start > 6);
+    }
+}
@@ -0,0 +1,318 @@
+// Oliver Berzs
+// https://github.com/oberzs/duku
+
+//! Optional feature `tiled` module for Tiled map support.
+
+#![cfg(feature = "tiled")]
+
+use json_dep::Value;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use xml_dep::Document;
+use xml_dep::Node;
+
+use crate::duku::Duku;
+use crate::error::Error;
+use crate::error::Result;
+use crate::mesh::Tile;
+use crate::mesh::TileRotation;
+use crate::mesh::Tilemap;
+use crate::mesh::Tileset;
+use crate::resources::Handle;
+
+// global tile id flags
+const FLIP_X: u32 = 0x8000_0000;
+const FLIP_Y: u32 = 0x4000_0000;
+const FLIP_DIAGONAL: u32 = 0x2000_0000;
+
+struct TiledMap {
+    width: u32,
+    height: u32,
+    tileset: TiledTileset,
+    layers: Vec<TiledLayer>,
+}
+
+struct TiledTileset {
+    first_gid: u32,
+    image: PathBuf,
+    tile_width: u32,
+    tile_height: u32,
+    margin: u32,
+    spacing: u32,
+}
+
+struct TiledLayer {
+    gids: Vec<u32>,
+    visible: bool,
+}
+
+impl Duku {
+    /// Create a tilemap from a Tiled map file
+    ///
+    /// Supports `.tmx` and `.json` maps with
+    /// a single tileset using a PNG image.
+    pub fn create_tilemap_tiled(&mut self, path: impl AsRef<Path>) -> Result<Handle<Tilemap>> {
+        let bytes = fs::read(path.as_ref())?;
+        let root = path.as_ref().parent().unwrap_or_else(|| Path::new("./"));
+        self.create_tilemap_tiled_bytes(&bytes, root)
+    }
+
+    /// Create a tilemap from Tiled map bytes
+    ///
+    /// `root` is used to find the tileset files.
+    pub fn create_tilemap_tiled_bytes(
+        &mut self,
+        bytes: &[u8],
+        root: impl AsRef<Path>,
+    ) -> Result<Handle<Tilemap>> {
+        let map = load_map(bytes, root.as_ref())?;
+
+        let texture = self.create_texture_png(root.as_ref().join(&map.tileset.image), None)?;
+        let mut tileset = Tileset::new(&texture, map.tileset.tile_width, map.tileset.tile_height);
+        tileset.margin = map.tileset.margin;
+        tileset.spacing = map.tileset.spacing;
+
+        let tilemap = self.create_tilemap(tileset, map.width, map.height, map.layers.len());
+        {
+            let mut t = tilemap.write();
+            for (i, layer) in map.layers.iter().enumerate() {
+                t.set_layer_visible(i, layer.visible);
+                for (j, gid) in layer.gids.iter().enumerate() {
+                    let x = j as u32 % map.width;
+                    let y = j as u32 / map.width;
+                    t.set_tile(i, x, y, load_tile(*gid, map.tileset.first_gid));
+                }
+            }
+        }
+
+        Ok(tilemap)
+    }
+}
+
+fn load_map(bytes: &[u8], root: &Path) -> Result<TiledMap> {
+    if is_json(bytes) {
+        let json: Value = json_dep::from_slice(bytes).map_err(|_| Error::InvalidTiled)?;
+        load_json_map(&json, root)
+    } else {
+        let text = std::str::from_utf8(bytes).map_err(|_| Error::InvalidTiled)?;
+        let doc = Document::parse(text).map_err(|_| Error::InvalidTiled)?;
+        load_xml_map(doc.root_element(), root)
+    }
+}
+
+const fn load_tile(gid: u32, first_gid: u32) -> Option<Tile> {
+    let id = gid & !(FLIP_X | FLIP_Y | FLIP_DIAGONAL);
+    if id < first_gid || id == 0 {
+        return None;
+    }
+
+    let flip_x = gid & FLIP_X != 0;
+    let flip_y = gid & FLIP_Y != 0;
+
+    // diagonal flip is a rotation and a horizontal flip
+    let (rotation, flip_x) = if gid & FLIP_DIAGONAL != 0 {
+        (TileRotation::Deg90, !flip_x)
+    } else {
+        (TileRotation::None, flip_x)
+    };
+
+    Some(Tile {
+        index: id - first_gid,
+        flip_x,
+        flip_y,
+        rotation,
+    })
+}
+
+fn load_json_map(json: &Value, root: &Path) -> Result<TiledMap> {
+    if json["infinite"].as_bool() == Some(true) {
+        return Err(Error::UnsupportedTiled("infinite".to_string()));
+    }
+
+    let width = json_u32(&json["width"])?;
+    let height = json_u32(&json["height"])?;
+
+    let tilesets = json["tilesets"].as_array().ok_or(Error::InvalidTiled)?;
+    let tileset = match tilesets.as_slice() {
+        [t] => t,
+        _ => return Err(Error::UnsupportedTiled("multiple tilesets".to_string())),
+    };
+    let first_gid = json_u32(&tileset["firstgid"])?;
+    let tileset = match tileset["source"].as_str() {
+        Some(source) => load_external_tileset(root, source, first_gid)?,
+        None => load_json_tileset(tileset, PathBuf::new(), first_gid)?,
+    };
+
+    let mut layers = vec![];
+    for layer in json["layers"].as_array().ok_or(Error::InvalidTiled)? {
+        if layer["type"] != "tilelayer" {
+            continue;
+        }
+        if !layer["compression"].as_str().unwrap_or("").is_empty() {
+            return Err(Error::UnsupportedTiled("compression".to_string()));
+        }
+
+        let gids = match &layer["data"] {
+            Value::Array(values) => values.iter().map(json_u32).collect::<Result<_>>()?,
+            Value::String(data) => decode_base64(data)?,
+            _ => return Err(Error::InvalidTiled),
+        };
+        if gids.len() != (width * height) as usize {
+            return Err(Error::InvalidTiled);
+        }
+
+        layers.push(TiledLayer {
+            visible: layer["visible"].as_bool().unwrap_or(true),
+            gids,
+        });
+    }
+
+    Ok(TiledMap {
+        width,
+        height,
+        tileset,
+        layers,
+    })
+}
+
+fn load_json_tileset(json: &Value, dir: PathBuf, first_gid: u32) -> Result<TiledTileset> {
+    let image = json["image"].as_str().ok_or(Error::InvalidTiled)?;
+
+    Ok(TiledTileset {
+        image: dir.join(image),
+        tile_width: json_u32(&json["tilewidth"])?,
+        tile_height: json_u32(&json["tileheight"])?,
+        margin: json["margin"].as_u64().unwrap_or(0) as u32,
+        spacing: json["spacing"].as_u64().unwrap_or(0) as u32,
+        first_gid,
+    })
+}
+
+fn load_xml_map(node: Node<'_, '_>, root: &Path) -> Result<TiledMap> {
+    if xml_u32(node, "infinite").unwrap_or(0) == 1 {
+        return Err(Error::UnsupportedTiled("infinite".to_string()));
+    }
+
+    let width = xml_u32(node, "width")?;
+    let height = xml_u32(node, "height")?;
+
+    let tilesets: Vec<_> = node
+        .children()
+        .filter(|n| n.has_tag_name("tileset"))
+        .collect();
+    let tileset = match tilesets.as_slice() {
+        [t] => *t,
+        _ => return Err(Error::UnsupportedTiled("multiple tilesets".to_string())),
+    };
+    let first_gid = xml_u32(tileset, "firstgid")?;
+    let tileset = match tileset.attribute("source") {
+        Some(source) => load_external_tileset(root, source, first_gid)?,
+        None => load_xml_tileset(tileset, PathBuf::new(), first_gid)?,
+    };
+
+    let mut layers = vec![];
+    for layer in node.children().filter(|n| n.has_tag_name("layer")) {
+        let data = layer
+            .children()
+            .find(|n| n.has_tag_name("data"))
+            .ok_or(Error::InvalidTiled)?;
+        if data.attribute("compression").is_some() {
+            return Err(Error::UnsupportedTiled("compression".to_string()));
+        }
+
+        let text = data.text().unwrap_or("");
+        let gids = match data.attribute("encoding") {
+            Some("csv") => text
+                .split(',')
+                .map(|s| s.trim().parse().map_err(|_| Error::InvalidTiled))
+                .collect::<Result<_>>()?,
+            Some("base64") => decode_base64(text)?,
+            Some(encoding) => return Err(Error::UnsupportedTiled(encoding.to_string())),
+            None => data
+                .children()
+                .filter(|n| n.has_tag_name("tile"))
+                .map(|n| xml_u32(n, "gid").unwrap_or(0))
+                .collect(),
+        };
+        if gids.len() != (width * height) as usize {
+            return Err(Error::InvalidTiled);
+        }
+
+        layers.push(TiledLayer {
+            visible: xml_u32(layer, "visible").unwrap_or(1) != 0,
+            gids,
+        });
+    }
+
+    Ok(TiledMap {
+        width,
+        height,
+        tileset,
+        layers,
+    })
+}
+
+fn load_xml_tileset(node: Node<'_, '_>, dir: PathBuf, first_gid: u32) -> Result<TiledTileset> {
+    let image = node
+        .children()
+        .find(|n| n.has_tag_name("image"))
+        .and_then(|n| n.attribute("source"))
+        .ok_or(Error::InvalidTiled)?;
+
+    Ok(TiledTileset {
+        image: dir.join(image),
+        tile_width: xml_u32(node, "tilewidth")?,
+        tile_height: xml_u32(node, "tileheight")?,
+        margin: xml_u32(node, "margin").unwrap_or(0),
+        spacing: xml_u32(node, "spacing").unwrap_or(0),
+        first_gid,
+    })
+}
+
+// loads .tsx or .json tileset from a separate file
+fn load_external_tileset(root: &Path, source: &str, first_gid: u32) -> Result<TiledTileset> {
+    let bytes = fs::read(root.join(source))?;
+
+    // tileset image is relative to the tileset file
+    let dir = Path::new(source)
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+
+    if is_json(&bytes) {
+        let json: Value = json_dep::from_slice(&bytes).map_err(|_| Error::InvalidTiled)?;
+        load_json_tileset(&json, dir, first_gid)
+    } else {
+        let text = std::str::from_utf8(&bytes).map_err(|_| Error::InvalidTiled)?;
+        let doc = Document::parse(text).map_err(|_| Error::InvalidTiled)?;
+        load_xml_tileset(doc.root_element(), dir, first_gid)
+    }
+}
+
+// gids are stored as little endian u32 values
+fn decode_base64(data: &str) -> Result<Vec<u32>> {
+    let bytes = base64_dep::decode(data.trim()).map_err(|_| Error::InvalidTiled)?;
+    if bytes.len() % 4 != 0 {
+        return Err(Error::InvalidTiled);
+    }
+
+    Ok(bytes
+        .chunks(4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect())
+}
+
+fn is_json(bytes: &[u8]) -> bool {
+    bytes.iter().find(|b| !b.is_ascii_whitespace()) == Some(&b'{')
+}
+
+fn json_u32(value: &Value) -> Result<u32> {
+    value.as_u64().map(|n| n as u32).ok_or(Error::InvalidTiled)
+}
+
+fn xml_u32(node: Node<'_, '_>, name: &str) -> Result<u32> {
+    node.attribute(name)
+        .and_then(|a| a.parse().ok())
+        .ok_or(Error::InvalidTiled)
+}
@@ -0,0 +1,417 @@
+// Oliver Berzs
+// https://github.com/oberzs/duku
+
+// GlyphCache - lazily rasterized glyphs in a growing atlas
+
+use std::collections::HashMap;
+
+use super::CharData;
+#[cfg(any(feature = "otf", test))]
+use super::FontMetrics;
+use crate::math::Vec2;
+use crate::math::Vec4;
+
+// atlas size limits in pixels, max is
+// the smallest image size all GPUs support
+#[cfg(any(feature = "otf", test))]
+pub(crate) const MIN_ATLAS_SIZE: u32 = 64;
+#[cfg(any(feature = "otf", test))]
+pub(crate) const MAX_ATLAS_SIZE: u32 = 4096;
+
+// empty space around glyphs in a cell
+const CELL_MARGIN: u32 = 1;
+
+// font that can rasterize glyphs on demand
+pub(crate) trait GlyphSource: Send + Sync {
+    // metrics in ems
+    #[cfg(any(feature = "otf", test))]
+    fn metrics(&self) -> FontMetrics;
+
+    fn px_size(&self) -> u32;
+
+    // returns 0 for missing characters
+    fn glyph_id(&self, c: char) -> u32;
+
+    // kerning between glyphs in ems
+    fn kerning(&self, first: u32, second: u32) -> f32;
+
+    fn rasterize(&self, id: u32) -> RasterGlyph;
+}
+
+// grayscale glyph bitmap with metrics in pixels
+pub(crate) struct RasterGlyph {
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    pub(crate) data: Vec<u8>,
+    pub(crate) bearing: Vec2,
+    pub(crate) advance: f32,
+}
+
+// glyphs are stored in equal square cells so
+// any free or evicted cell fits any glyph
+pub(crate) struct GlyphCache {
+    source: Box<dyn GlyphSource>,
+    data: Vec<u8>,
+    size: u32,
+    max_size: u32,
+    cell: u32,
+    free: Vec<(u32, u32)>,
+    entries: HashMap<u32, Entry>,
+    frame: u64,
+    grow: bool,
+    dirty: bool,
+}
+
+struct Entry {
+    cell: Option<(u32, u32)>,
+    width: u32,
+    height: u32,
+    last_used: u64,
+    // frame of the last rasterization
+    tried: u64,
+}
+
+impl GlyphCache {
+    #[cfg(any(feature = "otf", test))]
+    pub(crate) fn new(source: Box<dyn GlyphSource>) -> Self {
+        let metrics = source.metrics();
+        let px = source.px_size() as f32;
+        let cell = (metrics.height.max(1.0) * px).ceil() as u32 + CELL_MARGIN * 2;
+
+        // start with room for a few glyphs
+        let size = (cell * 4)
+            .next_power_of_two()
+            .max(MIN_ATLAS_SIZE)
+            .min(MAX_ATLAS_SIZE);
+
+        Self {
+            free: free_cells(cell, 0, size),
+            data: vec![0; (size * size) as usize],
+            max_size: MAX_ATLAS_SIZE,
+            entries: HashMap::new(),
+            frame: 0,
+            grow: false,
+            dirty: false,
+            source,
+            size,
+            cell,
+        }
+    }
+
+    #[cfg(feature = "otf")]
+    pub(crate) fn metrics(&self) -> FontMetrics {
+        self.source.metrics()
+    }
+
+    pub(crate) fn glyph_id(&self, c: char) -> u32 {
+        self.source.glyph_id(c)
+    }
+
+    pub(crate) fn kerning(&self, first: u32, second: u32) -> f32 {
+        self.source.kerning(first, second)
+    }
+
+    pub(crate) const fn size(&self) -> u32 {
+        self.size
+    }
+
+    pub(crate) fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub(crate) const fn should_grow(&self) -> bool {
+        self.grow && self.size < self.max_size
+    }
+
+    pub(crate) fn take_dirty(&mut self) -> bool {
+        let dirty = self.dirty;
+        self.dirty = false;
+        dirty
+    }
+
+    // glyphs used after this can't be evicted
+    // until the next frame
+    pub(crate) fn next_frame(&mut self) {
+        self.frame += 1;
+    }
+
+    // stops growing when the atlas
+    // texture couldn't be created
+    pub(crate) fn stop_growing(&mut self) {
+        self.max_size = self.size;
+        self.grow = false;
+    }
+
+    // doubles the atlas size keeping glyphs in place
+    pub(crate) fn grow(&mut self, glyph_data: &mut HashMap<u32, CharData>) {
+        let old_size = self.size;
+        let size = old_size * 2;
+
+        let mut data = vec![0; (size * size) as usize];
+        for (y, row) in self.data.chunks(old_size as usize).enumerate() {
+            let start = y * size as usize;
+            data[start..start + row.len()].copy_from_slice(row);
+        }
+
+        self.data = data;
+        self.size = size;
+        self.free.extend(free_cells(self.cell, old_size, size));
+        self.grow = false;
+        self.dirty = true;
+
+        // uvs depend on the atlas size
+        for (id, entry) in &self.entries {
+            if let (Some(cell), Some(data)) = (entry.cell, glyph_data.get_mut(id)) {
+                data.uvs = self.uvs(cell, entry.width, entry.height);
+            }
+        }
+    }
+
+    // makes sure glyph is loaded and marks it as used
+    pub(crate) fn load(&mut self, id: u32, glyph_data: &mut HashMap<u32, CharData>) {
+        if let Some(entry) = self.entries.get_mut(&id) {
+            entry.last_used = self.frame;
+            if entry.cell.is_some() || entry.width == 0 || entry.height == 0 {
+                return;
+            }
+
+            // glyphs waiting for a cell are
+            // retried once per frame
+            if entry.tried == self.frame {
+                return;
+            }
+        }
+
+        let raster = self.source.rasterize(id);
+        let px = self.source.px_size() as f32;
+
+        // glyphs larger than a cell are clipped
+        let max = self.cell - CELL_MARGIN * 2;
+        let width = raster.width.min(max);
+        let height = raster.height.min(max);
+
+        let cell = if width == 0 || height == 0 {
+            None
+        } else {
+            self.allocate(glyph_data)
+        };
+
+        if let Some((x, y)) = cell {
+            self.clear_cell(x, y);
+            for row in 0..height {
+                for col in 0..width {
+                    let src = (col + row * raster.width) as usize;
+                    let dst =
+                        (x + CELL_MARGIN + col + (y + CELL_MARGIN + row) * self.size) as usize;
+                    self.data[dst] = raster.data[src];
+                }
+            }
+            self.dirty = true;
+        }
+
+        // glyphs without a cell are invisible
+        // until the atlas has grown
+        let (uvs, bounds) = match cell {
+            Some(c) => (
+                self.uvs(c, width, height),
+                Vec2::new(width as f32, height as f32) / px,
+            ),
+            None => (Vec4::default(), Vec2::default()),
+        };
+
+        glyph_data.insert(
+            id,
+            CharData {
+                bearing: raster.bearing / px,
+                advance: raster.advance / px,
+                page: 0,
+                uvs,
+                bounds,
+            },
+        );
+        self.entries.insert(
+            id,
+            Entry {
+                last_used: self.frame,
+                tried: self.frame,
+                cell,
+                width,
+                height,
+            },
+        );
+    }
+
+    fn allocate(&mut self, glyph_data: &mut HashMap<u32, CharData>) -> Option<(u32, u32)> {
+        if let Some(cell) = self.free.pop() {
+            return Some(cell);
+        }
+
+        if self.size < self.max_size {
+            self.grow = true;
+            return None;
+        }
+
+        // evict least recently used glyph that wasn't used
+        // in this frame, keeping the missing glyph fallback
+        let frame = self.frame;
+        let evicted = self
+            .entries
+            .iter()
+            .filter(|(i, e)| **i != 0 && e.cell.is_some() && e.last_used < frame)
+            .min_by_key(|(_, e)| e.last_used)
+            .map(|(i, _)| *i)?;
+
+        glyph_data.remove(&evicted);
+        self.entries.remove(&evicted).and_then(|e| e.cell)
+    }
+
+    fn clear_cell(&mut self, x: u32, y: u32) {
+        for row in y..y + self.cell {
+            let start = (x + row * self.size) as usize;
+            for b in &mut self.data[start..start + self.cell as usize] {
+                *b = 0;
+            }
+        }
+    }
+
+    fn uvs(&self, (x, y): (u32, u32), width: u32, height: u32) -> Vec4 {
+        let size = self.size as f32;
+        let u = (x + CELL_MARGIN) as f32 / size;
+        let v = (y + CELL_MARGIN) as f32 / size;
+        Vec4::new(u, v, u + width as f32 / size, v + height as f32 / size)
+    }
+}
+
+// cells of a grid of `size` that are
+// outside of the grid of `old_size`
+fn free_cells(cell: u32, old_size: u32, size: u32) -> Vec<(u32, u32)> {
+    let old_count = old_size / cell;
+    let count = size / cell;
+
+    let mut cells = vec![];
+    for y in 0..count {
+        for x in 0..count {
+            if x >= old_count || y >= old_count {
+                cells.push((x * cell, y * cell));
+            }
+        }
+    }
+
+    // cells are popped from the end
+    cells.reverse();
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+
+    use super::FontMetrics;
+    use super::GlyphCache;
+    use super::GlyphSource;
+    use super::RasterGlyph;
+    use crate::math::Vec2;
+
+    #[derive(Default)]
+    struct Squares {
+        rasterized: Arc<AtomicUsize>,
+    }
+
+    impl GlyphSource for Squares {
+        fn metrics(&self) -> FontMetrics {
+            FontMetrics {
+                height: 1.0,
+                line_gap: 0.0,
+                ascender: 1.0,
+                descender: 0.0,
+                space_width: 0.5,
+            }
+        }
+
+        fn px_size(&self) -> u32 {
+            30
+        }
+
+        fn glyph_id(&self, c: char) -> u32 {
+            c as u32
+        }
+
+        fn kerning(&self, _: u32, _: u32) -> f32 {
+            0.0
+        }
+
+        fn rasterize(&self, _: u32) -> RasterGlyph {
+            self.rasterized.fetch_add(1, Ordering::Relaxed);
+            RasterGlyph {
+                width: 10,
+                height: 10,
+                data: vec![255; 100],
+                bearing: Vec2::default(),
+                advance: 10.0,
+            }
+        }
+    }
+
+    #[test]
+    fn grow_when_full() {
+        let mut cache = GlyphCache::new(Box::new(Squares::default()));
+        let mut glyphs = HashMap::new();
+        let size = cache.size();
+
+        // 16 cells fit in the starting atlas
+        for id in 0..17 {
+            cache.load(id, &mut glyphs);
+        }
+        assert!(cache.should_grow());
+        assert_eq!(glyphs[&16].bounds, Vec2::default());
+
+        cache.grow(&mut glyphs);
+        cache.next_frame();
+        cache.load(16, &mut glyphs);
+        assert_eq!(cache.size(), size * 2);
+        assert_eq!(glyphs[&16].bounds, Vec2::new(10.0, 10.0) / 30.0);
+    }
+
+    #[test]
+    fn evict_least_recently_used() {
+        let mut cache = GlyphCache::new(Box::new(Squares::default()));
+        let mut glyphs = HashMap::new();
+        cache.stop_growing();
+
+        for id in 0..16 {
+            cache.load(id, &mut glyphs);
+        }
+        cache.next_frame();
+        for id in (0..17).filter(|i| *i != 1) {
+            cache.load(id, &mut glyphs);
+        }
+
+        assert!(!glyphs.contains_key(&1));
+        assert!(glyphs.contains_key(&16));
+    }
+
+    #[test]
+    fn retry_pending_once_per_frame() {
+        let squares = Squares::default();
+        let rasterized = Arc::clone(&squares.rasterized);
+        let mut cache = GlyphCache::new(Box::new(squares));
+        let mut glyphs = HashMap::new();
+        cache.stop_growing();
+
+        // atlas is full with glyphs used this frame
+        for id in 0..17 {
+            cache.load(id, &mut glyphs);
+        }
+        cache.load(16, &mut glyphs);
+        assert_eq!(rasterized.load(Ordering::Relaxed), 17);
+        assert_eq!(glyphs[&16].bounds, Vec2::default());
+
+        cache.next_frame();
+        cache.load(16, &mut glyphs);
+        assert_eq!(rasterized.load(Ordering::Relaxed), 18);
+        assert_eq!(glyphs[&16].bounds, Vec2::new(10.0, 10.0) / 30.0);
+    }
+}
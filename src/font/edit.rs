@@ -0,0 +1,301 @@
+// Oliver Berzs
+// https://github.com/oberzs/duku
+
+// TextEdit - editable text state
+
+use std::ops::Range;
+
+use super::TextLayout;
+#[cfg(feature = "window")]
+use crate::window::Events;
+#[cfg(feature = "window")]
+use crate::window::Key;
+
+/// Editable text with a cursor and selection.
+///
+/// Positions are byte indices in the text.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use duku::Duku;
+/// # use duku::TextEdit;
+/// # use duku::TextStyle;
+/// # use std::cell::RefCell;
+/// # let (mut d, w) = Duku::windowed(1, 1).unwrap();
+/// let edit = RefCell::new(TextEdit::new("Hello"));
+///
+/// w.while_open(move |e| {
+///     d.draw(None, |t| {
+///         let mut edit = edit.borrow_mut();
+///         let layout = t.text_layout(edit.text(), None, TextStyle::default());
+///         edit.update(e, &layout);
+///
+///         let layout = t.text_layout(edit.text(), None, TextStyle::default());
+///         let caret = layout.caret(edit.cursor());
+///         t.text(edit.text(), [0.0, 0.0]);
+///     });
+/// });
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    text: String,
+    cursor: usize,
+    anchor: usize,
+}
+
+impl TextEdit {
+    /// Create edit with cursor at the end of `text`
+    pub fn new(text: impl Into<String>) -> Self {
+        let text = text.into();
+        let cursor = text.len();
+        Self {
+            anchor: cursor,
+            cursor,
+            text,
+        }
+    }
+
+    /// Get edited text
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Replace text and move cursor to the end
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+        self.cursor = self.text.len();
+        self.anchor = self.cursor;
+    }
+
+    /// Get cursor position
+    pub const fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Get selected range
+    ///
+    /// Range is empty if nothing is selected.
+    pub fn selection(&self) -> Range<usize> {
+        self.anchor.min(self.cursor)..self.anchor.max(self.cursor)
+    }
+
+    /// Get selected text
+    pub fn selected_text(&self) -> &str {
+        &self.text[self.selection()]
+    }
+
+    /// Move cursor to `index`
+    ///
+    /// If `select` is true, the selection
+    /// is extended to the new position.
+    pub fn set_cursor(&mut self, index: usize, select: bool) {
+        let mut index = index.min(self.text.len());
+        while !self.text.is_char_boundary(index) {
+            index -= 1;
+        }
+
+        self.cursor = index;
+        if !select {
+            self.anchor = index;
+        }
+    }
+
+    /// Select all text
+    pub fn select_all(&mut self) {
+        self.anchor = 0;
+        self.cursor = self.text.len();
+    }
+
+    /// Insert text at cursor replacing the selection
+    pub fn insert(&mut self, text: &str) {
+        let range = self.selection();
+        self.text.replace_range(range.clone(), text);
+        self.set_cursor(range.start + text.len(), false);
+    }
+
+    /// Remove selection or character before cursor
+    pub fn backspace(&mut self) {
+        if self.anchor == self.cursor {
+            let index = self.prev_index();
+            self.set_cursor(index, true);
+        }
+        self.insert("");
+    }
+
+    /// Remove selection or character after cursor
+    pub fn delete(&mut self) {
+        if self.anchor == self.cursor {
+            let index = self.next_index();
+            self.set_cursor(index, true);
+        }
+        self.insert("");
+    }
+
+    /// Move cursor one character left
+    pub fn move_left(&mut self, select: bool) {
+        let index = if select || self.anchor == self.cursor {
+            self.prev_index()
+        } else {
+            self.selection().start
+        };
+        self.set_cursor(index, select);
+    }
+
+    /// Move cursor one character right
+    pub fn move_right(&mut self, select: bool) {
+        let index = if select || self.anchor == self.cursor {
+            self.next_index()
+        } else {
+            self.selection().end
+        };
+        self.set_cursor(index, select);
+    }
+
+    /// Move cursor to the line above in `layout`
+    pub fn move_up(&mut self, layout: &TextLayout, select: bool) {
+        let caret = layout.caret(self.cursor);
+        let index = layout.index_at([caret.pos.x, caret.pos.y + caret.size.y / 2.0]);
+        self.set_cursor(index, select);
+    }
+
+    /// Move cursor to the line below in `layout`
+    pub fn move_down(&mut self, layout: &TextLayout, select: bool) {
+        let caret = layout.caret(self.cursor);
+        let below = caret.pos.y - caret.size.y * 1.5;
+
+        // stay on the last line
+        if below < -layout.height() {
+            return;
+        }
+
+        let index = layout.index_at([caret.pos.x, below]);
+        self.set_cursor(index, select);
+    }
+
+    /// Move cursor to the start of the line in `layout`
+    pub fn move_home(&mut self, layout: &TextLayout, select: bool) {
+        let index = line_text(layout, self.cursor).map_or(0, |t| t.start);
+        self.set_cursor(index, select);
+    }
+
+    /// Move cursor to the end of the line in `layout`
+    pub fn move_end(&mut self, layout: &TextLayout, select: bool) {
+        let index = line_text(layout, self.cursor).map_or(self.text.len(), |t| t.end);
+        self.set_cursor(index, select);
+    }
+
+    /// Handle typed characters, backspace,
+    /// delete, arrow keys and selection
+    ///
+    /// `layout` should be the layout of
+    /// the current text.
+    #[cfg(feature = "window")]
+    pub fn update(&mut self, events: &Events, layout: &TextLayout) {
+        let select = events.is_key_pressed(Key::LShift) || events.is_key_pressed(Key::RShift);
+        let control = events.is_key_pressed(Key::LControl) || events.is_key_pressed(Key::RControl);
+
+        if let Some(c) = events.typed_char() {
+            let mut buffer = [0; 4];
+            self.insert(c.encode_utf8(&mut buffer));
+        }
+
+        if control && events.is_key_typed(Key::A) {
+            self.select_all();
+        }
+        if events.is_key_typed(Key::Back) {
+            self.backspace();
+        }
+        if events.is_key_typed(Key::Delete) {
+            self.delete();
+        }
+        if events.is_key_typed(Key::Left) {
+            self.move_left(select);
+        }
+        if events.is_key_typed(Key::Right) {
+            self.move_right(select);
+        }
+        if events.is_key_typed(Key::Up) {
+            self.move_up(layout, select);
+        }
+        if events.is_key_typed(Key::Down) {
+            self.move_down(layout, select);
+        }
+        if events.is_key_typed(Key::Home) {
+            self.move_home(layout, select);
+        }
+        if events.is_key_typed(Key::End) {
+            self.move_end(layout, select);
+        }
+    }
+
+    fn prev_index(&self) -> usize {
+        self.text[..self.cursor]
+            .chars()
+            .next_back()
+            .map_or(0, |c| self.cursor - c.len_utf8())
+    }
+
+    fn next_index(&self) -> usize {
+        self.text[self.cursor..]
+            .chars()
+            .next()
+            .map_or(self.cursor, |c| self.cursor + c.len_utf8())
+    }
+}
+
+impl Default for TextEdit {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+// text range of the line with the index
+fn line_text(layout: &TextLayout, index: usize) -> Option<Range<usize>> {
+    layout
+        .lines()
+        .iter()
+        .rev()
+        .find(|l| l.text.start <= index)
+        .map(|l| l.text.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TextEdit;
+
+    #[test]
+    fn insert_and_backspace() {
+        let mut edit = TextEdit::new("héllo");
+        edit.move_left(false);
+        edit.backspace();
+        edit.insert("L");
+        assert_eq!(edit.text(), "hélLo");
+        assert_eq!(edit.cursor(), 5);
+
+        // index inside a character is moved before it
+        edit.set_cursor(2, false);
+        edit.backspace();
+        assert_eq!(edit.text(), "élLo");
+        assert_eq!(edit.cursor(), 0);
+    }
+
+    #[test]
+    fn select_and_replace() {
+        let mut edit = TextEdit::new("hello world");
+        edit.set_cursor(6, false);
+        for _ in 0..5 {
+            edit.move_right(true);
+        }
+        assert_eq!(edit.selected_text(), "world");
+
+        edit.insert("there");
+        assert_eq!(edit.text(), "hello there");
+        assert_eq!(edit.selection(), 11..11);
+
+        edit.select_all();
+        edit.move_left(false);
+        assert_eq!(edit.cursor(), 0);
+        assert!(edit.selected_text().is_empty());
+    }
+}
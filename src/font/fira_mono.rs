@@ -104,949 +104,1141 @@ pub fn fira_mono() -> FontData<'static> {
         line_gap: 0.0,
         ascender: 0.77916664,
         descender: -0.22083332,
-        char_data: HashMap::new(),
-        texture_data: DATA,
+        char_glyphs: HashMap::new(),
+        glyph_data: HashMap::new(),
+        kerning: HashMap::new(),
+        ligatures: HashMap::new(),
+        texture_data: vec![DATA],
         texture_width: 970,
         texture_height: 23,
+        sdf_range: None,
     };
 
-    font_data.char_data.insert(
-        '!',
+    font_data.char_glyphs.insert('!', 1);
+    font_data.glyph_data.insert(
+        1,
         CharData {
             uvs: Vec4::new(0.0010309279, 0.04347826, 0.0051546395, 0.6956522),
             bounds: Vec2::new(0.16666667, 0.625),
             bearing: Vec2::new(0.20833333, 0.041666668),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '\"',
+    font_data.char_glyphs.insert('\"', 2);
+    font_data.glyph_data.insert(
+        2,
         CharData {
             uvs: Vec4::new(0.006185567, 0.04347826, 0.012371134, 0.3043478),
             bounds: Vec2::new(0.25, 0.25),
             bearing: Vec2::new(0.125, -0.375),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '#',
+    font_data.char_glyphs.insert('#', 3);
+    font_data.glyph_data.insert(
+        3,
         CharData {
             uvs: Vec4::new(0.013402062, 0.04347826, 0.023711339, 0.6521739),
             bounds: Vec2::new(0.41666666, 0.5833333),
             bearing: Vec2::new(0.041666668, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '$',
+    font_data.char_glyphs.insert('$', 4);
+    font_data.glyph_data.insert(
+        4,
         CharData {
             uvs: Vec4::new(0.024742268, 0.04347826, 0.035051547, 0.95652175),
             bounds: Vec2::new(0.41666666, 0.875),
             bearing: Vec2::new(0.041666668, 0.16666667),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '%',
+    font_data.char_glyphs.insert('%', 5);
+    font_data.glyph_data.insert(
+        5,
         CharData {
             uvs: Vec4::new(0.036082473, 0.04347826, 0.048453607, 0.73913044),
             bounds: Vec2::new(0.5, 0.6666667),
             bearing: Vec2::new(0.0, 0.041666668),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '&',
+    font_data.char_glyphs.insert('&', 6);
+    font_data.glyph_data.insert(
+        6,
         CharData {
             uvs: Vec4::new(0.049484536, 0.04347826, 0.060824744, 0.6956522),
             bounds: Vec2::new(0.45833334, 0.625),
             bearing: Vec2::new(0.041666668, 0.041666668),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '\'',
+    font_data.char_glyphs.insert('\'', 7);
+    font_data.glyph_data.insert(
+        7,
         CharData {
             uvs: Vec4::new(0.06185567, 0.04347826, 0.065979384, 0.3043478),
             bounds: Vec2::new(0.16666667, 0.25),
             bearing: Vec2::new(0.20833333, -0.375),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '(',
+    font_data.char_glyphs.insert('(', 8);
+    font_data.glyph_data.insert(
+        8,
         CharData {
             uvs: Vec4::new(0.067010306, 0.04347826, 0.074226804, 0.91304344),
             bounds: Vec2::new(0.29166666, 0.8333333),
             bearing: Vec2::new(0.125, 0.125),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        ')',
+    font_data.char_glyphs.insert(')', 9);
+    font_data.glyph_data.insert(
+        9,
         CharData {
             uvs: Vec4::new(0.07525773, 0.04347826, 0.08247423, 0.91304344),
             bounds: Vec2::new(0.29166666, 0.8333333),
             bearing: Vec2::new(0.125, 0.125),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '*',
+    font_data.char_glyphs.insert('*', 10);
+    font_data.glyph_data.insert(
+        10,
         CharData {
             uvs: Vec4::new(0.08350515, 0.04347826, 0.09381443, 0.4347826),
             bounds: Vec2::new(0.41666666, 0.375),
             bearing: Vec2::new(0.083333336, -0.083333336),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '+',
+    font_data.char_glyphs.insert('+', 11);
+    font_data.glyph_data.insert(
+        11,
         CharData {
             uvs: Vec4::new(0.09484536, 0.04347826, 0.10309278, 0.39130434),
             bounds: Vec2::new(0.33333334, 0.33333334),
             bearing: Vec2::new(0.083333336, -0.083333336),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        ',',
+    font_data.char_glyphs.insert(',', 12);
+    font_data.glyph_data.insert(
+        12,
         CharData {
             uvs: Vec4::new(0.10412371, 0.04347826, 0.10824742, 0.39130434),
             bounds: Vec2::new(0.16666667, 0.33333334),
             bearing: Vec2::new(0.16666667, 0.16666667),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '-',
+    font_data.char_glyphs.insert('-', 13);
+    font_data.glyph_data.insert(
+        13,
         CharData {
             uvs: Vec4::new(0.10927835, 0.04347826, 0.11752577, 0.13043478),
             bounds: Vec2::new(0.33333334, 0.083333336),
             bearing: Vec2::new(0.125, -0.20833333),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '.',
+    font_data.char_glyphs.insert('.', 14);
+    font_data.glyph_data.insert(
+        14,
         CharData {
             uvs: Vec4::new(0.1185567, 0.04347826, 0.12268041, 0.26086956),
             bounds: Vec2::new(0.16666667, 0.20833333),
             bearing: Vec2::new(0.16666667, 0.041666668),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '/',
+    font_data.char_glyphs.insert('/', 15);
+    font_data.glyph_data.insert(
+        15,
         CharData {
             uvs: Vec4::new(0.12371134, 0.04347826, 0.13402061, 0.91304344),
             bounds: Vec2::new(0.41666666, 0.8333333),
             bearing: Vec2::new(0.041666668, 0.125),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '0',
+    font_data.char_glyphs.insert('0', 16);
+    font_data.glyph_data.insert(
+        16,
         CharData {
             uvs: Vec4::new(0.13505155, 0.04347826, 0.14536083, 0.6956522),
             bounds: Vec2::new(0.41666666, 0.625),
             bearing: Vec2::new(0.083333336, 0.041666668),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '1',
+    font_data.char_glyphs.insert('1', 17);
+    font_data.glyph_data.insert(
+        17,
         CharData {
             uvs: Vec4::new(0.14639175, 0.04347826, 0.15670103, 0.6521739),
             bounds: Vec2::new(0.41666666, 0.5833333),
             bearing: Vec2::new(0.083333336, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '2',
+    font_data.char_glyphs.insert('2', 18);
+    font_data.glyph_data.insert(
+        18,
         CharData {
             uvs: Vec4::new(0.15773197, 0.04347826, 0.16701032, 0.6521739),
             bounds: Vec2::new(0.375, 0.5833333),
             bearing: Vec2::new(0.041666668, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '3',
+    font_data.char_glyphs.insert('3', 19);
+    font_data.glyph_data.insert(
+        19,
         CharData {
             uvs: Vec4::new(0.16804124, 0.04347826, 0.17835052, 0.6956522),
             bounds: Vec2::new(0.41666666, 0.625),
             bearing: Vec2::new(0.041666668, 0.041666668),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '4',
+    font_data.char_glyphs.insert('4', 20);
+    font_data.glyph_data.insert(
+        20,
         CharData {
             uvs: Vec4::new(0.17938145, 0.04347826, 0.18969072, 0.6521739),
             bounds: Vec2::new(0.41666666, 0.5833333),
             bearing: Vec2::new(0.041666668, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '5',
+    font_data.char_glyphs.insert('5', 21);
+    font_data.glyph_data.insert(
+        21,
         CharData {
             uvs: Vec4::new(0.19072165, 0.04347826, 0.20103092, 0.6956522),
             bounds: Vec2::new(0.41666666, 0.625),
             bearing: Vec2::new(0.083333336, 0.041666668),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '6',
+    font_data.char_glyphs.insert('6', 22);
+    font_data.glyph_data.insert(
+        22,
         CharData {
             uvs: Vec4::new(0.20206186, 0.04347826, 0.21237114, 0.6956522),
             bounds: Vec2::new(0.41666666, 0.625),
             bearing: Vec2::new(0.083333336, 0.041666668),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '7',
+    font_data.char_glyphs.insert('7', 23);
+    font_data.glyph_data.insert(
+        23,
         CharData {
             uvs: Vec4::new(0.21340206, 0.04347826, 0.22371134, 0.6956522),
             bounds: Vec2::new(0.41666666, 0.625),
             bearing: Vec2::new(0.083333336, 0.041666668),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '8',
+    font_data.char_glyphs.insert('8', 24);
+    font_data.glyph_data.insert(
+        24,
         CharData {
             uvs: Vec4::new(0.22474226, 0.04347826, 0.23505154, 0.6956522),
             bounds: Vec2::new(0.41666666, 0.625),
             bearing: Vec2::new(0.041666668, 0.041666668),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '9',
+    font_data.char_glyphs.insert('9', 25);
+    font_data.glyph_data.insert(
+        25,
         CharData {
             uvs: Vec4::new(0.23608248, 0.04347826, 0.24639176, 0.6956522),
             bounds: Vec2::new(0.41666666, 0.625),
             bearing: Vec2::new(0.041666668, 0.041666668),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        ':',
+    font_data.char_glyphs.insert(':', 26);
+    font_data.glyph_data.insert(
+        26,
         CharData {
             uvs: Vec4::new(0.24742268, 0.04347826, 0.25154638, 0.5652174),
             bounds: Vec2::new(0.16666667, 0.5),
             bearing: Vec2::new(0.16666667, 0.041666668),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        ';',
+    font_data.char_glyphs.insert(';', 27);
+    font_data.glyph_data.insert(
+        27,
         CharData {
             uvs: Vec4::new(0.2525773, 0.04347826, 0.25670102, 0.6956522),
             bounds: Vec2::new(0.16666667, 0.625),
             bearing: Vec2::new(0.16666667, 0.16666667),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '<',
+    font_data.char_glyphs.insert('<', 28);
+    font_data.glyph_data.insert(
+        28,
         CharData {
             uvs: Vec4::new(0.25773194, 0.04347826, 0.26804122, 0.5652174),
             bounds: Vec2::new(0.41666666, 0.5),
             bearing: Vec2::new(0.083333336, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '=',
+    font_data.char_glyphs.insert('=', 29);
+    font_data.glyph_data.insert(
+        29,
         CharData {
             uvs: Vec4::new(0.26907218, 0.04347826, 0.2773196, 0.3043478),
             bounds: Vec2::new(0.33333334, 0.25),
             bearing: Vec2::new(0.083333336, -0.125),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '>',
+    font_data.char_glyphs.insert('>', 30);
+    font_data.glyph_data.insert(
+        30,
         CharData {
             uvs: Vec4::new(0.2783505, 0.04347826, 0.28865978, 0.5652174),
             bounds: Vec2::new(0.41666666, 0.5),
             bearing: Vec2::new(0.083333336, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '?',
+    font_data.char_glyphs.insert('?', 31);
+    font_data.glyph_data.insert(
+        31,
         CharData {
             uvs: Vec4::new(0.28969073, 0.04347826, 0.2989691, 0.6956522),
             bounds: Vec2::new(0.375, 0.625),
             bearing: Vec2::new(0.083333336, 0.041666668),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '@',
+    font_data.char_glyphs.insert('@', 32);
+    font_data.glyph_data.insert(
+        32,
         CharData {
             uvs: Vec4::new(0.3, 0.04347826, 0.31237113, 0.73913044),
             bounds: Vec2::new(0.5, 0.6666667),
             bearing: Vec2::new(0.0, 0.083333336),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'A',
+    font_data.char_glyphs.insert('A', 33);
+    font_data.glyph_data.insert(
+        33,
         CharData {
             uvs: Vec4::new(0.31340206, 0.04347826, 0.32577318, 0.6521739),
             bounds: Vec2::new(0.5, 0.5833333),
             bearing: Vec2::new(0.0, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'B',
+    font_data.char_glyphs.insert('B', 34);
+    font_data.glyph_data.insert(
+        34,
         CharData {
             uvs: Vec4::new(0.32680413, 0.04347826, 0.3371134, 0.6521739),
             bounds: Vec2::new(0.41666666, 0.5833333),
             bearing: Vec2::new(0.083333336, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'C',
+    font_data.char_glyphs.insert('C', 35);
+    font_data.glyph_data.insert(
+        35,
         CharData {
             uvs: Vec4::new(0.33814433, 0.04347826, 0.34948453, 0.6956522),
             bounds: Vec2::new(0.45833334, 0.625),
             bearing: Vec2::new(0.041666668, 0.041666668),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'D',
+    font_data.char_glyphs.insert('D', 36);
+    font_data.glyph_data.insert(
+        36,
         CharData {
             uvs: Vec4::new(0.35051546, 0.04347826, 0.36185566, 0.6521739),
             bounds: Vec2::new(0.45833334, 0.5833333),
             bearing: Vec2::new(0.083333336, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'E',
+    font_data.char_glyphs.insert('E', 37);
+    font_data.glyph_data.insert(
+        37,
         CharData {
             uvs: Vec4::new(0.3628866, 0.04347826, 0.37216496, 0.6521739),
             bounds: Vec2::new(0.375, 0.5833333),
             bearing: Vec2::new(0.125, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'F',
+    font_data.char_glyphs.insert('F', 38);
+    font_data.glyph_data.insert(
+        38,
         CharData {
             uvs: Vec4::new(0.3731959, 0.04347826, 0.38247424, 0.6521739),
             bounds: Vec2::new(0.375, 0.5833333),
             bearing: Vec2::new(0.125, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'G',
+    font_data.char_glyphs.insert('G', 39);
+    font_data.glyph_data.insert(
+        39,
         CharData {
             uvs: Vec4::new(0.38350517, 0.04347826, 0.39484537, 0.6956522),
             bounds: Vec2::new(0.45833334, 0.625),
             bearing: Vec2::new(0.041666668, 0.041666668),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'H',
+    font_data.char_glyphs.insert('H', 40);
+    font_data.glyph_data.insert(
+        40,
         CharData {
             uvs: Vec4::new(0.3958763, 0.04347826, 0.40618557, 0.6521739),
             bounds: Vec2::new(0.41666666, 0.5833333),
             bearing: Vec2::new(0.083333336, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'I',
+    font_data.char_glyphs.insert('I', 41);
+    font_data.glyph_data.insert(
+        41,
         CharData {
             uvs: Vec4::new(0.4072165, 0.04347826, 0.41546392, 0.6521739),
             bounds: Vec2::new(0.33333334, 0.5833333),
             bearing: Vec2::new(0.083333336, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'J',
+    font_data.char_glyphs.insert('J', 42);
+    font_data.glyph_data.insert(
+        42,
         CharData {
             uvs: Vec4::new(0.41649485, 0.04347826, 0.4257732, 0.6956522),
             bounds: Vec2::new(0.375, 0.625),
             bearing: Vec2::new(0.041666668, 0.041666668),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'K',
+    font_data.char_glyphs.insert('K', 43);
+    font_data.glyph_data.insert(
+        43,
         CharData {
             uvs: Vec4::new(0.42680413, 0.04347826, 0.4371134, 0.6521739),
             bounds: Vec2::new(0.41666666, 0.5833333),
             bearing: Vec2::new(0.083333336, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'L',
+    font_data.char_glyphs.insert('L', 44);
+    font_data.glyph_data.insert(
+        44,
         CharData {
             uvs: Vec4::new(0.43814433, 0.04347826, 0.44742268, 0.6521739),
             bounds: Vec2::new(0.375, 0.5833333),
             bearing: Vec2::new(0.125, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'M',
+    font_data.char_glyphs.insert('M', 45);
+    font_data.glyph_data.insert(
+        45,
         CharData {
             uvs: Vec4::new(0.4484536, 0.04347826, 0.46082473, 0.6521739),
             bounds: Vec2::new(0.5, 0.5833333),
             bearing: Vec2::new(0.041666668, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'N',
+    font_data.char_glyphs.insert('N', 46);
+    font_data.glyph_data.insert(
+        46,
         CharData {
             uvs: Vec4::new(0.46185568, 0.04347826, 0.47216496, 0.6521739),
             bounds: Vec2::new(0.41666666, 0.5833333),
             bearing: Vec2::new(0.083333336, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'O',
+    font_data.char_glyphs.insert('O', 47);
+    font_data.glyph_data.insert(
+        47,
         CharData {
             uvs: Vec4::new(0.47319588, 0.04347826, 0.485567, 0.6956522),
             bounds: Vec2::new(0.5, 0.625),
             bearing: Vec2::new(0.041666668, 0.041666668),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'P',
+    font_data.char_glyphs.insert('P', 48);
+    font_data.glyph_data.insert(
+        48,
         CharData {
             uvs: Vec4::new(0.48659793, 0.04347826, 0.4969072, 0.6521739),
             bounds: Vec2::new(0.41666666, 0.5833333),
             bearing: Vec2::new(0.083333336, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'Q',
+    font_data.char_glyphs.insert('Q', 49);
+    font_data.glyph_data.insert(
+        49,
         CharData {
             uvs: Vec4::new(0.49793816, 0.04347826, 0.5103093, 0.82608694),
             bounds: Vec2::new(0.5, 0.75),
             bearing: Vec2::new(0.041666668, 0.16666667),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'R',
+    font_data.char_glyphs.insert('R', 50);
+    font_data.glyph_data.insert(
+        50,
         CharData {
             uvs: Vec4::new(0.5113402, 0.04347826, 0.5216495, 0.6521739),
             bounds: Vec2::new(0.41666666, 0.5833333),
             bearing: Vec2::new(0.083333336, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'S',
+    font_data.char_glyphs.insert('S', 51);
+    font_data.glyph_data.insert(
+        51,
         CharData {
             uvs: Vec4::new(0.5226804, 0.04347826, 0.5340206, 0.6956522),
             bounds: Vec2::new(0.45833334, 0.625),
             bearing: Vec2::new(0.041666668, 0.041666668),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'T',
+    font_data.char_glyphs.insert('T', 52);
+    font_data.glyph_data.insert(
+        52,
         CharData {
             uvs: Vec4::new(0.5350515, 0.04347826, 0.54742265, 0.6521739),
             bounds: Vec2::new(0.5, 0.5833333),
             bearing: Vec2::new(0.041666668, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'U',
+    font_data.char_glyphs.insert('U', 53);
+    font_data.glyph_data.insert(
+        53,
         CharData {
             uvs: Vec4::new(0.5484536, 0.04347826, 0.5587629, 0.6956522),
             bounds: Vec2::new(0.41666666, 0.625),
             bearing: Vec2::new(0.041666668, 0.041666668),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'V',
+    font_data.char_glyphs.insert('V', 54);
+    font_data.glyph_data.insert(
+        54,
         CharData {
             uvs: Vec4::new(0.55979383, 0.04347826, 0.57216495, 0.6521739),
             bounds: Vec2::new(0.5, 0.5833333),
             bearing: Vec2::new(0.041666668, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'W',
+    font_data.char_glyphs.insert('W', 55);
+    font_data.glyph_data.insert(
+        55,
         CharData {
             uvs: Vec4::new(0.5731959, 0.04347826, 0.585567, 0.6521739),
             bounds: Vec2::new(0.5, 0.5833333),
             bearing: Vec2::new(0.0, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'X',
+    font_data.char_glyphs.insert('X', 56);
+    font_data.glyph_data.insert(
+        56,
         CharData {
             uvs: Vec4::new(0.5865979, 0.04347826, 0.59896904, 0.6521739),
             bounds: Vec2::new(0.5, 0.5833333),
             bearing: Vec2::new(0.041666668, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'Y',
+    font_data.char_glyphs.insert('Y', 57);
+    font_data.glyph_data.insert(
+        57,
         CharData {
             uvs: Vec4::new(0.6, 0.04347826, 0.61237115, 0.6521739),
             bounds: Vec2::new(0.5, 0.5833333),
             bearing: Vec2::new(0.0, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'Z',
+    font_data.char_glyphs.insert('Z', 58);
+    font_data.glyph_data.insert(
+        58,
         CharData {
             uvs: Vec4::new(0.61340207, 0.04347826, 0.62371135, 0.6521739),
             bounds: Vec2::new(0.41666666, 0.5833333),
             bearing: Vec2::new(0.041666668, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '[',
+    font_data.char_glyphs.insert('[', 59);
+    font_data.glyph_data.insert(
+        59,
         CharData {
             uvs: Vec4::new(0.62474227, 0.04347826, 0.63092786, 0.91304344),
             bounds: Vec2::new(0.25, 0.8333333),
             bearing: Vec2::new(0.16666667, 0.125),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '\\',
+    font_data.char_glyphs.insert('\\', 60);
+    font_data.glyph_data.insert(
+        60,
         CharData {
             uvs: Vec4::new(0.6319588, 0.04347826, 0.64226806, 0.91304344),
             bounds: Vec2::new(0.41666666, 0.8333333),
             bearing: Vec2::new(0.041666668, 0.125),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        ']',
+    font_data.char_glyphs.insert(']', 61);
+    font_data.glyph_data.insert(
+        61,
         CharData {
             uvs: Vec4::new(0.643299, 0.04347826, 0.6494846, 0.91304344),
             bounds: Vec2::new(0.25, 0.8333333),
             bearing: Vec2::new(0.125, 0.125),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '^',
+    font_data.char_glyphs.insert('^', 62);
+    font_data.glyph_data.insert(
+        62,
         CharData {
             uvs: Vec4::new(0.65051544, 0.04347826, 0.6608247, 0.34782606),
             bounds: Vec2::new(0.41666666, 0.29166666),
             bearing: Vec2::new(0.041666668, -0.41666666),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '_',
+    font_data.char_glyphs.insert('_', 63);
+    font_data.glyph_data.insert(
+        63,
         CharData {
             uvs: Vec4::new(0.6618557, 0.04347826, 0.672165, 0.13043478),
             bounds: Vec2::new(0.41666666, 0.083333336),
             bearing: Vec2::new(0.041666668, 0.16666667),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '`',
+    font_data.char_glyphs.insert('`', 64);
+    font_data.glyph_data.insert(
+        64,
         CharData {
             uvs: Vec4::new(0.6731959, 0.04347826, 0.6793815, 0.21739131),
             bounds: Vec2::new(0.25, 0.16666667),
             bearing: Vec2::new(0.16666667, -0.5),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'a',
+    font_data.char_glyphs.insert('a', 65);
+    font_data.glyph_data.insert(
+        65,
         CharData {
             uvs: Vec4::new(0.68041235, 0.04347826, 0.69072163, 0.5652174),
             bounds: Vec2::new(0.41666666, 0.5),
             bearing: Vec2::new(0.041666668, 0.041666668),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'b',
+    font_data.char_glyphs.insert('b', 66);
+    font_data.glyph_data.insert(
+        66,
         CharData {
             uvs: Vec4::new(0.69175255, 0.04347826, 0.7010309, 0.73913044),
             bounds: Vec2::new(0.375, 0.6666667),
             bearing: Vec2::new(0.083333336, 0.041666668),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'c',
+    font_data.char_glyphs.insert('c', 67);
+    font_data.glyph_data.insert(
+        67,
         CharData {
             uvs: Vec4::new(0.70206183, 0.04347826, 0.7123711, 0.5652174),
             bounds: Vec2::new(0.41666666, 0.5),
             bearing: Vec2::new(0.083333336, 0.041666668),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'd',
+    font_data.char_glyphs.insert('d', 68);
+    font_data.glyph_data.insert(
+        68,
         CharData {
             uvs: Vec4::new(0.71340203, 0.04347826, 0.7226804, 0.73913044),
             bounds: Vec2::new(0.375, 0.6666667),
             bearing: Vec2::new(0.041666668, 0.041666668),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'e',
+    font_data.char_glyphs.insert('e', 69);
+    font_data.glyph_data.insert(
+        69,
         CharData {
             uvs: Vec4::new(0.7237113, 0.04347826, 0.7340206, 0.5652174),
             bounds: Vec2::new(0.41666666, 0.5),
             bearing: Vec2::new(0.083333336, 0.041666668),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'f',
+    font_data.char_glyphs.insert('f', 70);
+    font_data.glyph_data.insert(
+        70,
         CharData {
             uvs: Vec4::new(0.7350516, 0.04347826, 0.7463918, 0.6956522),
             bounds: Vec2::new(0.45833334, 0.625),
             bearing: Vec2::new(0.083333336, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'g',
+    font_data.char_glyphs.insert('g', 71);
+    font_data.glyph_data.insert(
+        71,
         CharData {
             uvs: Vec4::new(0.7474227, 0.04347826, 0.7587629, 0.7826087),
             bounds: Vec2::new(0.45833334, 0.7083333),
             bearing: Vec2::new(0.041666668, 0.20833333),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'h',
+    font_data.char_glyphs.insert('h', 72);
+    font_data.glyph_data.insert(
+        72,
         CharData {
             uvs: Vec4::new(0.7597938, 0.04347826, 0.7690722, 0.6956522),
             bounds: Vec2::new(0.375, 0.625),
             bearing: Vec2::new(0.083333336, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'i',
+    font_data.char_glyphs.insert('i', 73);
+    font_data.glyph_data.insert(
+        73,
         CharData {
             uvs: Vec4::new(0.7701031, 0.04347826, 0.77938145, 0.73913044),
             bounds: Vec2::new(0.375, 0.6666667),
             bearing: Vec2::new(0.083333336, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'j',
+    font_data.char_glyphs.insert('j', 74);
+    font_data.glyph_data.insert(
+        74,
         CharData {
             uvs: Vec4::new(0.7804124, 0.04347826, 0.7886598, 0.95652175),
             bounds: Vec2::new(0.33333334, 0.875),
             bearing: Vec2::new(0.083333336, 0.20833333),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'k',
+    font_data.char_glyphs.insert('k', 75);
+    font_data.glyph_data.insert(
+        75,
         CharData {
             uvs: Vec4::new(0.78969073, 0.04347826, 0.8, 0.6956522),
             bounds: Vec2::new(0.41666666, 0.625),
             bearing: Vec2::new(0.083333336, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'l',
+    font_data.char_glyphs.insert('l', 76);
+    font_data.glyph_data.insert(
+        76,
         CharData {
             uvs: Vec4::new(0.80103093, 0.04347826, 0.8113402, 0.73913044),
             bounds: Vec2::new(0.41666666, 0.6666667),
             bearing: Vec2::new(0.041666668, 0.041666668),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'm',
+    font_data.char_glyphs.insert('m', 77);
+    font_data.glyph_data.insert(
+        77,
         CharData {
             uvs: Vec4::new(0.81237113, 0.04347826, 0.8226804, 0.5217391),
             bounds: Vec2::new(0.41666666, 0.45833334),
             bearing: Vec2::new(0.041666668, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'n',
+    font_data.char_glyphs.insert('n', 78);
+    font_data.glyph_data.insert(
+        78,
         CharData {
             uvs: Vec4::new(0.82371134, 0.04347826, 0.8329897, 0.5217391),
             bounds: Vec2::new(0.375, 0.45833334),
             bearing: Vec2::new(0.083333336, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'o',
+    font_data.char_glyphs.insert('o', 79);
+    font_data.glyph_data.insert(
+        79,
         CharData {
             uvs: Vec4::new(0.8340206, 0.04347826, 0.8443299, 0.5652174),
             bounds: Vec2::new(0.41666666, 0.5),
             bearing: Vec2::new(0.041666668, 0.041666668),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'p',
+    font_data.char_glyphs.insert('p', 80);
+    font_data.glyph_data.insert(
+        80,
         CharData {
             uvs: Vec4::new(0.8453608, 0.04347826, 0.8546392, 0.73913044),
             bounds: Vec2::new(0.375, 0.6666667),
             bearing: Vec2::new(0.083333336, 0.20833333),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'q',
+    font_data.char_glyphs.insert('q', 81);
+    font_data.glyph_data.insert(
+        81,
         CharData {
             uvs: Vec4::new(0.8556701, 0.04347826, 0.86494845, 0.73913044),
             bounds: Vec2::new(0.375, 0.6666667),
             bearing: Vec2::new(0.041666668, 0.20833333),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'r',
+    font_data.char_glyphs.insert('r', 82);
+    font_data.glyph_data.insert(
+        82,
         CharData {
             uvs: Vec4::new(0.8659794, 0.04347826, 0.87525773, 0.5217391),
             bounds: Vec2::new(0.375, 0.45833334),
             bearing: Vec2::new(0.083333336, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        's',
+    font_data.char_glyphs.insert('s', 83);
+    font_data.glyph_data.insert(
+        83,
         CharData {
             uvs: Vec4::new(0.87628865, 0.04347826, 0.88659793, 0.5652174),
             bounds: Vec2::new(0.41666666, 0.5),
             bearing: Vec2::new(0.041666668, 0.041666668),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        't',
+    font_data.char_glyphs.insert('t', 84);
+    font_data.glyph_data.insert(
+        84,
         CharData {
             uvs: Vec4::new(0.88762885, 0.04347826, 0.89793813, 0.6956522),
             bounds: Vec2::new(0.41666666, 0.625),
             bearing: Vec2::new(0.083333336, 0.041666668),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'u',
+    font_data.char_glyphs.insert('u', 85);
+    font_data.glyph_data.insert(
+        85,
         CharData {
             uvs: Vec4::new(0.89896905, 0.04347826, 0.9072165, 0.5652174),
             bounds: Vec2::new(0.33333334, 0.5),
             bearing: Vec2::new(0.083333336, 0.041666668),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'v',
+    font_data.char_glyphs.insert('v', 86);
+    font_data.glyph_data.insert(
+        86,
         CharData {
             uvs: Vec4::new(0.9082474, 0.04347826, 0.9185567, 0.5217391),
             bounds: Vec2::new(0.41666666, 0.45833334),
             bearing: Vec2::new(0.041666668, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'w',
+    font_data.char_glyphs.insert('w', 87);
+    font_data.glyph_data.insert(
+        87,
         CharData {
             uvs: Vec4::new(0.9195876, 0.04347826, 0.93195873, 0.5217391),
             bounds: Vec2::new(0.5, 0.45833334),
             bearing: Vec2::new(0.0, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'x',
+    font_data.char_glyphs.insert('x', 88);
+    font_data.glyph_data.insert(
+        88,
         CharData {
             uvs: Vec4::new(0.9329897, 0.04347826, 0.943299, 0.5217391),
             bounds: Vec2::new(0.41666666, 0.45833334),
             bearing: Vec2::new(0.041666668, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'y',
+    font_data.char_glyphs.insert('y', 89);
+    font_data.glyph_data.insert(
+        89,
         CharData {
             uvs: Vec4::new(0.9443299, 0.04347826, 0.9546392, 0.73913044),
             bounds: Vec2::new(0.41666666, 0.6666667),
             bearing: Vec2::new(0.041666668, 0.20833333),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        'z',
+    font_data.char_glyphs.insert('z', 90);
+    font_data.glyph_data.insert(
+        90,
         CharData {
             uvs: Vec4::new(0.9556701, 0.04347826, 0.96391755, 0.5217391),
             bounds: Vec2::new(0.33333334, 0.45833334),
             bearing: Vec2::new(0.083333336, 0.0),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '{',
+    font_data.char_glyphs.insert('{', 91);
+    font_data.glyph_data.insert(
+        91,
         CharData {
             uvs: Vec4::new(0.9649485, 0.04347826, 0.9731959, 0.91304344),
             bounds: Vec2::new(0.33333334, 0.8333333),
             bearing: Vec2::new(0.083333336, 0.125),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '|',
+    font_data.char_glyphs.insert('|', 92);
+    font_data.glyph_data.insert(
+        92,
         CharData {
             uvs: Vec4::new(0.97422683, 0.04347826, 0.9762887, 0.91304344),
             bounds: Vec2::new(0.083333336, 0.8333333),
             bearing: Vec2::new(0.20833333, 0.125),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '}',
+    font_data.char_glyphs.insert('}', 93);
+    font_data.glyph_data.insert(
+        93,
         CharData {
             uvs: Vec4::new(0.9773196, 0.04347826, 0.98556703, 0.91304344),
             bounds: Vec2::new(0.33333334, 0.8333333),
             bearing: Vec2::new(0.125, 0.125),
             advance: 0.5,
+            page: 0,
         },
     );
 
-    font_data.char_data.insert(
-        '~',
+    font_data.char_glyphs.insert('~', 94);
+    font_data.glyph_data.insert(
+        94,
         CharData {
             uvs: Vec4::new(0.98659796, 0.04347826, 0.9989691, 0.26086956),
             bounds: Vec2::new(0.5, 0.20833333),
             bearing: Vec2::new(0.041666668, -0.16666667),
             advance: 0.5,
+            page: 0,
         },
     );
 
@@ -0,0 +1,744 @@
+// Oliver Berzs
+// https://github.com/oberzs/duku
+
+// TextLayout - line breaking and glyph positioning
+
+use std::mem;
+use std::ops::Range;
+
+use super::Font;
+use super::FontMetrics;
+use super::ShapedGlyph;
+use crate::math::Vec2;
+
+/// Positioned lines and glyphs of a text.
+///
+/// Positions are relative to the top-left
+/// corner of the text with y going up.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use duku::Duku;
+/// # use duku::TextStyle;
+/// # use duku::TextWrap;
+/// # let (mut d, _) = Duku::windowed(1, 1).unwrap();
+/// # d.draw(None, |t| {
+/// let style = TextStyle {
+///     wrap: TextWrap::Word,
+///     ..Default::default()
+/// };
+/// let layout = t.text_layout("Hello, World!", Some(50.0), style);
+/// let lines = layout.lines().len();
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct TextLayout {
+    lines: Vec<TextLine>,
+    glyphs: Vec<LayoutGlyph>,
+    width: f32,
+    height: f32,
+    line_height: f32,
+}
+
+/// Single line in a text layout.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLine {
+    /// range of the line's glyphs in the layout
+    pub glyphs: Range<usize>,
+    /// byte range of the line's text
+    pub text: Range<usize>,
+    /// left edge of the line
+    pub x: f32,
+    /// width of the line without trailing spaces
+    pub width: f32,
+    /// top edge of the line
+    pub top: f32,
+    /// height of the line with line spacing
+    pub height: f32,
+    /// baseline height from the top of the text
+    pub baseline: f32,
+}
+
+/// Rectangle in a text layout.
+///
+/// Used for carets and selections.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TextRect {
+    /// top-left corner
+    pub pos: Vec2,
+    /// width and height
+    pub size: Vec2,
+}
+
+/// Single positioned character in a text layout.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LayoutGlyph {
+    /// the character of the glyph
+    ///
+    /// For ligatures this is the first character
+    pub c: char,
+    /// glyph id in the font
+    pub glyph: u32,
+    /// byte index of the character in the text
+    ///
+    /// For rich text this is the index in
+    /// the text of all spans joined together
+    pub index: usize,
+    /// pen position on the baseline
+    pub pos: Vec2,
+    /// horizontal advance of the glyph
+    pub advance: f32,
+    /// index of the glyph's line
+    pub line: usize,
+    /// index of the glyph's font in the fallback list
+    pub font: usize,
+    /// index of the glyph's span in rich text
+    pub span: usize,
+}
+
+// text laid out with the same fonts and size
+pub(crate) struct LayoutPart<'a> {
+    pub(crate) text: &'a str,
+    pub(crate) fonts: &'a [&'a Font],
+    pub(crate) font_size: u32,
+}
+
+/// Text settings for layout.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TextStyle {
+    /// horizontal alignment of the lines
+    pub align: TextAlign,
+    /// line spacing multiplier
+    pub line_height: f32,
+    /// how lines are broken to fit
+    pub wrap: TextWrap,
+    /// what happens to lines that still don't fit
+    pub overflow: TextOverflow,
+}
+
+/// Horizontal text alignment.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TextAlign {
+    /// align lines to the left edge
+    Left,
+    /// center lines
+    Center,
+    /// align lines to the right edge
+    Right,
+}
+
+/// Line breaking mode.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TextWrap {
+    /// break lines between words
+    Word,
+    /// break lines between any characters
+    Char,
+    /// break lines only on new lines
+    None,
+}
+
+/// Handling of lines wider than the max width.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TextOverflow {
+    /// remove characters that don't fit
+    Clip,
+    /// replace characters that don't fit with `...`
+    Ellipsis,
+}
+
+impl TextLayout {
+    /// Lay out text with font
+    ///
+    /// If `max_width` is `None`, lines are
+    /// only broken on new lines.
+    pub fn new(
+        font: &Font,
+        font_size: u32,
+        text: &str,
+        max_width: Option<f32>,
+        style: TextStyle,
+    ) -> Self {
+        Self::with_fallbacks(&[font], font_size, text, max_width, style)
+    }
+
+    /// Lay out text with fallback fonts
+    ///
+    /// Each character uses the first font
+    /// that contains it. Lines fit the
+    /// tallest of the used fonts.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `fonts` is empty.
+    pub fn with_fallbacks(
+        fonts: &[&Font],
+        font_size: u32,
+        text: &str,
+        max_width: Option<f32>,
+        style: TextStyle,
+    ) -> Self {
+        let part = LayoutPart {
+            text,
+            fonts,
+            font_size,
+        };
+        Self::from_parts(&[part], max_width, style)
+    }
+
+    // lays out parts with different fonts and sizes
+    // one after another on shared baselines
+    pub(crate) fn from_parts(
+        parts: &[LayoutPart<'_>],
+        max_width: Option<f32>,
+        style: TextStyle,
+    ) -> Self {
+        // break text into lines of glyphs
+        // remembering the part each line starts in
+        let mut lines: Vec<PendingLine> = vec![];
+        let mut line = PendingLine::new(0, 0);
+        let mut x = 0.0;
+        let mut offset = 0;
+        let mut end = 0;
+
+        for (p, part) in parts.iter().enumerate() {
+            for (n, paragraph) in part.text.split('\n').enumerate() {
+                if n > 0 {
+                    lines.push(line.until(end));
+                    line = PendingLine::new(p, offset);
+                    x = 0.0;
+                }
+                if line.glyphs.is_empty() {
+                    line.part = p;
+                }
+
+                let chars = paragraph.strip_suffix('\r').unwrap_or(paragraph);
+                for (f, shaped) in shape_runs(part.fonts, chars) {
+                    let a = shaped.advance * part.font_size as f32;
+                    let index = offset + shaped.index;
+
+                    if let Some(max) = max_width {
+                        let breaking = shaped.c != ' ' && !line.glyphs.is_empty() && x + a > max;
+                        match style.wrap {
+                            TextWrap::Char if breaking => {
+                                lines.push(line.until(index));
+                                line = PendingLine::new(p, index);
+                                x = 0.0;
+                            }
+                            TextWrap::Word if breaking => {
+                                // move the current word to the next line
+                                if let Some(space) = line.glyphs.iter().rposition(|g| g.c == ' ') {
+                                    let mut rest = line.glyphs.split_off(space + 1);
+                                    let start = rest.first().map_or(x, |g| g.pos.x);
+                                    for g in &mut rest {
+                                        g.pos.x -= start;
+                                    }
+                                    let (rest_part, rest_index) =
+                                        rest.first().map_or((p, index), |g| (g.span, g.index));
+                                    let mut next = PendingLine::new(rest_part, rest_index);
+                                    next.glyphs = rest;
+                                    lines.push(mem::replace(&mut line, next).until(rest_index));
+                                    x -= start;
+                                } else {
+                                    // word is wider than the line
+                                    lines.push(line.until(index));
+                                    line = PendingLine::new(p, index);
+                                    x = 0.0;
+                                }
+                            }
+                            _ => (),
+                        }
+                    }
+
+                    line.glyphs.push(LayoutGlyph {
+                        c: shaped.c,
+                        glyph: shaped.id,
+                        index,
+                        pos: Vec2::new(x, 0.0),
+                        advance: a,
+                        line: 0,
+                        font: f,
+                        span: p,
+                    });
+                    x += a;
+                }
+
+                end = offset + chars.len();
+                offset += paragraph.len() + 1;
+            }
+
+            // there is no new line after the last paragraph
+            offset -= 1;
+        }
+
+        // like str::lines, there is no
+        // line after a final new line
+        let ends_with_newline = parts
+            .iter()
+            .rev()
+            .find(|p| !p.text.is_empty())
+            .map(|p| p.text.ends_with('\n'));
+        if ends_with_newline == Some(false) {
+            lines.push(line.until(end));
+        }
+
+        // handle lines that don't fit
+        if let Some(max) = max_width {
+            for line in &mut lines {
+                let part = &parts[line.part];
+                let font = part.fonts[0];
+                let dot = Dot {
+                    glyph: font.glyph_id('.'),
+                    advance: font
+                        .char_data('.')
+                        .map_or(0.0, |d| d.scaled(part.font_size).advance),
+                    span: line.part,
+                };
+                fit_line(&mut line.glyphs, max, style.overflow, dot);
+            }
+        }
+
+        // measure lines
+        let widths: Vec<_> = lines.iter().map(|l| line_width(&l.glyphs)).collect();
+        let width = widths.iter().fold(0.0f32, |a, b| a.max(*b));
+        let box_width = max_width.unwrap_or(width);
+
+        // position lines
+        let mut result_lines = vec![];
+        let mut glyphs = vec![];
+        let mut top = 0.0;
+        for (i, (line, line_width)) in lines.into_iter().zip(widths).enumerate() {
+            let x = match style.align {
+                TextAlign::Left => 0.0,
+                TextAlign::Center => (box_width - line_width) / 2.0,
+                TextAlign::Right => box_width - line_width,
+            };
+
+            // lines fit all used fonts
+            let p = line.part;
+            let metrics = line
+                .glyphs
+                .iter()
+                .map(|g| {
+                    let part = &parts[g.span];
+                    part.fonts[g.font].metrics().scaled(part.font_size)
+                })
+                .fold(
+                    parts[p].fonts[0].metrics().scaled(parts[p].font_size),
+                    |a, b| FontMetrics {
+                        height: a.height.max(b.height),
+                        line_gap: a.line_gap.max(b.line_gap),
+                        descender: a.descender.min(b.descender),
+                        ..a
+                    },
+                );
+            let line_top = top;
+            let height = (metrics.height + metrics.line_gap) * style.line_height;
+            top -= height;
+            let baseline = top - metrics.descender;
+
+            let start = glyphs.len();
+            glyphs.extend(line.glyphs.into_iter().map(|mut g| {
+                g.pos += Vec2::new(x, baseline);
+                g.line = i;
+                g
+            }));
+            result_lines.push(TextLine {
+                glyphs: start..glyphs.len(),
+                text: line.text,
+                width: line_width,
+                top: line_top,
+                height,
+                baseline,
+                x,
+            });
+        }
+
+        // height for carets outside of lines
+        let line_height = parts.last().map_or(0.0, |p| {
+            let metrics = p.fonts[0].metrics().scaled(p.font_size);
+            (metrics.height + metrics.line_gap) * style.line_height
+        });
+
+        Self {
+            height: -top,
+            lines: result_lines,
+            line_height,
+            width,
+            glyphs,
+        }
+    }
+
+    /// Get byte index of the caret position
+    /// closest to `pos`
+    ///
+    /// Position is relative to the top-left
+    /// corner of the text with y going up.
+    pub fn index_at(&self, pos: impl Into<Vec2>) -> usize {
+        let pos = pos.into();
+
+        // closest line from the top
+        let line = match self
+            .lines
+            .iter()
+            .find(|l| pos.y >= l.top - l.height)
+            .or_else(|| self.lines.last())
+        {
+            Some(l) => l,
+            None => return 0,
+        };
+
+        self.glyphs[line.glyphs.clone()]
+            .iter()
+            .find(|g| pos.x < g.pos.x + g.advance / 2.0)
+            .map_or(line.text.end, |g| g.index.max(line.text.start))
+    }
+
+    /// Get caret rectangle before the
+    /// character at byte index `index`
+    ///
+    /// Carets are 1 unit wide and as
+    /// tall as the line.
+    pub fn caret(&self, index: usize) -> TextRect {
+        let line = self
+            .lines
+            .iter()
+            .rev()
+            .find(|l| l.text.start <= index)
+            .or_else(|| self.lines.first());
+
+        match line {
+            // index is after a final new line
+            Some(l) if index > l.text.end => TextRect {
+                pos: Vec2::new(0.0, l.top - l.height),
+                size: Vec2::new(1.0, self.line_height),
+            },
+            Some(l) => TextRect {
+                pos: Vec2::new(self.caret_x(l, index), l.top),
+                size: Vec2::new(1.0, l.height),
+            },
+            None => TextRect {
+                pos: Vec2::default(),
+                size: Vec2::new(1.0, self.line_height),
+            },
+        }
+    }
+
+    /// Get selection rectangles of the
+    /// byte range `range`, one for each line
+    pub fn selection(&self, range: Range<usize>) -> Vec<TextRect> {
+        self.lines
+            .iter()
+            .filter(|l| range.start <= l.text.end && range.end > l.text.start)
+            .filter_map(|l| {
+                let start = self.caret_x(l, range.start.max(l.text.start));
+                let end = self.caret_x(l, range.end.min(l.text.end));
+                if end > start {
+                    Some(TextRect {
+                        pos: Vec2::new(start, l.top),
+                        size: Vec2::new(end - start, l.height),
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    fn caret_x(&self, line: &TextLine, index: usize) -> f32 {
+        let glyphs = &self.glyphs[line.glyphs.clone()];
+        match glyphs.iter().find(|g| g.index >= index) {
+            Some(g) => g.pos.x,
+            None => glyphs.last().map_or(line.x, |g| g.pos.x + g.advance),
+        }
+    }
+
+    /// Get laid out lines
+    pub fn lines(&self) -> &[TextLine] {
+        &self.lines
+    }
+
+    /// Get laid out glyphs
+    pub fn glyphs(&self) -> &[LayoutGlyph] {
+        &self.glyphs
+    }
+
+    /// Get width of the widest line
+    pub const fn width(&self) -> f32 {
+        self.width
+    }
+
+    /// Get height of all lines
+    pub const fn height(&self) -> f32 {
+        self.height
+    }
+
+    // width of the widest line with trailing spaces
+    pub(crate) fn advance_width(&self) -> f32 {
+        self.lines
+            .iter()
+            .map(|l| {
+                self.glyphs[l.glyphs.clone()]
+                    .last()
+                    .map_or(0.0, |g| g.pos.x + g.advance - l.x)
+            })
+            .fold(0.0, f32::max)
+    }
+}
+
+impl Default for TextStyle {
+    fn default() -> Self {
+        Self {
+            align: TextAlign::Left,
+            line_height: 1.0,
+            wrap: TextWrap::Word,
+            overflow: TextOverflow::Clip,
+        }
+    }
+}
+
+// splits text into runs of characters
+// that use the same font
+pub(crate) fn font_runs(fonts: &[&Font], text: &str) -> Vec<(usize, Range<usize>)> {
+    let mut runs: Vec<(usize, Range<usize>)> = vec![];
+
+    for (i, c) in text.char_indices() {
+        let end = i + c.len_utf8();
+
+        // spaces continue the current run
+        let font = match runs.last() {
+            Some((f, _)) if c == ' ' => *f,
+            _ => fonts.iter().position(|f| f.has_char(c)).unwrap_or(0),
+        };
+
+        match runs.last_mut() {
+            Some((f, range)) if *f == font => range.end = end,
+            _ => runs.push((font, i..end)),
+        }
+    }
+
+    runs
+}
+
+// shapes runs with their fonts
+fn shape_runs(fonts: &[&Font], text: &str) -> Vec<(usize, ShapedGlyph)> {
+    let mut glyphs = vec![];
+    for (f, range) in font_runs(fonts, text) {
+        for mut shaped in fonts[f].shape(&text[range.clone()]) {
+            shaped.index += range.start;
+            glyphs.push((f, shaped));
+        }
+    }
+    glyphs
+}
+
+// line that is still being broken
+struct PendingLine {
+    glyphs: Vec<LayoutGlyph>,
+    part: usize,
+    text: Range<usize>,
+}
+
+impl PendingLine {
+    const fn new(part: usize, start: usize) -> Self {
+        Self {
+            glyphs: vec![],
+            text: start..start,
+            part,
+        }
+    }
+
+    fn until(mut self, end: usize) -> Self {
+        self.text.end = end;
+        self
+    }
+}
+
+// glyph used for ellipsis
+#[derive(Copy, Clone)]
+struct Dot {
+    glyph: u32,
+    advance: f32,
+    span: usize,
+}
+
+fn fit_line(line: &mut Vec<LayoutGlyph>, max: f32, overflow: TextOverflow, dot: Dot) {
+    if line_width(line) <= max {
+        return;
+    }
+
+    match overflow {
+        TextOverflow::Clip => line.retain(|g| g.pos.x + g.advance <= max),
+        TextOverflow::Ellipsis => {
+            let limit = max - dot.advance * 3.0;
+            let index = line
+                .iter()
+                .find(|g| g.pos.x + g.advance > limit)
+                .map_or(0, |g| g.index);
+            line.retain(|g| g.pos.x + g.advance <= limit);
+
+            let mut x = line.last().map_or(0.0, |g| g.pos.x + g.advance);
+            for _ in 0..3 {
+                line.push(LayoutGlyph {
+                    c: '.',
+                    glyph: dot.glyph,
+                    pos: Vec2::new(x, 0.0),
+                    advance: dot.advance,
+                    span: dot.span,
+                    line: 0,
+                    font: 0,
+                    index,
+                });
+                x += dot.advance;
+            }
+        }
+    }
+}
+
+// width without trailing spaces
+fn line_width(line: &[LayoutGlyph]) -> f32 {
+    line.iter()
+        .rev()
+        .find(|g| g.c != ' ')
+        .map_or(0.0, |g| g.pos.x + g.advance)
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::fira_mono;
+    use super::super::Font;
+    use super::LayoutPart;
+    use super::TextAlign;
+    use super::TextLayout;
+    use super::TextOverflow;
+    use super::TextStyle;
+    use super::TextWrap;
+
+    const SIZE: u32 = 12;
+
+    fn font() -> Font {
+        Font::with_textures(fira_mono(), vec![]).expect("bad font")
+    }
+
+    // fira mono advance at SIZE
+    fn advance() -> f32 {
+        font()
+            .char_data('a')
+            .expect("no glyph")
+            .scaled(SIZE)
+            .advance
+    }
+
+    // spaces are a third of the font size
+    fn space() -> f32 {
+        SIZE as f32 / 3.0
+    }
+
+    // lays out text with max width in advances
+    fn layout(text: &str, columns: Option<f32>, style: TextStyle) -> TextLayout {
+        let font = font();
+        let part = LayoutPart {
+            fonts: &[&font],
+            font_size: SIZE,
+            text,
+        };
+        TextLayout::from_parts(&[part], columns.map(|c| c * advance()), style)
+    }
+
+    fn lines<'a>(layout: &TextLayout, text: &'a str) -> Vec<&'a str> {
+        layout
+            .lines()
+            .iter()
+            .map(|l| &text[l.text.clone()])
+            .collect()
+    }
+
+    #[test]
+    fn new_lines() {
+        let text = "ab\ncd\r\n";
+        let l = layout(text, None, TextStyle::default());
+        assert_eq!(lines(&l, text), vec!["ab", "cd"]);
+        assert_eq!(l.lines()[1].glyphs, 2..4);
+        assert_eq_delta!(l.width(), advance() * 2.0);
+        assert_eq_delta!(l.glyphs()[3].pos.x, advance());
+    }
+
+    #[test]
+    fn word_wrap() {
+        let text = "ab cd ef";
+        let l = layout(text, Some(5.5), TextStyle::default());
+        assert_eq!(lines(&l, text), vec!["ab cd ", "ef"]);
+        assert_eq_delta!(l.lines()[0].width, advance() * 4.0 + space());
+        assert_eq_delta!(l.glyphs()[6].pos.x, 0.0);
+    }
+
+    #[test]
+    fn word_wrap_long_word() {
+        let text = "abcdef";
+        let l = layout(text, Some(4.5), TextStyle::default());
+        assert_eq!(lines(&l, text), vec!["abcd", "ef"]);
+        assert_eq!(l.glyphs().len(), 6);
+        assert_eq_delta!(l.glyphs()[4].pos.x, 0.0);
+    }
+
+    #[test]
+    fn char_wrap() {
+        let style = TextStyle {
+            wrap: TextWrap::Char,
+            ..Default::default()
+        };
+        let text = "ab cd";
+        let l = layout(text, Some(3.5), style);
+        assert_eq!(lines(&l, text), vec!["ab ", "cd"]);
+    }
+
+    #[test]
+    fn align() {
+        let style = TextStyle {
+            align: TextAlign::Right,
+            ..Default::default()
+        };
+        let l = layout("ab", Some(5.0), style);
+        assert_eq_delta!(l.lines()[0].x, advance() * 3.0);
+        assert_eq_delta!(l.glyphs()[0].pos.x, advance() * 3.0);
+
+        let style = TextStyle {
+            align: TextAlign::Center,
+            ..Default::default()
+        };
+        let l = layout("ab", Some(5.0), style);
+        assert_eq_delta!(l.lines()[0].x, advance() * 1.5);
+    }
+
+    #[test]
+    fn clip() {
+        let style = TextStyle {
+            wrap: TextWrap::None,
+            ..Default::default()
+        };
+        let l = layout("abcdef", Some(3.5), style);
+        assert_eq!(l.glyphs().len(), 3);
+    }
+
+    #[test]
+    fn ellipsis() {
+        let style = TextStyle {
+            wrap: TextWrap::None,
+            overflow: TextOverflow::Ellipsis,
+            ..Default::default()
+        };
+        let l = layout("abcdefgh", Some(5.5), style);
+        let chars: String = l.glyphs().iter().map(|g| g.c).collect();
+        assert_eq!(chars, "ab...");
+        assert_eq!(l.glyphs()[2].index, 2);
+        assert_eq_delta!(l.glyphs()[4].pos.x, advance() * 4.0);
+    }
+
+    #[test]
+    fn trailing_spaces() {
+        let l = layout("ab  ", None, TextStyle::default());
+        assert_eq_delta!(l.width(), advance() * 2.0);
+        assert_eq_delta!(l.advance_width(), (advance() + space()) * 2.0);
+    }
+}
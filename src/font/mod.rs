@@ -1,11 +1,16 @@
 // Oliver Berzs
 // https://github.com/oberzs/duku
 
+mod cache;
+mod edit;
 mod fira_mono;
+mod layout;
+mod span;
 
 use std::collections::HashMap;
 
 use crate::device::Device;
+use crate::error::Error;
 use crate::error::Result;
 use crate::image::Format;
 use crate::image::Mips;
@@ -13,13 +18,45 @@ use crate::image::Texture;
 use crate::math::Vec2;
 use crate::math::Vec4;
 use crate::pipeline::Uniforms;
+use cache::GlyphCache;
 use fira_mono::fira_mono;
 
+#[cfg(feature = "otf")]
+pub(crate) use cache::GlyphSource;
+#[cfg(feature = "otf")]
+pub(crate) use cache::RasterGlyph;
+#[cfg(feature = "otf")]
+pub(crate) use cache::MAX_ATLAS_SIZE;
+#[cfg(feature = "otf")]
+pub(crate) use cache::MIN_ATLAS_SIZE;
+
+pub use edit::TextEdit;
+pub(crate) use layout::font_runs;
+pub use layout::LayoutGlyph;
+pub use layout::TextAlign;
+pub use layout::TextLayout;
+pub use layout::TextLine;
+pub use layout::TextOverflow;
+pub use layout::TextRect;
+pub use layout::TextStyle;
+pub use layout::TextWrap;
+pub use span::Span;
+
+pub(crate) use layout::LayoutPart;
+
 /// Font for text drawing.
 pub struct Font {
     metrics: FontMetrics,
-    char_data: HashMap<char, CharData>,
-    texture: Texture,
+    char_glyphs: HashMap<char, u32>,
+    glyph_data: HashMap<u32, CharData>,
+    kerning: HashMap<(u32, u32), f32>,
+    ligatures: HashMap<Vec<u32>, u32>,
+    max_ligature: usize,
+    fallback: u32,
+    sdf_range: Option<f32>,
+    textures: Vec<Texture>,
+    cache: Option<GlyphCache>,
+    kerning_source: Option<Box<dyn KerningSource>>,
 }
 
 /// Font data and metrics.
@@ -35,14 +72,23 @@ pub struct FontData<'a> {
     pub ascender: f32,
     /// the lowest point below the baseline (negative)
     pub descender: f32,
-    /// data for all loaded characters
-    pub char_data: HashMap<char, CharData>,
-    /// grayscale bytes for creating the font atlas texture
-    pub texture_data: &'a [u8],
-    /// atlas texture's width
+    /// glyph ids for all loaded characters
+    pub char_glyphs: HashMap<char, u32>,
+    /// data for all loaded glyphs
+    pub glyph_data: HashMap<u32, CharData>,
+    /// advance adjustment between two glyphs
+    pub kerning: HashMap<(u32, u32), f32>,
+    /// glyph sequences replaced by a single glyph
+    pub ligatures: HashMap<Vec<u32>, u32>,
+    /// grayscale bytes for each font atlas page
+    pub texture_data: Vec<&'a [u8]>,
+    /// atlas page's width
     pub texture_width: u32,
-    /// atlas texture's height
+    /// atlas page's height
     pub texture_height: u32,
+    /// signed distance range in ems if the
+    /// atlas is a signed distance field
+    pub sdf_range: Option<f32>,
 }
 
 /// Character data and metrics.
@@ -59,6 +105,22 @@ pub struct CharData {
     pub bearing: Vec2,
     /// advance between this and the next character in a string
     pub advance: f32,
+    /// index of the atlas page containing the character
+    pub page: u32,
+}
+
+// font that reads kerning of glyph pairs on demand
+pub(crate) trait KerningSource: Send + Sync {
+    // kerning between glyphs in ems
+    fn kerning(&self, first: u32, second: u32) -> f32;
+}
+
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct ShapedGlyph {
+    pub(crate) id: u32,
+    pub(crate) c: char,
+    pub(crate) index: usize,
+    pub(crate) advance: f32,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -80,15 +142,37 @@ impl Font {
         uniforms: &mut Uniforms,
         data: FontData<'_>,
     ) -> Result<Self> {
-        let texture = Texture::new(
-            device,
-            uniforms,
-            data.texture_data.to_vec(),
-            data.texture_width,
-            data.texture_height,
-            Format::Gray,
-            Mips::Zero,
-        )?;
+        let textures = data
+            .texture_data
+            .iter()
+            .map(|page| {
+                Texture::new(
+                    device,
+                    uniforms,
+                    page.to_vec(),
+                    data.texture_width,
+                    data.texture_height,
+                    Format::Gray,
+                    Mips::Zero,
+                )
+            })
+            .collect::<Result<_>>()?;
+
+        Self::with_textures(data, textures)
+    }
+
+    fn with_textures(data: FontData<'_>, textures: Vec<Texture>) -> Result<Self> {
+        // use '?' for missing characters if possible
+        let fallback = match data.char_glyphs.get(&'?') {
+            Some(id) if data.glyph_data.contains_key(id) => *id,
+            _ => data
+                .glyph_data
+                .keys()
+                .min()
+                .copied()
+                .ok_or(Error::InvalidFont)?,
+        };
+        let max_ligature = data.ligatures.keys().map(|l| l.len()).max().unwrap_or(0);
 
         Ok(Self {
             metrics: FontMetrics {
@@ -98,32 +182,249 @@ impl Font {
                 descender: data.descender,
                 space_width: 1.0 / 3.0,
             },
-            char_data: data.char_data,
-            texture,
+            char_glyphs: data.char_glyphs,
+            glyph_data: data.glyph_data,
+            kerning: data.kerning,
+            ligatures: data.ligatures,
+            sdf_range: data.sdf_range,
+            max_ligature,
+            fallback,
+            textures,
+            cache: None,
+            kerning_source: None,
         })
     }
 
-    pub(crate) const fn texture(&self) -> &Texture {
-        &self.texture
+    // font that loads glyphs when they are first used
+    #[cfg(feature = "otf")]
+    pub(crate) fn dynamic(
+        device: &Device,
+        uniforms: &mut Uniforms,
+        source: Box<dyn GlyphSource>,
+    ) -> Result<Self> {
+        let cache = GlyphCache::new(source);
+        let size = cache.size();
+        let texture = Texture::new(
+            device,
+            uniforms,
+            cache.data().to_vec(),
+            size,
+            size,
+            Format::Gray,
+            Mips::Zero,
+        )?;
+
+        let mut font = Self {
+            metrics: cache.metrics(),
+            char_glyphs: HashMap::new(),
+            glyph_data: HashMap::new(),
+            kerning: HashMap::new(),
+            ligatures: HashMap::new(),
+            max_ligature: 0,
+            // missing characters use glyph 0
+            fallback: 0,
+            sdf_range: None,
+            textures: vec![texture],
+            cache: Some(cache),
+            kerning_source: None,
+        };
+        font.load_text("");
+
+        Ok(font)
+    }
+
+    /// Check if glyphs are loaded when first used
+    pub const fn is_dynamic(&self) -> bool {
+        self.cache.is_some()
+    }
+
+    // reads kerning when glyph pairs are first used
+    #[cfg(feature = "otf")]
+    pub(crate) fn set_kerning_source(&mut self, source: Box<dyn KerningSource>) {
+        self.kerning_source = Some(source);
+    }
+
+    // fonts that need text loaded before layout
+    pub(crate) fn is_lazy(&self) -> bool {
+        self.cache.is_some() || self.kerning_source.is_some()
+    }
+
+    // loads missing glyphs and kerning of dynamic fonts
+    // and kerning of fonts with a kerning source
+    pub(crate) fn load_text(&mut self, text: &str) {
+        if let Some(source) = &self.kerning_source {
+            let ids: Vec<_> = text.chars().map(|c| self.glyph_id(c)).collect();
+            for pair in ids.windows(2) {
+                self.kerning
+                    .entry((pair[0], pair[1]))
+                    .or_insert_with(|| source.kerning(pair[0], pair[1]));
+            }
+        }
+
+        let cache = match &mut self.cache {
+            Some(c) => c,
+            None => return,
+        };
+
+        cache.load(self.fallback, &mut self.glyph_data);
+
+        let mut previous = None;
+        for c in text.chars() {
+            let id = *self
+                .char_glyphs
+                .entry(c)
+                .or_insert_with(|| cache.glyph_id(c));
+            cache.load(id, &mut self.glyph_data);
+
+            if let Some(p) = previous {
+                self.kerning
+                    .entry((p, id))
+                    .or_insert_with(|| cache.kerning(p, id));
+            }
+            previous = Some(id);
+        }
+    }
+
+    // grows the atlas and uploads new glyphs
+    // at the start of a frame
+    pub(crate) fn update(&mut self, device: &Device, uniforms: &mut Uniforms) {
+        let cache = match &mut self.cache {
+            Some(c) => c,
+            None => return,
+        };
+
+        if cache.should_grow() {
+            let size = cache.size() * 2;
+            let data = vec![0; (size * size) as usize];
+            match Texture::new(device, uniforms, data, size, size, Format::Gray, Mips::Zero) {
+                Ok(texture) => {
+                    self.textures[0].destroy(device, uniforms);
+                    self.textures[0] = texture;
+                    cache.grow(&mut self.glyph_data);
+                }
+                Err(_) => cache.stop_growing(),
+            }
+        }
+
+        cache.next_frame();
+        self.upload(device);
+    }
+
+    // uploads glyphs loaded while recording
+    pub(crate) fn upload(&mut self, device: &Device) {
+        if let Some(cache) = &mut self.cache {
+            if cache.take_dirty() {
+                self.textures[0].data.copy_from_slice(cache.data());
+                self.textures[0].update(device);
+            }
+        }
+    }
+
+    pub(crate) fn texture(&self, page: u32) -> &Texture {
+        &self.textures[page as usize]
+    }
+
+    /// Get signed distance range in ems
+    ///
+    /// Is `None` for bitmap fonts
+    pub const fn sdf_range(&self) -> Option<f32> {
+        self.sdf_range
     }
 
     pub(crate) const fn metrics(&self) -> FontMetrics {
         self.metrics
     }
 
-    pub(crate) fn char_data(&self, c: char) -> CharData {
-        match self.char_data.get(&c) {
-            Some(data) => *data,
-            None => *self
-                .char_data
-                .values()
-                .next()
-                .expect("font has no characters"),
+    /// Check if font contains a character
+    pub fn has_char(&self, c: char) -> bool {
+        match &self.cache {
+            Some(cache) => cache.glyph_id(c) != 0,
+            None => self.char_glyphs.contains_key(&c),
         }
     }
 
+    pub(crate) fn char_data(&self, c: char) -> Result<CharData> {
+        self.glyph_data(self.glyph_id(c))
+    }
+
+    pub(crate) fn glyph_id(&self, c: char) -> u32 {
+        self.char_glyphs.get(&c).copied().unwrap_or(self.fallback)
+    }
+
+    pub(crate) fn glyph_data(&self, id: u32) -> Result<CharData> {
+        self.glyph_data
+            .get(&id)
+            .or_else(|| self.glyph_data.get(&self.fallback))
+            .copied()
+            .ok_or(Error::InvalidFont)
+    }
+
+    pub(crate) fn kerning(&self, first: u32, second: u32) -> f32 {
+        self.kerning.get(&(first, second)).copied().unwrap_or(0.0)
+    }
+
+    // maps text to glyphs, substituting ligatures
+    // and applying kerning to the advances
+    pub(crate) fn shape(&self, text: &str) -> Vec<ShapedGlyph> {
+        let chars: Vec<_> = text
+            .char_indices()
+            .map(|(index, c)| ShapedGlyph {
+                id: self.glyph_id(c),
+                advance: 0.0,
+                index,
+                c,
+            })
+            .collect();
+
+        // substitute longest ligatures first
+        let mut glyphs = Vec::with_capacity(chars.len());
+        let mut i = 0;
+        while i < chars.len() {
+            let longest = self.max_ligature.min(chars.len() - i);
+            let ligature = (2..=longest).rev().find_map(|len| {
+                let part = &chars[i..i + len];
+                if part.iter().any(|g| g.c == ' ') {
+                    return None;
+                }
+                let ids: Vec<_> = part.iter().map(|g| g.id).collect();
+                self.ligatures.get(&ids).map(|id| (*id, len))
+            });
+
+            match ligature {
+                Some((id, len)) => {
+                    glyphs.push(ShapedGlyph { id, ..chars[i] });
+                    i += len;
+                }
+                None => {
+                    glyphs.push(chars[i]);
+                    i += 1;
+                }
+            }
+        }
+
+        // calculate advances
+        for i in 0..glyphs.len() {
+            let glyph = glyphs[i];
+            if glyph.c == ' ' {
+                glyphs[i].advance = self.metrics.space_width;
+                continue;
+            }
+
+            let kerning = match glyphs.get(i + 1) {
+                Some(next) if next.c != ' ' => self.kerning(glyph.id, next.id),
+                _ => 0.0,
+            };
+            let advance = self.glyph_data(glyph.id).map_or(0.0, |d| d.advance);
+            glyphs[i].advance = advance + kerning;
+        }
+
+        glyphs
+    }
+
     pub(crate) fn destroy(&self, device: &Device, uniforms: &mut Uniforms) {
-        self.texture.destroy(device, uniforms);
+        for texture in &self.textures {
+            texture.destroy(device, uniforms);
+        }
     }
 }
 
@@ -148,6 +449,7 @@ impl CharData {
             bounds: self.bounds * scale,
             bearing: self.bearing * scale,
             advance: self.advance * scale,
+            page: self.page,
         }
     }
 }
@@ -0,0 +1,213 @@
+// Oliver Berzs
+// https://github.com/oberzs/duku
+
+// Span - styled part of rich text
+
+use super::Font;
+use crate::color::Rgb;
+use crate::resources::Handle;
+
+/// Part of rich text with its own style.
+///
+/// Unset values use the target's settings.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use duku::Duku;
+/// # use duku::Span;
+/// # let (mut d, _) = Duku::windowed(1, 1).unwrap();
+/// # d.draw(None, |t| {
+/// let spans = [
+///     Span::new("error: "),
+///     Span {
+///         color: Some("#ff0000".into()),
+///         underline: true,
+///         ..Span::new("file not found")
+///     },
+/// ];
+/// t.rich_text(&spans, [0.0, 0.0]);
+/// # });
+/// ```
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Span<'a> {
+    /// text of the span
+    pub text: &'a str,
+    /// color of the text
+    pub color: Option<Rgb>,
+    /// font of the text
+    pub font: Option<&'a Handle<Font>>,
+    /// font size of the text
+    pub size: Option<u32>,
+    /// draw line under the text
+    pub underline: bool,
+    /// draw line through the text
+    pub strikethrough: bool,
+}
+
+impl<'a> Span<'a> {
+    /// Create span with default style
+    pub const fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            color: None,
+            font: None,
+            size: None,
+            underline: false,
+            strikethrough: false,
+        }
+    }
+
+    /// Parse spans from markup
+    ///
+    /// Supported tags are `[color=#rrggbb]`,
+    /// `[size=24]`, `[u]` for underline and
+    /// `[s]` for strikethrough. Tags are closed
+    /// with `[/color]`, `[/size]`, `[/u]` and `[/s]`.
+    /// Use `[[` for a literal `[`. Unknown tags
+    /// are kept as text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use duku::Span;
+    /// let spans = Span::parse("[color=#ff0000]error[/color]: [u]file[/u]");
+    /// assert_eq!(spans.len(), 3);
+    /// assert_eq!(spans[0].text, "error");
+    /// ```
+    pub fn parse(markup: &'a str) -> Vec<Self> {
+        let mut spans = vec![];
+        let mut styles = vec![Span::default()];
+        let mut start = 0;
+        let mut i = 0;
+
+        while i < markup.len() {
+            let rest = &markup[i..];
+            if !rest.starts_with('[') {
+                i += rest.chars().next().map_or(1, char::len_utf8);
+                continue;
+            }
+
+            // escaped bracket
+            if rest.starts_with("[[") {
+                push_span(&mut spans, &styles, &markup[start..=i]);
+                i += 2;
+                start = i;
+                continue;
+            }
+
+            let end = match rest.find(']') {
+                Some(e) => e,
+                None => break,
+            };
+            let tag = &rest[1..end];
+            let current = *styles.last().expect("bad code");
+
+            let style = match tag_style(tag, current) {
+                Some(s) => s,
+                None if is_closing(tag) && styles.len() > 1 => {
+                    // closing tag
+                    push_span(&mut spans, &styles, &markup[start..i]);
+                    styles.pop();
+                    i += end + 1;
+                    start = i;
+                    continue;
+                }
+                None => {
+                    i += 1;
+                    continue;
+                }
+            };
+
+            push_span(&mut spans, &styles, &markup[start..i]);
+            styles.push(style);
+            i += end + 1;
+            start = i;
+        }
+
+        push_span(&mut spans, &styles, &markup[start..]);
+        spans
+    }
+}
+
+// parses opening tag into a style
+// based on the current style
+fn tag_style<'a>(tag: &str, current: Span<'a>) -> Option<Span<'a>> {
+    let (name, value) = match tag.find('=') {
+        Some(i) => (&tag[..i], Some(&tag[i + 1..])),
+        None => (tag, None),
+    };
+
+    match (name, value) {
+        ("color", Some(v)) => Some(Span {
+            color: Some(parse_color(v)?),
+            ..current
+        }),
+        ("size", Some(v)) => Some(Span {
+            size: Some(v.parse().ok()?),
+            ..current
+        }),
+        ("u", None) => Some(Span {
+            underline: true,
+            ..current
+        }),
+        ("s", None) => Some(Span {
+            strikethrough: true,
+            ..current
+        }),
+        _ => None,
+    }
+}
+
+fn is_closing(tag: &str) -> bool {
+    matches!(tag, "/color" | "/size" | "/u" | "/s")
+}
+
+// supports #rgb, #rrggbb and #rrggbbaa
+fn parse_color(value: &str) -> Option<Rgb> {
+    let hex = value.trim_start_matches('#');
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    match hex.len() {
+        3 => {
+            let long: String = hex.chars().flat_map(|c| vec![c, c]).collect();
+            Some(Rgb::from(long.as_str()))
+        }
+        6 | 8 => Some(Rgb::from(hex)),
+        _ => None,
+    }
+}
+
+fn push_span<'a>(spans: &mut Vec<Span<'a>>, styles: &[Span<'a>], text: &'a str) {
+    if !text.is_empty() {
+        let style = styles.last().expect("bad code");
+        spans.push(Span { text, ..*style });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Span;
+    use crate::color::Rgb;
+
+    #[test]
+    fn parse_nested() {
+        let spans = Span::parse("a[color=#f00]b[u]c[/u][/color]d");
+        assert_eq!(spans.len(), 4);
+        assert_eq!(spans[0], Span::new("a"));
+        assert_eq!(spans[1].color, Some(Rgb::new(255, 0, 0)));
+        assert!(spans[2].underline);
+        assert_eq!(spans[2].color, Some(Rgb::new(255, 0, 0)));
+        assert_eq!(spans[3], Span::new("d"));
+    }
+
+    #[test]
+    fn parse_unknown_and_escaped() {
+        let spans = Span::parse("[[x] [b]y[/b]");
+        let text: String = spans.iter().map(|s| s.text).collect();
+        assert_eq!(text, "[x] [b]y[/b]");
+        assert!(spans.iter().all(|s| s.color.is_none()));
+    }
+}
@@ -0,0 +1,363 @@
+// Oliver Berzs
+// https://github.com/oberzs/duku
+
+// Animation - texture parts played one after another
+
+use super::Texture;
+use crate::math::Vec2;
+use crate::resources::Handle;
+
+/// Sprite sheet animation.
+///
+/// Plays parts of a texture one after another.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use duku::Duku;
+/// # use duku::Animation;
+/// # use duku::Format;
+/// # use duku::Mips;
+/// # let (mut d, _) = Duku::windowed(1, 1).unwrap();
+/// # let texture = d.create_texture(vec![255; 32], Format::Srgba, Mips::Zero, 8, 1).unwrap();
+/// let mut run = Animation::grid(&texture, 8, 1, 10.0);
+///
+/// // every frame
+/// run.update(d.delta_time());
+/// d.draw(None, |t| {
+///     t.animation(&run, [0.0, 0.0]);
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct Animation {
+    texture: Handle<Texture>,
+    frames: Vec<AnimationFrame>,
+    playback: Playback,
+}
+
+/// Single frame of an animation.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct AnimationFrame {
+    /// position of the frame in the texture in pixels
+    pub pos: Vec2,
+    /// size of the frame in pixels
+    pub size: Vec2,
+    /// how long the frame is shown in seconds
+    pub duration: f32,
+}
+
+/// Animation playback mode.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AnimationMode {
+    /// play frames once and stop on the last one
+    Once,
+    /// play frames from start to end repeatedly
+    Loop,
+    /// play frames forwards and then backwards
+    PingPong,
+}
+
+// frame position and playback settings
+#[derive(Debug, Clone)]
+struct Playback {
+    mode: AnimationMode,
+    speed: f32,
+    time: f32,
+    current: usize,
+    reverse: bool,
+    playing: bool,
+}
+
+impl Animation {
+    /// Create animation from frames
+    pub fn new(texture: &Handle<Texture>, frames: Vec<AnimationFrame>) -> Self {
+        Self {
+            texture: texture.clone(),
+            playback: Playback::new(),
+            frames,
+        }
+    }
+
+    /// Create animation by splitting the texture into a grid
+    ///
+    /// Frames go from left to right, top to bottom
+    /// and are played at `fps` frames per second.
+    pub fn grid(texture: &Handle<Texture>, columns: u32, rows: u32, fps: f32) -> Self {
+        let (width, height) = {
+            let tex = texture.read();
+            (tex.width(), tex.height())
+        };
+        Self::new(texture, grid_frames(width, height, columns, rows, fps))
+    }
+
+    /// Advance animation by the time passed
+    pub fn update(&mut self, delta_time: f32) {
+        self.playback.update(&self.frames, delta_time);
+    }
+
+    /// Continue playing the animation
+    pub fn play(&mut self) {
+        self.playback.playing = true;
+    }
+
+    /// Pause the animation on the current frame
+    pub fn pause(&mut self) {
+        self.playback.playing = false;
+    }
+
+    /// Go back to the first frame and keep playing
+    pub fn restart(&mut self) {
+        self.playback = Playback {
+            mode: self.playback.mode,
+            speed: self.playback.speed,
+            ..Playback::new()
+        };
+    }
+
+    /// Set playback mode
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use duku::Duku;
+    /// # use duku::Animation;
+    /// # use duku::AnimationMode;
+    /// # use duku::Format;
+    /// # use duku::Mips;
+    /// # let (mut d, _) = Duku::windowed(1, 1).unwrap();
+    /// # let texture = d.create_texture(vec![255; 32], Format::Srgba, Mips::Zero, 8, 1).unwrap();
+    /// let mut run = Animation::grid(&texture, 8, 1, 10.0);
+    ///
+    /// // play frames 0..7 and then back to 0
+    /// run.mode(AnimationMode::PingPong);
+    /// ```
+    pub fn mode(&mut self, mode: AnimationMode) {
+        self.playback.mode = mode;
+    }
+
+    /// Set playback speed multiplier
+    ///
+    /// Negative speeds are treated as 0.
+    pub fn speed(&mut self, speed: f32) {
+        self.playback.speed = speed.max(0.0);
+    }
+
+    /// Check if animation is playing
+    ///
+    /// Animations with [Once](crate::image::AnimationMode::Once)
+    /// mode stop playing after the last frame.
+    pub const fn is_playing(&self) -> bool {
+        self.playback.playing
+    }
+
+    /// Get index of the current frame
+    pub const fn frame_index(&self) -> usize {
+        self.playback.current
+    }
+
+    /// Get the current frame
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the
+    /// animation has no frames.
+    pub fn frame(&self) -> AnimationFrame {
+        self.frames[self.playback.current]
+    }
+
+    /// Get all animation frames
+    pub fn frames(&self) -> &[AnimationFrame] {
+        &self.frames
+    }
+
+    /// Get texture of the animation
+    pub const fn texture(&self) -> &Handle<Texture> {
+        &self.texture
+    }
+}
+
+impl Playback {
+    const fn new() -> Self {
+        Self {
+            mode: AnimationMode::Loop,
+            speed: 1.0,
+            time: 0.0,
+            current: 0,
+            reverse: false,
+            playing: true,
+        }
+    }
+
+    fn update(&mut self, frames: &[AnimationFrame], delta_time: f32) {
+        if !self.playing || frames.is_empty() {
+            return;
+        }
+
+        self.time += delta_time * self.speed;
+        loop {
+            let duration = frames[self.current].duration;
+            if duration <= 0.0 || self.time < duration {
+                break;
+            }
+            self.time -= duration;
+            self.next_frame(frames.len() - 1);
+
+            if !self.playing {
+                self.time = 0.0;
+                break;
+            }
+        }
+    }
+
+    fn next_frame(&mut self, last: usize) {
+        match self.mode {
+            AnimationMode::Once => {
+                if self.current < last {
+                    self.current += 1;
+                } else {
+                    self.playing = false;
+                }
+            }
+            AnimationMode::Loop => {
+                self.current = if self.current < last {
+                    self.current + 1
+                } else {
+                    0
+                };
+            }
+            AnimationMode::PingPong => {
+                if last == 0 {
+                    return;
+                }
+                if self.reverse && self.current == 0 {
+                    self.reverse = false;
+                } else if !self.reverse && self.current == last {
+                    self.reverse = true;
+                }
+                if self.reverse {
+                    self.current -= 1;
+                } else {
+                    self.current += 1;
+                }
+            }
+        }
+    }
+}
+
+// splits an image into a grid of frames
+// going from left to right, top to bottom
+fn grid_frames(width: u32, height: u32, columns: u32, rows: u32, fps: f32) -> Vec<AnimationFrame> {
+    let frame_width = width as f32 / columns.max(1) as f32;
+    let frame_height = height as f32 / rows.max(1) as f32;
+    let duration = if fps > 0.0 { 1.0 / fps } else { 0.0 };
+
+    (0..rows)
+        .flat_map(|y| (0..columns).map(move |x| (x, y)))
+        .map(|(x, y)| AnimationFrame {
+            pos: Vec2::new(x as f32 * frame_width, y as f32 * frame_height),
+            size: Vec2::new(frame_width, frame_height),
+            duration,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::grid_frames;
+    use super::AnimationFrame;
+    use super::AnimationMode;
+    use super::Playback;
+    use crate::math::Vec2;
+
+    fn frames(count: usize) -> Vec<AnimationFrame> {
+        vec![
+            AnimationFrame {
+                pos: Vec2::new(0.0, 0.0),
+                size: Vec2::new(1.0, 1.0),
+                duration: 0.1,
+            };
+            count
+        ]
+    }
+
+    fn sequence(playback: &mut Playback, frames: &[AnimationFrame], steps: usize) -> Vec<usize> {
+        (0..steps)
+            .map(|_| {
+                playback.update(frames, 0.1);
+                playback.current
+            })
+            .collect()
+    }
+
+    #[test]
+    fn loop_mode() {
+        let f = frames(3);
+        let mut p = Playback::new();
+        assert_eq!(sequence(&mut p, &f, 4), vec![1, 2, 0, 1]);
+        assert!(p.playing);
+    }
+
+    #[test]
+    fn once_mode() {
+        let f = frames(3);
+        let mut p = Playback::new();
+        p.mode = AnimationMode::Once;
+        assert_eq!(sequence(&mut p, &f, 4), vec![1, 2, 2, 2]);
+        assert!(!p.playing);
+    }
+
+    #[test]
+    fn ping_pong_mode() {
+        let f = frames(3);
+        let mut p = Playback::new();
+        p.mode = AnimationMode::PingPong;
+        assert_eq!(sequence(&mut p, &f, 6), vec![1, 2, 1, 0, 1, 2]);
+
+        let single = frames(1);
+        let mut p = Playback::new();
+        p.mode = AnimationMode::PingPong;
+        assert_eq!(sequence(&mut p, &single, 2), vec![0, 0]);
+    }
+
+    #[test]
+    fn pause() {
+        let f = frames(3);
+        let mut p = Playback::new();
+        p.playing = false;
+        assert_eq!(sequence(&mut p, &f, 2), vec![0, 0]);
+        p.playing = true;
+        assert_eq!(sequence(&mut p, &f, 1), vec![1]);
+    }
+
+    #[test]
+    fn speed() {
+        let f = frames(4);
+        let mut p = Playback::new();
+        p.speed = 2.0;
+        assert_eq!(sequence(&mut p, &f, 2), vec![2, 0]);
+
+        // a long step skips several frames
+        let mut p = Playback::new();
+        p.update(&f, 0.25);
+        assert_eq!(p.current, 2);
+        assert_eq_delta!(p.time, 0.05);
+    }
+
+    #[test]
+    fn empty() {
+        let mut p = Playback::new();
+        p.update(&[], 1.0);
+        assert_eq!(p.current, 0);
+    }
+
+    #[test]
+    fn grid() {
+        let f = grid_frames(64, 32, 4, 2, 10.0);
+        assert_eq!(f.len(), 8);
+        assert_eq!(f[1].pos, Vec2::new(16.0, 0.0));
+        assert_eq!(f[4].pos, Vec2::new(0.0, 16.0));
+        assert_eq!(f[7].size, Vec2::new(16.0, 16.0));
+        assert_eq_delta!(f[0].duration, 0.1);
+        assert_eq_delta!(grid_frames(8, 8, 1, 1, 0.0)[0].duration, 0.0);
+    }
+}
@@ -0,0 +1,207 @@
+// Oliver Berzs
+// https://github.com/oberzs/duku
+
+// TextureAtlas - many small images packed into few textures
+
+use std::collections::HashMap;
+
+use super::Texture;
+use crate::math::Vec2;
+use crate::resources::Handle;
+
+/// Many small images packed into few textures.
+///
+/// Images are looked up by name as sprites.
+/// Drawing sprites from the same atlas needs
+/// only a few texture slots.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use duku::Duku;
+/// # use duku::TextureAtlas;
+/// # let (mut d, _) = Duku::windowed(1, 1).unwrap();
+/// # let pixels = vec![255; 16 * 16 * 4];
+/// let builder = TextureAtlas::builder(1024).image("coin", pixels, 16, 16);
+/// let atlas = d.create_texture_atlas(builder).unwrap();
+///
+/// # d.draw(None, |t| {
+/// // when drawing
+/// t.sprite(&atlas, "coin", [0.0, 0.0]);
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct TextureAtlas {
+    pub(crate) textures: Vec<Handle<Texture>>,
+    sprites: HashMap<String, Sprite>,
+}
+
+/// Region of a texture atlas.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Sprite {
+    /// index of the atlas texture
+    pub texture: usize,
+    /// x position in pixels
+    pub x: u32,
+    /// y position in pixels
+    pub y: u32,
+    /// width in pixels
+    pub width: u32,
+    /// height in pixels
+    pub height: u32,
+}
+
+/// Texture atlas builder.
+///
+/// Collects images before packing them.
+#[derive(Debug, Clone)]
+pub struct TextureAtlasBuilder {
+    pub(crate) size: u32,
+    pub(crate) padding: u32,
+    pub(crate) images: Vec<AtlasImage>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct AtlasImage {
+    pub(crate) name: String,
+    pub(crate) data: Vec<u8>,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+}
+
+impl TextureAtlas {
+    /// Create builder for a texture atlas
+    ///
+    /// `size` is the width and height of
+    /// each atlas texture.
+    pub const fn builder(size: u32) -> TextureAtlasBuilder {
+        TextureAtlasBuilder {
+            size,
+            padding: 1,
+            images: vec![],
+        }
+    }
+
+    pub(crate) const fn new(
+        textures: Vec<Handle<Texture>>,
+        sprites: HashMap<String, Sprite>,
+    ) -> Self {
+        Self { textures, sprites }
+    }
+
+    /// Get sprite by name
+    pub fn sprite(&self, name: impl AsRef<str>) -> Option<Sprite> {
+        self.sprites.get(name.as_ref()).copied()
+    }
+
+    /// Get texture that the sprite is in
+    pub fn texture(&self, sprite: Sprite) -> &Handle<Texture> {
+        &self.textures[sprite.texture]
+    }
+
+    /// Get all atlas textures
+    pub fn textures(&self) -> &[Handle<Texture>] {
+        &self.textures
+    }
+
+    /// Get UV region of sprite
+    ///
+    /// Returns the top-left and bottom-right
+    /// UV coordinates.
+    pub fn uvs(&self, name: impl AsRef<str>) -> Option<(Vec2, Vec2)> {
+        let sprite = self.sprite(name)?;
+        let (tw, th) = {
+            let tex = self.textures[sprite.texture].read();
+            (tex.width() as f32, tex.height() as f32)
+        };
+
+        Some((
+            Vec2::new(sprite.x as f32 / tw, sprite.y as f32 / th),
+            Vec2::new(
+                (sprite.x + sprite.width) as f32 / tw,
+                (sprite.y + sprite.height) as f32 / th,
+            ),
+        ))
+    }
+}
+
+impl TextureAtlasBuilder {
+    /// Use padding between images in pixels
+    pub const fn padding(mut self, padding: u32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Add image from sRGBA bytes
+    pub fn image(mut self, name: impl AsRef<str>, data: Vec<u8>, width: u32, height: u32) -> Self {
+        self.images.push(AtlasImage {
+            name: name.as_ref().to_string(),
+            data,
+            width,
+            height,
+        });
+        self
+    }
+}
+
+// packs rectangles into square pages using shelves
+// returns page, x and y for each rectangle
+pub(crate) fn pack(
+    sizes: &[(u32, u32)],
+    size: u32,
+    padding: u32,
+) -> Option<Vec<(usize, u32, u32)>> {
+    // place tallest rectangles first
+    let mut order: Vec<_> = (0..sizes.len()).collect();
+    order.sort_by(|a, b| sizes[*b].1.cmp(&sizes[*a].1));
+
+    let mut result = vec![(0, 0, 0); sizes.len()];
+    let mut page = 0;
+    let mut x = padding;
+    let mut y = padding;
+    let mut shelf_height = 0;
+
+    for i in order {
+        let (w, h) = sizes[i];
+        if w + padding * 2 > size || h + padding * 2 > size {
+            return None;
+        }
+
+        // go to next shelf
+        if x + w + padding > size {
+            x = padding;
+            y += shelf_height + padding;
+            shelf_height = 0;
+        }
+
+        // go to next page
+        if y + h + padding > size {
+            page += 1;
+            x = padding;
+            y = padding;
+            shelf_height = 0;
+        }
+
+        result[i] = (page, x, y);
+        x += w + padding;
+        shelf_height = shelf_height.max(h);
+    }
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pack;
+
+    #[test]
+    fn pack_pages() {
+        let sizes = [(4, 4), (8, 8), (4, 2), (8, 8)];
+        let packed = pack(&sizes, 10, 1).expect("does not fit");
+        assert_eq!(packed[1], (0, 1, 1));
+        assert_eq!(packed[3], (1, 1, 1));
+        assert_eq!(packed[0], (2, 1, 1));
+        assert_eq!(packed[2], (2, 1, 6));
+        assert!(pack(&[(12, 1)], 10, 0).is_none());
+    }
+}
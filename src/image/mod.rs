@@ -1,6 +1,8 @@
 // Oliver Berzs
 // https://github.com/oberzs/duku
 
+mod animation;
+mod atlas;
 mod canvas;
 mod cubemap;
 mod properties;
@@ -16,9 +18,17 @@ use crate::pipeline::Attachment;
 use crate::vk;
 use properties::with_alpha;
 
+pub(crate) use atlas::pack;
+pub(crate) use properties::sampler_index;
 pub(crate) use properties::ImageLayout;
 pub(crate) use properties::ImageUsage;
 
+pub use animation::Animation;
+pub use animation::AnimationFrame;
+pub use animation::AnimationMode;
+pub use atlas::Sprite;
+pub use atlas::TextureAtlas;
+pub use atlas::TextureAtlasBuilder;
 pub use canvas::Canvas;
 pub use cubemap::Cubemap;
 pub use cubemap::CubemapSides;
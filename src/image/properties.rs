@@ -69,6 +69,8 @@ pub enum Wrap {
     ClampEdge,
     /// repeats the texture
     Repeat,
+    /// repeats the texture, mirroring every other repeat
+    MirroredRepeat,
 }
 
 /// Image's pixel format.
@@ -235,6 +237,7 @@ impl Wrap {
             Self::ClampBorder => vk::SAMPLER_ADDRESS_MODE_CLAMP_TO_BORDER,
             Self::ClampEdge => vk::SAMPLER_ADDRESS_MODE_CLAMP_TO_EDGE,
             Self::Repeat => vk::SAMPLER_ADDRESS_MODE_REPEAT,
+            Self::MirroredRepeat => vk::SAMPLER_ADDRESS_MODE_MIRRORED_REPEAT,
         }
     }
 }
@@ -255,6 +258,21 @@ impl Filter {
     }
 }
 
+// index of the filter and wrap combination
+// in the uniform sampler array
+pub(crate) const fn sampler_index(filter: Filter, wrap: Wrap) -> u32 {
+    match (filter, wrap) {
+        (Filter::Linear, Wrap::Repeat) => 0,
+        (Filter::Linear, Wrap::ClampBorder) => 1,
+        (Filter::Linear, Wrap::ClampEdge) => 2,
+        (Filter::Nearest, Wrap::Repeat) => 3,
+        (Filter::Nearest, Wrap::ClampBorder) => 4,
+        (Filter::Nearest, Wrap::ClampEdge) => 5,
+        (Filter::Linear, Wrap::MirroredRepeat) => 6,
+        (Filter::Nearest, Wrap::MirroredRepeat) => 7,
+    }
+}
+
 pub(crate) fn with_alpha(data: Vec<u8>) -> Vec<u8> {
     let mut new_data = Vec::with_capacity(4 * data.len() / 3);
     for pixel in data.chunks(3) {
@@ -1,16 +1,20 @@
 // Oliver Berzs
 // https://github.com/oberzs/duku
 
+use super::sampler_index;
 use super::with_alpha;
+use super::Filter;
 use super::Format;
 use super::Image;
 use super::ImageLayout;
 use super::Mips;
+use super::Wrap;
 use crate::buffer::Buffer;
 use crate::color::Rgb;
 use crate::device::Device;
 use crate::error::Result;
 use crate::pipeline::Uniforms;
+use crate::pipeline::MAX_TEXTURES;
 
 /// Image that can be sampled in a shader.
 ///
@@ -20,6 +24,7 @@ pub struct Texture {
     /// pixel data as bytes
     pub data: Vec<u8>,
     opaque: bool,
+    sampler: Option<(Filter, Wrap)>,
 
     image: Image,
     shader_index: u32,
@@ -70,6 +75,7 @@ impl Texture {
 
         Ok(Self {
             data: image_data,
+            sampler: None,
             opaque,
             image,
             shader_index,
@@ -110,6 +116,15 @@ impl Texture {
         }
     }
 
+    /// Set filter and wrap mode that materials use
+    /// when sampling this texture
+    ///
+    /// Can be set before or after the texture
+    /// is added to a material.
+    pub fn set_sampler(&mut self, filter: Filter, wrap: Wrap) {
+        self.sampler = Some((filter, wrap));
+    }
+
     /// Get a pixel's color in the image
     pub fn pixel(&self, x: u32, y: u32) -> Rgb {
         debug_assert!(matches!(self.image.format(), Format::Rgba | Format::Srgba));
@@ -146,6 +161,27 @@ impl Texture {
         staging_buffer.destroy(device);
     }
 
+    // shader index with the texture's sampler,
+    // used for material parameters
+    pub(crate) const fn material_index(&self) -> u32 {
+        match self.sampler {
+            Some((filter, wrap)) => {
+                self.shader_index + MAX_TEXTURES * (sampler_index(filter, wrap) + 1)
+            }
+            None => self.shader_index,
+        }
+    }
+
+    #[cfg(feature = "gltf")]
+    pub(crate) const fn sampler(&self) -> Option<(Filter, Wrap)> {
+        self.sampler
+    }
+
+    #[cfg(feature = "gltf")]
+    pub(crate) const fn format(&self) -> Format {
+        self.image.format()
+    }
+
     pub(crate) const fn opaque(&self) -> bool {
         self.opaque
     }
@@ -106,15 +106,31 @@ pub use self::duku::Duku;
 pub use self::duku::DukuBuilder;
 pub use color::Gradient;
 pub use color::Hsb;
+pub use color::LinearGradient;
 pub use color::Mix;
+pub use color::RadialGradient;
 pub use color::Rgb;
 pub use color::Rgbf;
+pub use color::ShapeGradient;
 pub use device::Stats;
 pub use error::Error;
 pub use error::Result;
 pub use font::CharData;
 pub use font::Font;
 pub use font::FontData;
+pub use font::LayoutGlyph;
+pub use font::Span;
+pub use font::TextAlign;
+pub use font::TextEdit;
+pub use font::TextLayout;
+pub use font::TextLine;
+pub use font::TextOverflow;
+pub use font::TextRect;
+pub use font::TextStyle;
+pub use font::TextWrap;
+pub use image::Animation;
+pub use image::AnimationFrame;
+pub use image::AnimationMode;
 pub use image::Canvas;
 pub use image::ColorSpace;
 pub use image::Cubemap;
@@ -123,18 +139,39 @@ pub use image::Filter;
 pub use image::Format;
 pub use image::Mips;
 pub use image::Msaa;
+pub use image::Sprite;
 pub use image::Texture;
+pub use image::TextureAtlas;
+pub use image::TextureAtlasBuilder;
 pub use image::Wrap;
 pub use math::Mat4;
 pub use math::Quat;
 pub use math::Vec2;
 pub use math::Vec3;
 pub use math::Vec4;
+pub use mesh::AnimationChannel;
+pub use mesh::AnimationClip;
+pub use mesh::AnimationPlayer;
+pub use mesh::ChannelValues;
+pub use mesh::Interpolation;
+pub use mesh::Joint;
 pub use mesh::Mesh;
 pub use mesh::Model;
 pub use mesh::ModelNode;
+pub use mesh::MorphTarget;
+pub use mesh::Pose;
+pub use mesh::Primitive;
+pub use mesh::Skin;
+pub use mesh::Tile;
+pub use mesh::TileRotation;
+pub use mesh::Tilemap;
+pub use mesh::Tileset;
+pub use pipeline::AlphaMode;
 pub use pipeline::Material;
 pub use pipeline::Shader;
+pub use pipeline::TextureSlot;
+pub use renderer::BillboardMode;
+pub use renderer::BillboardSize;
 pub use renderer::BorderMode;
 pub use renderer::Camera;
 pub use renderer::Light;
@@ -156,5 +193,7 @@ pub use features::glsl;
 pub use features::gltf;
 #[cfg(feature = "otf")]
 pub use features::otf;
+#[cfg(feature = "svg")]
+pub use features::svg;
 #[cfg(feature = "window")]
 pub use features::window;
@@ -83,6 +83,56 @@ impl Quat {
         result.w = -result.w;
         result
     }
+
+    /// Calculate the dot product of 2 quaternions
+    pub fn dot(self, other: Self) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+
+    /// Calculate the unit quaternion
+    pub fn unit(self) -> Self {
+        let length = self.dot(self).sqrt();
+        if length == 0.0 {
+            Self::default()
+        } else {
+            Self::new(
+                self.x / length,
+                self.y / length,
+                self.z / length,
+                self.w / length,
+            )
+        }
+    }
+
+    /// Spherically interpolate between 2 rotations
+    ///
+    /// `t` of 0 results in `self`, 1 in `other`
+    pub fn slerp(self, other: Self, t: f32) -> Self {
+        // take the shortest path
+        let mut cos = self.dot(other);
+        let mut end = other;
+        if cos < 0.0 {
+            cos = -cos;
+            end = Self::new(-other.x, -other.y, -other.z, -other.w);
+        }
+
+        // use linear interpolation for close rotations
+        let (a, b) = if cos > 0.9995 {
+            (1.0 - t, t)
+        } else {
+            let angle = cos.acos();
+            let sin = angle.sin();
+            (((1.0 - t) * angle).sin() / sin, (t * angle).sin() / sin)
+        };
+
+        Self::new(
+            self.x * a + end.x * b,
+            self.y * a + end.y * b,
+            self.z * a + end.z * b,
+            self.w * a + end.w * b,
+        )
+        .unit()
+    }
 }
 
 impl Default for Quat {
@@ -171,6 +221,22 @@ mod test {
         assert_eq!(Quat::default(), Quat::new(0.0, 0.0, 0.0, 1.0));
     }
 
+    #[test]
+    fn unit() {
+        let q = Quat::new(0.0, 2.0, 0.0, 0.0).unit();
+        assert_eq!(q, Quat::new(0.0, 1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn slerp() {
+        let a = Quat::default();
+        let b = Quat::axis_rotation([0.0, 1.0, 0.0], 90.0);
+        let r = a.slerp(b, 0.5) * Vec3::forward();
+        assert_eq_delta!(r.x, 0.707_106_77);
+        assert_eq_delta!(r.y, 0.0);
+        assert_eq_delta!(r.z, 0.707_106_77);
+    }
+
     #[test]
     fn axis_rotation() {
         let q = Quat::axis_rotation([1.0, 0.0, 0.0], 180.0);
@@ -152,6 +152,13 @@ impl Vec3 {
         o.unit() * projected_length
     }
 
+    /// Linearly interpolate between 2 vectors
+    ///
+    /// `t` of 0 results in `self`, 1 in `other`
+    pub fn lerp(&self, other: impl Into<Self>, t: f32) -> Self {
+        *self + (other.into() - *self) * t
+    }
+
     /// Get the [Vec2](crate::math::Vec2)
     /// made from this vectors x and y
     pub const fn xy(&self) -> Vec2 {
@@ -327,6 +334,16 @@ mod test {
         assert_eq_delta!(r.z, -3.0);
     }
 
+    #[test]
+    fn lerp() {
+        let a = Vec3::new(0.0, 2.0, 4.0);
+        let b = Vec3::new(2.0, 4.0, 8.0);
+        let r = a.lerp(b, 0.5);
+        assert_eq_delta!(r.x, 1.0);
+        assert_eq_delta!(r.y, 3.0);
+        assert_eq_delta!(r.z, 6.0);
+    }
+
     #[test]
     fn length() {
         let v = Vec3::new(3.0, 4.0, 0.0);
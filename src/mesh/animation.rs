@@ -0,0 +1,742 @@
+// Oliver Berzs
+// https://github.com/oberzs/duku
+
+// AnimationPlayer - keyframe clip sampling and blending
+
+use super::Model;
+use super::ModelNode;
+use super::Skin;
+use crate::math::Mat4;
+use crate::math::Quat;
+use crate::math::Vec3;
+use crate::resources::Handle;
+
+/// Keyframe animation of model nodes.
+///
+/// Usually loaded from a glTF file.
+#[derive(Debug, Clone)]
+pub struct AnimationClip {
+    /// name of the clip
+    pub name: String,
+    /// animated properties
+    pub channels: Vec<AnimationChannel>,
+    /// length of the clip in seconds
+    pub duration: f32,
+}
+
+/// Keyframes for one property of a node.
+#[derive(Debug, Clone)]
+pub struct AnimationChannel {
+    /// model node index that is animated
+    pub node: usize,
+    /// how values are interpolated between keyframes
+    pub interpolation: Interpolation,
+    /// keyframe times in seconds
+    pub times: Vec<f32>,
+    /// keyframe values
+    ///
+    /// Cubic spline channels store an in-tangent,
+    /// value and out-tangent for each keyframe.
+    pub values: ChannelValues,
+}
+
+/// Animated property values.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChannelValues {
+    /// node translations
+    Translation(Vec<Vec3>),
+    /// node rotations
+    Rotation(Vec<Quat>),
+    /// node scales
+    Scale(Vec<Vec3>),
+    /// morph target weights of the node's meshes,
+    /// one weight per target for each keyframe
+    MorphWeights(Vec<f32>),
+}
+
+/// Interpolation between keyframes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Interpolation {
+    /// keep value until next keyframe
+    Step,
+    /// linearly interpolate values
+    Linear,
+    /// interpolate values on a cubic spline
+    CubicSpline,
+}
+
+/// Local transform of a node or joint.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Pose {
+    /// translation part
+    pub translation: Vec3,
+    /// rotation part
+    pub rotation: Quat,
+    /// scale part
+    pub scale: Vec3,
+}
+
+/// Player for model animation clips.
+///
+/// Can blend between multiple clips.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use duku::Duku;
+/// # use duku::AnimationPlayer;
+/// # let (mut d, _) = Duku::windowed(1, 1).unwrap();
+/// # let model = d.create_model();
+/// let mut player = AnimationPlayer::new();
+/// player.play(0);
+///
+/// // every frame
+/// player.update(d.delta_time());
+/// player.apply(&mut model.write());
+/// d.draw(None, |t| {
+///     t.model(&model);
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct AnimationPlayer {
+    layers: Vec<Layer>,
+    speed: f32,
+    playing: bool,
+    looping: bool,
+}
+
+#[derive(Debug, Copy, Clone)]
+struct Layer {
+    clip: usize,
+    time: f32,
+    weight: f32,
+    // weight change per second
+    fade: f32,
+}
+
+trait Keyframe: Copy {
+    fn lerp(self, other: Self, t: f32) -> Self;
+
+    fn hermite(self, out_tangent: Self, other: Self, in_tangent: Self, t: f32, dt: f32) -> Self;
+}
+
+impl AnimationClip {
+    /// Sample the local transform of a node
+    ///
+    /// `rest` is used for properties that are
+    /// not animated by this clip.
+    pub fn sample(&self, node: usize, time: f32, rest: Pose) -> Pose {
+        let mut pose = rest;
+        for channel in self.channels.iter().filter(|c| c.node == node) {
+            let times = &channel.times;
+            let interpolation = channel.interpolation;
+            match &channel.values {
+                ChannelValues::Translation(values) => {
+                    if let Some(v) = sample(times, values, interpolation, time) {
+                        pose.translation = v;
+                    }
+                }
+                ChannelValues::Rotation(values) => {
+                    if let Some(v) = sample(times, values, interpolation, time) {
+                        pose.rotation = v;
+                    }
+                }
+                ChannelValues::Scale(values) => {
+                    if let Some(v) = sample(times, values, interpolation, time) {
+                        pose.scale = v;
+                    }
+                }
+                ChannelValues::MorphWeights(_) => (),
+            }
+        }
+        pose
+    }
+
+    /// Sample the morph target weights of a node
+    ///
+    /// Returns `None` if the clip does not
+    /// animate the weights of the node.
+    pub fn sample_weights(&self, node: usize, time: f32) -> Option<Vec<f32>> {
+        self.channels
+            .iter()
+            .filter(|c| c.node == node)
+            .find_map(|channel| match &channel.values {
+                ChannelValues::MorphWeights(values) => {
+                    let keyframes = channel.times.len().max(1);
+                    let stride = match channel.interpolation {
+                        Interpolation::CubicSpline => 3,
+                        _ => 1,
+                    };
+                    let count = values.len() / (keyframes * stride);
+
+                    // sample each target's weights separately
+                    (0..count)
+                        .map(|target| {
+                            let ws: Vec<_> =
+                                values.iter().skip(target).step_by(count).copied().collect();
+                            sample(&channel.times, &ws, channel.interpolation, time)
+                        })
+                        .collect()
+                }
+                _ => None,
+            })
+    }
+
+    /// Check if clip animates the transform of a node
+    pub fn animates(&self, node: usize) -> bool {
+        self.channels
+            .iter()
+            .any(|c| c.node == node && !matches!(c.values, ChannelValues::MorphWeights(_)))
+    }
+
+    /// Check if clip animates the morph target weights of a node
+    pub fn animates_weights(&self, node: usize) -> bool {
+        self.channels
+            .iter()
+            .any(|c| c.node == node && matches!(c.values, ChannelValues::MorphWeights(_)))
+    }
+}
+
+impl Pose {
+    /// Calculate the transform matrix
+    pub fn matrix(&self) -> Mat4 {
+        Mat4::compose(self.translation, self.scale, self.rotation)
+    }
+
+    /// Interpolate between 2 poses
+    pub fn blend(&self, other: Self, t: f32) -> Self {
+        Self {
+            translation: self.translation.lerp(other.translation, t),
+            rotation: self.rotation.slerp(other.rotation, t),
+            scale: self.scale.lerp(other.scale, t),
+        }
+    }
+}
+
+impl Default for Pose {
+    fn default() -> Self {
+        Self {
+            translation: Vec3::default(),
+            rotation: Quat::default(),
+            scale: Vec3::uniform(1.0),
+        }
+    }
+}
+
+impl AnimationPlayer {
+    /// Create player with no clips playing
+    pub const fn new() -> Self {
+        Self {
+            layers: vec![],
+            speed: 1.0,
+            playing: true,
+            looping: true,
+        }
+    }
+
+    /// Play clip from the start, stopping other clips
+    pub fn play(&mut self, clip: usize) {
+        self.playing = true;
+        self.layers = vec![Layer {
+            time: 0.0,
+            weight: 1.0,
+            fade: 0.0,
+            clip,
+        }];
+    }
+
+    /// Fade into clip over `duration` seconds,
+    /// fading out other clips
+    pub fn cross_fade(&mut self, clip: usize, duration: f32) {
+        if duration <= 0.0 {
+            self.play(clip);
+            return;
+        }
+
+        for layer in &mut self.layers {
+            layer.fade = -1.0 / duration;
+        }
+
+        match self.layers.iter_mut().find(|l| l.clip == clip) {
+            Some(layer) => layer.fade = 1.0 / duration,
+            None => self.layers.push(Layer {
+                time: 0.0,
+                weight: 0.0,
+                fade: 1.0 / duration,
+                clip,
+            }),
+        }
+    }
+
+    /// Set how much a clip contributes to the pose
+    ///
+    /// Starts playing the clip if it was not playing.
+    pub fn blend(&mut self, clip: usize, weight: f32) {
+        match self.layers.iter_mut().find(|l| l.clip == clip) {
+            Some(layer) => {
+                layer.weight = weight;
+                layer.fade = 0.0;
+            }
+            None => self.layers.push(Layer {
+                time: 0.0,
+                fade: 0.0,
+                weight,
+                clip,
+            }),
+        }
+    }
+
+    /// Stop playing all clips
+    pub fn stop(&mut self) {
+        self.layers.clear();
+    }
+
+    /// Pause all clips on the current time
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    /// Continue playing paused clips
+    pub fn resume(&mut self) {
+        self.playing = true;
+    }
+
+    /// Move all clips to time in seconds
+    pub fn seek(&mut self, time: f32) {
+        for layer in &mut self.layers {
+            layer.time = time;
+        }
+    }
+
+    /// Set whether clips repeat after reaching the end
+    pub fn looping(&mut self, looping: bool) {
+        self.looping = looping;
+    }
+
+    /// Set playback speed multiplier
+    pub fn speed(&mut self, speed: f32) {
+        self.speed = speed;
+    }
+
+    /// Check if player is paused
+    pub const fn is_paused(&self) -> bool {
+        !self.playing
+    }
+
+    /// Get the current time of a clip in seconds
+    pub fn time(&self, clip: usize) -> f32 {
+        self.layers
+            .iter()
+            .find(|l| l.clip == clip)
+            .map(|l| l.time)
+            .unwrap_or(0.0)
+    }
+
+    /// Get the weight of a clip
+    pub fn weight(&self, clip: usize) -> f32 {
+        self.layers
+            .iter()
+            .find(|l| l.clip == clip)
+            .map(|l| l.weight)
+            .unwrap_or(0.0)
+    }
+
+    /// Advance clips and fades by the time passed
+    pub fn update(&mut self, delta_time: f32) {
+        if !self.playing {
+            return;
+        }
+
+        for layer in &mut self.layers {
+            layer.time += delta_time * self.speed;
+            layer.weight += layer.fade * delta_time;
+
+            if layer.fade > 0.0 && layer.weight >= 1.0 {
+                layer.weight = 1.0;
+                layer.fade = 0.0;
+            }
+        }
+
+        // remove faded out clips
+        self.layers.retain(|l| l.fade >= 0.0 || l.weight > 0.0);
+    }
+
+    /// Sample the blended local transform of a node
+    pub fn sample(&self, clips: &[AnimationClip], node: usize, rest: Pose) -> Pose {
+        let mut pose = rest;
+        let mut total = 0.0;
+
+        for layer in &self.layers {
+            if let Some(clip) = clips.get(layer.clip) {
+                if layer.weight <= 0.0 {
+                    continue;
+                }
+
+                let time = self.clip_time(clip, layer.time);
+
+                total += layer.weight;
+                let sampled = clip.sample(node, time, rest);
+                pose = pose.blend(sampled, layer.weight / total);
+            }
+        }
+
+        pose
+    }
+
+    /// Sample the blended morph target weights of a node
+    ///
+    /// Clips that do not animate the weights
+    /// blend towards zero.
+    pub fn sample_weights(&self, clips: &[AnimationClip], node: usize) -> Vec<f32> {
+        let mut weights: Vec<f32> = vec![];
+        let mut total = 0.0;
+
+        for layer in &self.layers {
+            if let Some(clip) = clips.get(layer.clip) {
+                if layer.weight <= 0.0 {
+                    continue;
+                }
+
+                let time = self.clip_time(clip, layer.time);
+                let sampled = clip.sample_weights(node, time).unwrap_or_default();
+
+                total += layer.weight;
+                let t = layer.weight / total;
+                if weights.len() < sampled.len() {
+                    weights.resize(sampled.len(), 0.0);
+                }
+                for (i, w) in weights.iter_mut().enumerate() {
+                    let s = sampled.get(i).copied().unwrap_or(0.0);
+                    *w = Keyframe::lerp(*w, s, t);
+                }
+            }
+        }
+
+        weights
+    }
+
+    /// Pose model nodes and skins with the current clips
+    ///
+    /// Nodes that are not animated keep their pose.
+    pub fn apply(&self, model: &mut Model) {
+        self.apply_parts(&model.animations, &mut model.nodes, &model.skins);
+    }
+
+    pub(crate) fn apply_parts(
+        &self,
+        clips: &[AnimationClip],
+        nodes: &mut [ModelNode],
+        skins: &[Handle<Skin>],
+    ) {
+        if self.layers.is_empty() {
+            return;
+        }
+
+        for node in nodes {
+            self.apply_node(clips, node);
+        }
+
+        for skin in skins {
+            let mut s = skin.write();
+            for joint in &mut s.joints {
+                if self.animates(clips, joint.node) {
+                    joint.pose = self.sample(clips, joint.node, joint.rest);
+                }
+            }
+        }
+    }
+
+    fn apply_node(&self, clips: &[AnimationClip], node: &mut ModelNode) {
+        if self.animates(clips, node.index) {
+            node.pose = self.sample(clips, node.index, node.rest);
+        }
+        if self.animates_weights(clips, node.index) {
+            let weights = self.sample_weights(clips, node.index);
+            node.set_morph_weights(&weights);
+        }
+
+        for child in &mut node.children {
+            self.apply_node(clips, child);
+        }
+    }
+
+    fn animates(&self, clips: &[AnimationClip], node: usize) -> bool {
+        self.layers
+            .iter()
+            .filter_map(|l| clips.get(l.clip))
+            .any(|c| c.animates(node))
+    }
+
+    fn animates_weights(&self, clips: &[AnimationClip], node: usize) -> bool {
+        self.layers
+            .iter()
+            .filter_map(|l| clips.get(l.clip))
+            .any(|c| c.animates_weights(node))
+    }
+
+    fn clip_time(&self, clip: &AnimationClip, time: f32) -> f32 {
+        if clip.duration <= 0.0 {
+            0.0
+        } else if self.looping {
+            time.rem_euclid(clip.duration)
+        } else {
+            time.min(clip.duration)
+        }
+    }
+}
+
+impl Default for AnimationPlayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Keyframe for f32 {
+    fn lerp(self, other: Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+
+    fn hermite(self, out_tangent: Self, other: Self, in_tangent: Self, t: f32, dt: f32) -> Self {
+        let (a, b, c, d) = hermite_factors(t);
+        self * a + out_tangent * (b * dt) + other * c + in_tangent * (d * dt)
+    }
+}
+
+impl Keyframe for Vec3 {
+    fn lerp(self, other: Self, t: f32) -> Self {
+        Vec3::lerp(&self, other, t)
+    }
+
+    fn hermite(self, out_tangent: Self, other: Self, in_tangent: Self, t: f32, dt: f32) -> Self {
+        let (a, b, c, d) = hermite_factors(t);
+        self * a + out_tangent * (b * dt) + other * c + in_tangent * (d * dt)
+    }
+}
+
+impl Keyframe for Quat {
+    fn lerp(self, other: Self, t: f32) -> Self {
+        self.slerp(other, t)
+    }
+
+    fn hermite(self, out_tangent: Self, other: Self, in_tangent: Self, t: f32, dt: f32) -> Self {
+        let (a, b, c, d) = hermite_factors(t);
+        let (b, d) = (b * dt, d * dt);
+        Quat::new(
+            self.x * a + out_tangent.x * b + other.x * c + in_tangent.x * d,
+            self.y * a + out_tangent.y * b + other.y * c + in_tangent.y * d,
+            self.z * a + out_tangent.z * b + other.z * c + in_tangent.z * d,
+            self.w * a + out_tangent.w * b + other.w * c + in_tangent.w * d,
+        )
+        .unit()
+    }
+}
+
+fn sample<T: Keyframe>(
+    times: &[f32],
+    values: &[T],
+    interpolation: Interpolation,
+    time: f32,
+) -> Option<T> {
+    let cubic = interpolation == Interpolation::CubicSpline;
+    let stride = if cubic { 3 } else { 1 };
+    let offset = if cubic { 1 } else { 0 };
+    let value = |i: usize| values.get(i * stride + offset).copied();
+
+    if times.is_empty() {
+        return None;
+    }
+
+    // clamp to first and last keyframes
+    let last = times.len() - 1;
+    if time <= times[0] {
+        return value(0);
+    }
+    if time >= times[last] {
+        return value(last);
+    }
+
+    // find keyframes around time
+    let next = times.iter().position(|t| *t > time).unwrap_or(last);
+    let prev = next - 1;
+    let dt = times[next] - times[prev];
+    let t = if dt > 0.0 {
+        (time - times[prev]) / dt
+    } else {
+        0.0
+    };
+
+    match interpolation {
+        Interpolation::Step => value(prev),
+        Interpolation::Linear => Some(value(prev)?.lerp(value(next)?, t)),
+        Interpolation::CubicSpline => {
+            let out_tangent = values.get(prev * 3 + 2).copied()?;
+            let in_tangent = values.get(next * 3).copied()?;
+            Some(value(prev)?.hermite(out_tangent, value(next)?, in_tangent, t, dt))
+        }
+    }
+}
+
+fn hermite_factors(t: f32) -> (f32, f32, f32, f32) {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    (
+        2.0 * t3 - 3.0 * t2 + 1.0,
+        t3 - 2.0 * t2 + t,
+        -2.0 * t3 + 3.0 * t2,
+        t3 - t2,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::AnimationChannel;
+    use super::AnimationClip;
+    use super::AnimationPlayer;
+    use super::ChannelValues;
+    use super::Interpolation;
+    use super::Pose;
+    use crate::math::Vec3;
+
+    fn clip(interpolation: Interpolation, values: Vec<Vec3>) -> AnimationClip {
+        AnimationClip {
+            name: "move".to_string(),
+            channels: vec![AnimationChannel {
+                node: 0,
+                times: vec![0.0, 1.0],
+                values: ChannelValues::Translation(values),
+                interpolation,
+            }],
+            duration: 1.0,
+        }
+    }
+
+    #[test]
+    fn sample_step() {
+        let c = clip(
+            Interpolation::Step,
+            vec![Vec3::default(), Vec3::new(2.0, 0.0, 0.0)],
+        );
+        let pose = c.sample(0, 0.5, Pose::default());
+        assert_eq!(pose.translation, Vec3::default());
+    }
+
+    #[test]
+    fn sample_linear() {
+        let c = clip(
+            Interpolation::Linear,
+            vec![Vec3::default(), Vec3::new(2.0, 0.0, 0.0)],
+        );
+        let pose = c.sample(0, 0.25, Pose::default());
+        assert_eq_delta!(pose.translation.x, 0.5);
+    }
+
+    #[test]
+    fn sample_cubic_spline() {
+        let c = clip(
+            Interpolation::CubicSpline,
+            vec![
+                Vec3::default(),
+                Vec3::default(),
+                Vec3::default(),
+                Vec3::default(),
+                Vec3::new(2.0, 0.0, 0.0),
+                Vec3::default(),
+            ],
+        );
+        let pose = c.sample(0, 0.5, Pose::default());
+        assert_eq_delta!(pose.translation.x, 1.0);
+    }
+
+    #[test]
+    fn sample_other_node() {
+        let c = clip(
+            Interpolation::Linear,
+            vec![Vec3::default(), Vec3::new(2.0, 0.0, 0.0)],
+        );
+        let rest = Pose {
+            translation: Vec3::new(0.0, 5.0, 0.0),
+            ..Default::default()
+        };
+        assert_eq!(c.sample(1, 0.5, rest), rest);
+    }
+
+    #[test]
+    fn sample_weights() {
+        let c = AnimationClip {
+            name: "smile".to_string(),
+            channels: vec![AnimationChannel {
+                node: 0,
+                times: vec![0.0, 1.0],
+                values: ChannelValues::MorphWeights(vec![0.0, 1.0, 1.0, 0.0]),
+                interpolation: Interpolation::Linear,
+            }],
+            duration: 1.0,
+        };
+        let weights = c.sample_weights(0, 0.25).unwrap();
+        assert_eq!(weights.len(), 2);
+        assert_eq_delta!(weights[0], 0.25);
+        assert_eq_delta!(weights[1], 0.75);
+        assert!(c.sample_weights(1, 0.25).is_none());
+        assert!(!c.animates(0));
+    }
+
+    #[test]
+    fn blend_clips() {
+        let clips = [
+            clip(Interpolation::Step, vec![Vec3::default(), Vec3::default()]),
+            clip(
+                Interpolation::Step,
+                vec![Vec3::new(2.0, 0.0, 0.0), Vec3::new(2.0, 0.0, 0.0)],
+            ),
+        ];
+        let mut player = AnimationPlayer::new();
+        player.blend(0, 1.0);
+        player.blend(1, 1.0);
+        let pose = player.sample(&clips, 0, Pose::default());
+        assert_eq_delta!(pose.translation.x, 1.0);
+    }
+
+    #[test]
+    fn looping() {
+        let clips = [clip(
+            Interpolation::Linear,
+            vec![Vec3::default(), Vec3::new(2.0, 0.0, 0.0)],
+        )];
+        let mut player = AnimationPlayer::new();
+        player.play(0);
+        player.update(1.5);
+        let pose = player.sample(&clips, 0, Pose::default());
+        assert_eq_delta!(pose.translation.x, 1.0);
+
+        player.looping(false);
+        let pose = player.sample(&clips, 0, Pose::default());
+        assert_eq_delta!(pose.translation.x, 2.0);
+    }
+
+    #[test]
+    fn pause_and_seek() {
+        let mut player = AnimationPlayer::new();
+        player.play(0);
+        player.pause();
+        player.update(0.5);
+        assert_eq_delta!(player.time(0), 0.0);
+
+        player.seek(0.75);
+        player.resume();
+        player.update(0.25);
+        assert_eq_delta!(player.time(0), 1.0);
+    }
+
+    #[test]
+    fn cross_fade() {
+        let mut player = AnimationPlayer::new();
+        player.play(0);
+        player.cross_fade(1, 1.0);
+        player.update(0.25);
+        assert_eq_delta!(player.weight(0), 0.75);
+        assert_eq_delta!(player.weight(1), 0.25);
+        player.update(1.0);
+        assert_eq_delta!(player.weight(0), 0.0);
+        assert_eq_delta!(player.weight(1), 1.0);
+    }
+}
@@ -1,7 +1,10 @@
 // Oliver Berzs
 // https://github.com/oberzs/duku
 
+mod animation;
 mod model;
+mod skin;
+mod tilemap;
 mod vertex;
 
 use std::iter;
@@ -12,12 +15,26 @@ use crate::color::Rgbf;
 use crate::device::Device;
 use crate::math::Vec2;
 use crate::math::Vec3;
+use crate::math::Vec4;
 use crate::vk;
 
+pub(crate) use tilemap::chunk_count;
 pub(crate) use vertex::Vertex;
 
+pub use animation::AnimationChannel;
+pub use animation::AnimationClip;
+pub use animation::AnimationPlayer;
+pub use animation::ChannelValues;
+pub use animation::Interpolation;
+pub use animation::Pose;
 pub use model::Model;
 pub use model::ModelNode;
+pub use skin::Joint;
+pub use skin::Skin;
+pub use tilemap::Tile;
+pub use tilemap::TileRotation;
+pub use tilemap::Tilemap;
+pub use tilemap::Tileset;
 
 /// Shape collection for rendering
 ///
@@ -49,6 +66,9 @@ pub struct Mesh {
     pub vertices: Vec<Vec3>,
     /// vertex UV coordinates
     pub uvs: Vec<Vec2>,
+    /// second set of vertex UV coordinates,
+    /// used for lightmaps and similar textures
+    pub second_uvs: Vec<Vec2>,
     /// vertex normal directions
     pub normals: Vec<Vec3>,
     /// vertex tangent directions
@@ -57,14 +77,52 @@ pub struct Mesh {
     pub colors: Vec<Rgbf>,
     /// vertex texture indices
     pub textures: Vec<u32>,
+    /// vertex skin joint indices
+    pub joints: Vec<[u32; 4]>,
+    /// vertex skin joint weights
+    pub weights: Vec<Vec4>,
+    /// blend shapes for the vertices
+    pub morph_targets: Vec<MorphTarget>,
+    /// how much each blend shape is applied
+    pub morph_weights: Vec<f32>,
     /// vertex indices
     pub indices: Vec<u32>,
+    /// how the indices are assembled
+    pub primitive: Primitive,
 
     vertex_buffer: Buffer<Vertex>,
     index_buffer: Buffer<u32>,
     index_count: usize,
 }
 
+/// Primitive type of a mesh.
+///
+/// Decides how the mesh's indices are
+/// assembled when drawing.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Primitive {
+    /// every 3 indices form a triangle
+    Triangles,
+    /// every 2 indices form a line
+    Lines,
+    /// every index is a point
+    Points,
+}
+
+/// Blend shape of a mesh.
+///
+/// Stores offsets that are added to the
+/// mesh's vertices based on the morph weight.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MorphTarget {
+    /// vertex position offsets
+    pub positions: Vec<Vec3>,
+    /// vertex normal offsets
+    pub normals: Vec<Vec3>,
+    /// vertex tangent offsets
+    pub tangents: Vec<Vec3>,
+}
+
 impl Mesh {
     pub(crate) fn new(device: &Device) -> Self {
         let vertex_buffer = Buffer::dynamic(device, BufferUsage::Vertex, 1);
@@ -73,11 +131,17 @@ impl Mesh {
         Self {
             vertices: vec![Vec3::default(); 1],
             uvs: vec![Vec2::default(); 1],
+            second_uvs: vec![],
             normals: vec![Vec3::default(); 1],
             tangents: vec![Vec3::default(); 1],
             colors: vec![Rgbf::gray(1.0); 1],
             textures: vec![0; 1],
+            joints: vec![[0; 4]; 1],
+            weights: vec![Vec4::default(); 1],
+            morph_targets: vec![],
+            morph_weights: vec![],
             indices: vec![0; 3],
+            primitive: Primitive::Triangles,
             index_count: 3,
             vertex_buffer,
             index_buffer,
@@ -91,8 +155,11 @@ impl Mesh {
         let mut normals = vec![];
         let mut tangents = vec![];
         let mut uvs = vec![];
+        let mut second_uvs = vec![];
         let mut colors = vec![];
         let mut textures = vec![];
+        let mut joints = vec![];
+        let mut weights = vec![];
 
         for mesh in meshes {
             indices.extend(mesh.indices.iter().map(|t| t + offset));
@@ -100,8 +167,11 @@ impl Mesh {
             normals.extend(&mesh.normals);
             tangents.extend(&mesh.tangents);
             uvs.extend(&mesh.uvs);
+            second_uvs.extend(&mesh.second_uvs);
             colors.extend(&mesh.colors);
             textures.extend(&mesh.textures);
+            joints.extend(&mesh.joints);
+            weights.extend(&mesh.weights);
             offset = vertices.len() as u32;
         }
 
@@ -111,7 +181,10 @@ impl Mesh {
         result.tangents = tangents;
         result.colors = colors;
         result.uvs = uvs;
+        result.second_uvs = second_uvs;
         result.textures = textures;
+        result.joints = joints;
+        result.weights = weights;
         result.indices = indices;
         result.update(device);
         result
@@ -187,23 +260,65 @@ impl Mesh {
         }
     }
 
+    /// Calculate vertex positions, normals and tangents
+    /// with the morph targets applied
+    pub fn morphed(&self) -> (Vec<Vec3>, Vec<Vec3>, Vec<Vec3>) {
+        let mut positions = self.vertices.clone();
+        let mut normals = self.normals.clone();
+        let mut tangents = self.tangents.clone();
+
+        for (target, weight) in self.morph_targets.iter().zip(&self.morph_weights) {
+            if *weight == 0.0 {
+                continue;
+            }
+
+            add_offsets(&mut positions, &target.positions, *weight);
+            add_offsets(&mut normals, &target.normals, *weight);
+            add_offsets(&mut tangents, &target.tangents, *weight);
+        }
+
+        for normal in &mut normals {
+            *normal = normal.unit();
+        }
+
+        (positions, normals, tangents)
+    }
+
     pub(crate) fn update(&mut self, device: &Device) {
-        let vertices: Vec<_> = self
-            .vertices
+        // apply blend shapes
+        let morphed = if self.morph_weights.iter().any(|w| *w != 0.0) {
+            Some(self.morphed())
+        } else {
+            None
+        };
+        let (positions, normals, tangents) = match &morphed {
+            Some((p, n, t)) => (p, n, t),
+            None => (&self.vertices, &self.normals, &self.tangents),
+        };
+
+        let vertices: Vec<_> = positions
             .iter()
             .zip(self.uvs.iter().chain(iter::repeat(&Vec2::default())))
-            .zip(self.normals.iter().chain(iter::repeat(&Vec3::default())))
-            .zip(self.tangents.iter().chain(iter::repeat(&Vec3::default())))
+            .zip(normals.iter().chain(iter::repeat(&Vec3::default())))
+            .zip(tangents.iter().chain(iter::repeat(&Vec3::default())))
             .zip(self.colors.iter().chain(iter::repeat(&Rgbf::gray(1.0))))
             .zip(self.textures.iter().chain(iter::repeat(&0)))
-            .map(|(((((pos, uv), normal), tangent), col), tex)| Vertex {
-                in_local_position: *pos,
-                in_normal: *normal,
-                in_tangent: *tangent,
-                in_uv: *uv,
-                in_color: (*col).into(),
-                in_texture: *tex,
-            })
+            .zip(self.joints.iter().chain(iter::repeat(&[0; 4])))
+            .zip(self.weights.iter().chain(iter::repeat(&Vec4::default())))
+            .zip(self.second_uvs.iter().chain(iter::repeat(&Vec2::default())))
+            .map(
+                |((((((((pos, uv), normal), tangent), col), tex), joint), weight), uv2)| Vertex {
+                    in_local_position: *pos,
+                    in_normal: *normal,
+                    in_tangent: *tangent,
+                    in_uv: *uv,
+                    in_color: (*col).into(),
+                    in_texture: *tex,
+                    in_joints: *joint,
+                    in_weights: *weight,
+                    in_second_uv: *uv2,
+                },
+            )
             .collect();
 
         // resize buffers if needed
@@ -238,3 +353,9 @@ impl Mesh {
         self.index_buffer.destroy(device);
     }
 }
+
+fn add_offsets(values: &mut [Vec3], offsets: &[Vec3], weight: f32) {
+    for (value, offset) in values.iter_mut().zip(offsets) {
+        *value += *offset * weight;
+    }
+}
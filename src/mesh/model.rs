@@ -4,10 +4,16 @@
 use std::collections::HashSet;
 use std::slice::Iter;
 
+use super::AnimationClip;
+use super::AnimationPlayer;
 use super::Mesh;
+use super::Pose;
+use super::Skin;
 use crate::math::Mat4;
 use crate::pipeline::Descriptor;
 use crate::pipeline::Material;
+use crate::renderer::Camera;
+use crate::renderer::Light;
 use crate::resources::Handle;
 
 /// Collection of meshes and materials.
@@ -16,6 +22,12 @@ use crate::resources::Handle;
 pub struct Model {
     /// render-nodes of the model
     pub nodes: Vec<ModelNode>,
+    /// skins used by the nodes
+    pub skins: Vec<Handle<Skin>>,
+    /// animation clips for the nodes
+    pub animations: Vec<AnimationClip>,
+    /// player for the animation clips
+    pub player: AnimationPlayer,
 }
 
 /// One node of the model.
@@ -24,12 +36,32 @@ pub struct Model {
 /// child object.
 #[derive(Clone)]
 pub struct ModelNode {
+    /// name of the node in the model file
+    pub name: String,
+    /// name of the node's mesh in the model file
+    pub mesh_name: String,
     /// meshes for this node
     pub meshes: Vec<Handle<Mesh>>,
     /// materials for this node
     pub materials: Vec<Handle<Material>>,
-    /// transform in matrix form for this node
-    pub matrix: Mat4,
+    /// index of the node in the model file,
+    /// targeted by animation clips
+    pub index: usize,
+    /// current local transform
+    pub pose: Pose,
+    /// local transform when not animated
+    pub rest: Pose,
+    /// skin that deforms the meshes
+    pub skin: Option<Handle<Skin>>,
+    /// morph target weights for the meshes
+    pub morph_weights: Vec<f32>,
+    /// light attached to this node in local space
+    pub light: Option<Light>,
+    /// camera attached to this node in local space
+    pub camera: Option<Camera>,
+    /// whether the node and its children
+    /// are drawn
+    pub visible: bool,
     /// child nodes
     pub children: Vec<Self>,
 }
@@ -48,6 +80,57 @@ impl Model {
             .for_each(|n| n.fix_color_space(&mut fixed));
     }
 
+    /// find animation clip index by name
+    pub fn find_animation(&self, name: impl AsRef<str>) -> Option<usize> {
+        self.animations.iter().position(|a| a.name == name.as_ref())
+    }
+
+    /// play animation clip by name from the start
+    pub fn play_animation(&mut self, name: impl AsRef<str>) {
+        if let Some(clip) = self.find_animation(name) {
+            self.player.play(clip);
+        }
+    }
+
+    /// advance playing animation clips by the time
+    /// passed and pose the nodes
+    pub fn update_animation(&mut self, delta_time: f32) {
+        self.player.update(delta_time);
+        self.player
+            .apply_parts(&self.animations, &mut self.nodes, &self.skins);
+    }
+
+    /// get lights of all nodes transformed
+    /// by their world transforms
+    pub fn lights(&self) -> Vec<Light> {
+        let mut lights = vec![];
+        for node in &self.nodes {
+            node.collect_lights(Mat4::identity(), &mut lights);
+        }
+        lights
+    }
+
+    /// get cameras of all nodes positioned
+    /// by their world transforms
+    pub fn cameras(&self) -> Vec<Camera> {
+        let mut cameras = vec![];
+        for node in &self.nodes {
+            node.collect_cameras(Mat4::identity(), &mut cameras);
+        }
+        cameras
+    }
+
+    /// find node by its index in the model file
+    pub fn find_node_index(&mut self, index: usize) -> Option<&mut ModelNode> {
+        self.nodes.iter_mut().find_map(|n| n.find_index(index))
+    }
+
+    /// find node by its name in the model file
+    pub fn find_node(&mut self, name: impl AsRef<str>) -> Option<&mut ModelNode> {
+        let name = name.as_ref();
+        self.nodes.iter_mut().find_map(|n| n.find_name(name))
+    }
+
     /// iterate through all meshes in the model
     pub fn meshes(&self) -> impl Iterator<Item = &Handle<Mesh>> {
         self.nodes.iter().map(|node| node.meshes()).flatten()
@@ -60,6 +143,64 @@ impl Model {
 }
 
 impl ModelNode {
+    /// transform in matrix form for this node
+    pub fn matrix(&self) -> Mat4 {
+        self.pose.matrix()
+    }
+
+    /// set morph target weights for this node's meshes
+    ///
+    /// Meshes shared with other nodes get
+    /// the weights that were set last. Models
+    /// loaded from glTF give each node its own
+    /// copy of meshes with morph targets.
+    pub fn set_morph_weights(&mut self, weights: &[f32]) {
+        self.morph_weights = weights.to_vec();
+        for mesh in &self.meshes {
+            mesh.write().morph_weights = weights.to_vec();
+        }
+    }
+
+    fn collect_lights(&self, parent: Mat4, lights: &mut Vec<Light>) {
+        let matrix = parent * self.matrix();
+        if let Some(light) = &self.light {
+            lights.push(light.transformed(matrix));
+        }
+        for child in &self.children {
+            child.collect_lights(matrix, lights);
+        }
+    }
+
+    fn collect_cameras(&self, parent: Mat4, cameras: &mut Vec<Camera>) {
+        let matrix = parent * self.matrix();
+        if let Some(camera) = &self.camera {
+            let (position, _, rotation) = matrix.decompose();
+            let mut c = camera.clone();
+            c.position = position + rotation * camera.position;
+            c.rotation = rotation * camera.rotation;
+            cameras.push(c);
+        }
+        for child in &self.children {
+            child.collect_cameras(matrix, cameras);
+        }
+    }
+
+    fn find_index(&mut self, index: usize) -> Option<&mut Self> {
+        if self.index == index {
+            Some(self)
+        } else {
+            self.children.iter_mut().find_map(|c| c.find_index(index))
+        }
+    }
+
+    fn find_name(&mut self, name: &str) -> Option<&mut Self> {
+        if self.name == name {
+            Some(self)
+        } else {
+            self.children.iter_mut().find_map(|c| c.find_name(name))
+        }
+    }
+
     pub(crate) fn orders(&self) -> impl Iterator<Item = (&Handle<Mesh>, &Handle<Material>)> {
         self.meshes.iter().zip(self.materials.iter())
     }
@@ -144,3 +285,45 @@ fn clamp(value: f32, min: f32, max: f32) -> f32 {
         value
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::Model;
+    use super::ModelNode;
+    use crate::mesh::AnimationPlayer;
+    use crate::mesh::Pose;
+
+    fn node(name: &str, index: usize, children: Vec<ModelNode>) -> ModelNode {
+        ModelNode {
+            name: name.to_string(),
+            mesh_name: String::new(),
+            meshes: vec![],
+            materials: vec![],
+            index,
+            pose: Pose::default(),
+            rest: Pose::default(),
+            skin: None,
+            morph_weights: vec![],
+            light: None,
+            camera: None,
+            visible: true,
+            children,
+        }
+    }
+
+    #[test]
+    fn find_node() {
+        let wheel = node("Wheel_FL", 2, vec![]);
+        let body = node("Body", 1, vec![wheel]);
+        let mut model = Model {
+            nodes: vec![node("Root", 0, vec![]), body],
+            skins: vec![],
+            animations: vec![],
+            player: AnimationPlayer::default(),
+        };
+
+        let found = model.find_node("Wheel_FL").map(|n| n.index);
+        assert_eq!(found, Some(2));
+        assert!(model.find_node("Wheel_FR").is_none());
+    }
+}
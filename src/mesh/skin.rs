@@ -0,0 +1,199 @@
+// Oliver Berzs
+// https://github.com/oberzs/duku
+
+// Skin - joint hierarchy used to deform meshes
+
+use super::Pose;
+use crate::buffer::Buffer;
+use crate::buffer::BufferUsage;
+use crate::device::Device;
+use crate::error::Result;
+use crate::math::Mat4;
+use crate::pipeline::Descriptor;
+use crate::pipeline::ShaderSkin;
+use crate::pipeline::Uniforms;
+use crate::pipeline::MAX_JOINTS;
+
+/// Joint hierarchy for skinned meshes.
+///
+/// Meshes drawn with a skin are deformed by
+/// their vertex joints and weights.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use duku::Duku;
+/// # use duku::Joint;
+/// # let (mut duku, _) = Duku::windowed(1, 1).unwrap();
+/// # let mesh = duku.create_mesh();
+/// let skin = duku.create_skin().unwrap();
+/// skin.write().joints.push(Joint::new(0, None));
+///
+/// # duku.draw(None, |t| {
+/// // when drawing
+/// t.skin(&skin);
+/// t.mesh(&mesh);
+/// # });
+/// ```
+pub struct Skin {
+    /// joints of the skin, max 128
+    pub joints: Vec<Joint>,
+
+    descriptor: Descriptor,
+    buffer: Buffer<ShaderSkin>,
+}
+
+/// One joint of a skin.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Joint {
+    /// model node index that animations target
+    pub node: usize,
+    /// index of the parent joint in the skin
+    ///
+    /// Out of range parents and parent
+    /// cycles make the joint a root
+    pub parent: Option<usize>,
+    /// transform of the joint's parents that
+    /// are not part of the skin
+    pub root: Mat4,
+    /// transform from mesh space to the joint's
+    /// space in bind pose
+    pub inverse_bind: Mat4,
+    /// current local transform
+    pub pose: Pose,
+    /// local transform when not animated
+    pub rest: Pose,
+}
+
+impl Skin {
+    pub(crate) fn new(device: &Device, uniforms: &mut Uniforms) -> Result<Self> {
+        let buffer = Buffer::dynamic(device, BufferUsage::Uniform, 1);
+        let descriptor = uniforms.skin_set(device, &buffer)?;
+
+        let skin = Self {
+            joints: vec![],
+            buffer,
+            descriptor,
+        };
+        skin.update();
+        Ok(skin)
+    }
+
+    /// Calculate joint matrices of the current pose
+    ///
+    /// The matrices transform vertices from
+    /// bind pose to the posed mesh.
+    pub fn matrices(&self) -> Vec<Mat4> {
+        let mut globals = vec![None; self.joints.len()];
+        (0..self.joints.len())
+            .map(|i| global_matrix(&self.joints, i, &mut globals) * self.joints[i].inverse_bind)
+            .collect()
+    }
+
+    pub(crate) fn update(&self) {
+        let mut joints = [Mat4::identity(); MAX_JOINTS];
+        for (joint, matrix) in joints.iter_mut().zip(self.matrices()) {
+            *joint = matrix;
+        }
+        self.buffer.copy_from_data(&[ShaderSkin { joints }]);
+    }
+
+    pub(crate) const fn descriptor(&self) -> Descriptor {
+        self.descriptor
+    }
+
+    pub(crate) fn destroy(&self, device: &Device) {
+        self.buffer.destroy(device);
+    }
+}
+
+impl Joint {
+    /// Create joint in rest pose for model node
+    pub fn new(node: usize, parent: Option<usize>) -> Self {
+        Self {
+            root: Mat4::identity(),
+            inverse_bind: Mat4::identity(),
+            pose: Pose::default(),
+            rest: Pose::default(),
+            parent,
+            node,
+        }
+    }
+}
+
+impl PartialEq for Skin {
+    fn eq(&self, other: &Self) -> bool {
+        self.buffer == other.buffer
+    }
+}
+
+// joints with a missing parent or in a
+// parent cycle are treated as roots
+fn global_matrix(joints: &[Joint], index: usize, globals: &mut [Option<Mat4>]) -> Mat4 {
+    // walk up to a root, a calculated joint or a cycle
+    let mut chain = vec![];
+    let mut current = Some(index);
+    while let Some(i) = current {
+        if globals[i].is_some() || chain.contains(&i) {
+            break;
+        }
+        chain.push(i);
+        current = joints[i].parent.filter(|p| *p < joints.len());
+    }
+
+    let mut matrix = match current.and_then(|i| globals[i]) {
+        Some(m) => m,
+        None => joints[chain[chain.len() - 1]].root,
+    };
+    for i in chain.into_iter().rev() {
+        matrix *= joints[i].pose.matrix();
+        globals[i] = Some(matrix);
+    }
+    matrix
+}
+
+#[cfg(test)]
+mod test {
+    use super::global_matrix;
+    use super::Joint;
+    use crate::math::Mat4;
+    use crate::math::Vec3;
+
+    fn joint(parent: Option<usize>, x: f32) -> Joint {
+        let mut joint = Joint::new(0, parent);
+        joint.pose.translation = Vec3::new(x, 0.0, 0.0);
+        joint
+    }
+
+    fn globals(joints: &[Joint]) -> Vec<Mat4> {
+        let mut globals = vec![None; joints.len()];
+        (0..joints.len())
+            .map(|i| global_matrix(joints, i, &mut globals))
+            .collect()
+    }
+
+    #[test]
+    fn hierarchy() {
+        let joints = [joint(None, 1.0), joint(Some(0), 2.0), joint(Some(1), 3.0)];
+        let matrices = globals(&joints);
+        assert_eq_delta!(matrices[1].decompose().0.x, 3.0);
+        assert_eq_delta!(matrices[2].decompose().0.x, 6.0);
+    }
+
+    #[test]
+    fn invalid_parent() {
+        // out of range and self parents
+        let joints = [joint(Some(5), 1.0), joint(Some(1), 2.0)];
+        let matrices = globals(&joints);
+        assert_eq_delta!(matrices[0].decompose().0.x, 1.0);
+        assert_eq_delta!(matrices[1].decompose().0.x, 2.0);
+    }
+
+    #[test]
+    fn parent_cycle() {
+        let joints = [joint(Some(1), 1.0), joint(Some(0), 2.0)];
+        let matrices = globals(&joints);
+        assert_eq_delta!(matrices[0].decompose().0.x, 3.0);
+        assert_eq_delta!(matrices[1].decompose().0.x, 2.0);
+    }
+}
@@ -0,0 +1,405 @@
+// Oliver Berzs
+// https://github.com/oberzs/duku
+
+// Tilemap - grid of texture tiles rendered in chunks
+
+use super::Mesh;
+use crate::color::Rgbf;
+use crate::image;
+use crate::image::Filter;
+use crate::image::Texture;
+use crate::image::Wrap;
+use crate::math::Vec2;
+use crate::math::Vec3;
+use crate::resources::Handle;
+
+// tile count on each side of a chunk
+pub(crate) const CHUNK_SIZE: u32 = 32;
+
+/// Grid of tiles from a tileset texture.
+///
+/// Tiles are built into chunk meshes that are
+/// only rebuilt when their tiles change.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use duku::Duku;
+/// # use duku::Tile;
+/// # use duku::Tileset;
+/// # let (mut d, _) = Duku::windowed(1, 1).unwrap();
+/// # let texture = d.create_texture(vec![255; 4], duku::Format::Srgba, duku::Mips::Zero, 1, 1).unwrap();
+/// let tileset = Tileset::new(&texture, 16, 16);
+/// let tilemap = d.create_tilemap(tileset, 256, 256, 1);
+/// tilemap.write().set_tile(0, 5, 5, Tile::new(3));
+///
+/// # d.draw(None, |t| {
+/// // when drawing
+/// t.tilemap(&tilemap, [0.0, 0.0]);
+/// # });
+/// ```
+pub struct Tilemap {
+    tileset: Tileset,
+    width: u32,
+    height: u32,
+    filter: Filter,
+    layers: Vec<TilemapLayer>,
+}
+
+/// Texture split into tiles.
+#[derive(Debug, Clone)]
+pub struct Tileset {
+    /// texture containing the tiles
+    pub texture: Handle<Texture>,
+    /// width of a tile in pixels
+    pub tile_width: u32,
+    /// height of a tile in pixels
+    pub tile_height: u32,
+    /// space around the tiles in pixels
+    pub margin: u32,
+    /// space between the tiles in pixels
+    pub spacing: u32,
+}
+
+/// One tile in a tilemap.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Tile {
+    /// index of the tile in the tileset
+    ///
+    /// Counts from left to right, top to bottom
+    pub index: u32,
+    /// flip tile horizontally after rotating
+    pub flip_x: bool,
+    /// flip tile vertically after rotating
+    pub flip_y: bool,
+    /// clockwise rotation of the tile
+    pub rotation: TileRotation,
+}
+
+/// Clockwise rotation of a tile.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TileRotation {
+    /// no rotation
+    None,
+    /// rotated by 90 degrees
+    Deg90,
+    /// rotated by 180 degrees
+    Deg180,
+    /// rotated by 270 degrees
+    Deg270,
+}
+
+pub(crate) struct TilemapLayer {
+    tiles: Vec<Option<Tile>>,
+    chunks: Vec<Chunk>,
+    visible: bool,
+}
+
+pub(crate) struct Chunk {
+    pub(crate) mesh: Handle<Mesh>,
+    pub(crate) empty: bool,
+    dirty: bool,
+}
+
+impl Tilemap {
+    pub(crate) fn new(
+        tileset: Tileset,
+        width: u32,
+        height: u32,
+        chunk_meshes: Vec<Vec<Handle<Mesh>>>,
+    ) -> Self {
+        let layers = chunk_meshes
+            .into_iter()
+            .map(|meshes| TilemapLayer {
+                tiles: vec![None; (width * height) as usize],
+                chunks: meshes
+                    .into_iter()
+                    .map(|mesh| Chunk {
+                        empty: true,
+                        dirty: false,
+                        mesh,
+                    })
+                    .collect(),
+                visible: true,
+            })
+            .collect();
+
+        Self {
+            filter: Filter::Nearest,
+            tileset,
+            width,
+            height,
+            layers,
+        }
+    }
+
+    /// Set tile at position in layer
+    ///
+    /// Position counts from the top-left corner.
+    /// Positions outside of the tilemap are ignored.
+    pub fn set_tile(&mut self, layer: usize, x: u32, y: u32, tile: impl Into<Option<Tile>>) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+
+        let chunk = self.chunk_index(x, y);
+        let index = (x + y * self.width) as usize;
+        if let Some(l) = self.layers.get_mut(layer) {
+            l.tiles[index] = tile.into();
+            l.chunks[chunk].dirty = true;
+        }
+    }
+
+    /// Remove tile at position in layer
+    pub fn clear_tile(&mut self, layer: usize, x: u32, y: u32) {
+        self.set_tile(layer, x, y, None);
+    }
+
+    /// Get tile at position in layer
+    pub fn tile(&self, layer: usize, x: u32, y: u32) -> Option<Tile> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        let index = (x + y * self.width) as usize;
+        self.layers.get(layer).and_then(|l| l.tiles[index])
+    }
+
+    /// Show or hide layer
+    pub fn set_layer_visible(&mut self, layer: usize, visible: bool) {
+        if let Some(l) = self.layers.get_mut(layer) {
+            l.visible = visible;
+        }
+    }
+
+    /// Set filtering used for the tileset texture
+    ///
+    /// Default is [Nearest](crate::image::Filter::Nearest)
+    pub fn filter(&mut self, filter: Filter) {
+        self.filter = filter;
+        for layer in &mut self.layers {
+            for chunk in &mut layer.chunks {
+                chunk.dirty = true;
+            }
+        }
+    }
+
+    /// Get width in tiles
+    pub const fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Get height in tiles
+    pub const fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Get layer count
+    pub fn layer_count(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Get the tileset
+    pub const fn tileset(&self) -> &Tileset {
+        &self.tileset
+    }
+
+    /// Get size in pixels
+    pub fn size(&self) -> Vec2 {
+        Vec2::new(
+            (self.width * self.tileset.tile_width) as f32,
+            (self.height * self.tileset.tile_height) as f32,
+        )
+    }
+
+    pub(crate) fn visible_chunks(&self) -> impl Iterator<Item = &Chunk> {
+        self.layers
+            .iter()
+            .filter(|l| l.visible)
+            .flat_map(|l| l.chunks.iter())
+            .filter(|c| !c.empty)
+    }
+
+    pub(crate) fn update(&mut self) {
+        let (tex_width, tex_height, texture) = {
+            let tex = self.tileset.texture.read();
+            (tex.width(), tex.height(), tex.shader_index())
+        };
+        let sampler = image::sampler_index(self.filter, Wrap::ClampEdge) as f32;
+        let tw = self.tileset.tile_width;
+        let th = self.tileset.tile_height;
+        let margin = self.tileset.margin;
+        let spacing = self.tileset.spacing;
+        let columns = (tex_width.saturating_sub(margin * 2) + spacing) / (tw + spacing).max(1);
+        let rows = (tex_height.saturating_sub(margin * 2) + spacing) / (th + spacing).max(1);
+
+        let chunk_columns = chunk_count(self.width);
+        let (width, height) = (self.width, self.height);
+
+        for layer in &mut self.layers {
+            for (c, chunk) in layer.chunks.iter_mut().enumerate() {
+                if !chunk.dirty {
+                    continue;
+                }
+                chunk.dirty = false;
+
+                let cx = (c as u32 % chunk_columns) * CHUNK_SIZE;
+                let cy = (c as u32 / chunk_columns) * CHUNK_SIZE;
+
+                let mut vertices = vec![];
+                let mut uvs = vec![];
+                let mut indices = vec![];
+
+                for y in cy..(cy + CHUNK_SIZE).min(height) {
+                    for x in cx..(cx + CHUNK_SIZE).min(width) {
+                        let tile = match layer.tiles[(x + y * width) as usize] {
+                            Some(t) if t.index < columns * rows => t,
+                            _ => continue,
+                        };
+
+                        // tile position with y going up
+                        let left = (x * tw) as f32;
+                        let bottom = ((height - y - 1) * th) as f32;
+                        let right = left + tw as f32;
+                        let top = bottom + th as f32;
+
+                        // tile part in texture
+                        let px = margin + (tile.index % columns) * (tw + spacing);
+                        let py = margin + (tile.index / columns) * (th + spacing);
+                        let u0 = px as f32 / tex_width as f32;
+                        let v0 = py as f32 / tex_height as f32;
+                        let u1 = (px + tw) as f32 / tex_width as f32;
+                        let v1 = (py + th) as f32 / tex_height as f32;
+
+                        let o = vertices.len() as u32;
+                        vertices.extend(&[
+                            Vec3::new(left, top, 0.0),
+                            Vec3::new(right, top, 0.0),
+                            Vec3::new(right, bottom, 0.0),
+                            Vec3::new(left, bottom, 0.0),
+                        ]);
+                        uvs.extend(&tile.corners([
+                            Vec2::new(u0, v0),
+                            Vec2::new(u1, v0),
+                            Vec2::new(u1, v1),
+                            Vec2::new(u0, v1),
+                        ]));
+                        indices.extend(&[o, o + 1, o + 2, o, o + 2, o + 3]);
+                    }
+                }
+
+                chunk.empty = indices.is_empty();
+                if !chunk.empty {
+                    let count = vertices.len();
+                    let mut mesh = chunk.mesh.write();
+                    mesh.vertices = vertices;
+                    mesh.uvs = uvs;
+                    mesh.indices = indices;
+                    mesh.colors = vec![Rgbf::gray(1.0); count];
+                    mesh.textures = vec![texture; count];
+                    // use normal to store sampler
+                    mesh.normals = vec![Vec3::new(sampler, 0.0, 0.0); count];
+                }
+            }
+        }
+    }
+
+    const fn chunk_index(&self, x: u32, y: u32) -> usize {
+        ((x / CHUNK_SIZE) + (y / CHUNK_SIZE) * chunk_count(self.width)) as usize
+    }
+}
+
+impl Tileset {
+    /// Create tileset with tiles of the given size
+    pub fn new(texture: &Handle<Texture>, tile_width: u32, tile_height: u32) -> Self {
+        Self {
+            texture: texture.clone(),
+            margin: 0,
+            spacing: 0,
+            tile_width,
+            tile_height,
+        }
+    }
+}
+
+impl Tile {
+    /// Create unflipped and unrotated tile
+    pub const fn new(index: u32) -> Self {
+        Self {
+            index,
+            flip_x: false,
+            flip_y: false,
+            rotation: TileRotation::None,
+        }
+    }
+
+    // reorders top-left, top-right, bottom-right
+    // and bottom-left corners with the tile transform
+    const fn corners(&self, c: [Vec2; 4]) -> [Vec2; 4] {
+        let turns = match self.rotation {
+            TileRotation::None => 0,
+            TileRotation::Deg90 => 1,
+            TileRotation::Deg180 => 2,
+            TileRotation::Deg270 => 3,
+        };
+        let mut r = [
+            c[(4 - turns) % 4],
+            c[(5 - turns) % 4],
+            c[(6 - turns) % 4],
+            c[(7 - turns) % 4],
+        ];
+        if self.flip_x {
+            r = [r[1], r[0], r[3], r[2]];
+        }
+        if self.flip_y {
+            r = [r[3], r[2], r[1], r[0]];
+        }
+        r
+    }
+}
+
+impl From<u32> for Tile {
+    fn from(index: u32) -> Self {
+        Self::new(index)
+    }
+}
+
+pub(crate) const fn chunk_count(tiles: u32) -> u32 {
+    match tiles % CHUNK_SIZE {
+        0 => tiles / CHUNK_SIZE,
+        _ => tiles / CHUNK_SIZE + 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Tile;
+    use super::TileRotation;
+    use crate::math::Vec2;
+
+    const CORNERS: [Vec2; 4] = [
+        Vec2::new(0.0, 0.0),
+        Vec2::new(1.0, 0.0),
+        Vec2::new(1.0, 1.0),
+        Vec2::new(0.0, 1.0),
+    ];
+
+    #[test]
+    fn corners_rotated() {
+        let mut tile = Tile::new(0);
+        tile.rotation = TileRotation::Deg90;
+        let c = tile.corners(CORNERS);
+        assert_eq!(c[0], CORNERS[3]);
+        assert_eq!(c[1], CORNERS[0]);
+    }
+
+    #[test]
+    fn corners_flipped() {
+        let mut tile = Tile::new(0);
+        tile.flip_x = true;
+        tile.flip_y = true;
+        let c = tile.corners(CORNERS);
+        assert_eq!(c[0], CORNERS[2]);
+        assert_eq!(c[3], CORNERS[1]);
+    }
+}
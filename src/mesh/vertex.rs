@@ -19,6 +19,9 @@ pub(crate) struct Vertex {
     pub(crate) in_uv: Vec2,
     pub(crate) in_color: Vec4,
     pub(crate) in_texture: u32,
+    pub(crate) in_joints: [u32; 4],
+    pub(crate) in_weights: Vec4,
+    pub(crate) in_second_uv: Vec2,
 }
 
 impl Vertex {
@@ -30,14 +33,17 @@ impl Vertex {
         }
     }
 
-    pub(crate) const fn attribute_descriptions() -> [vk::VertexInputAttributeDescription; 6] {
-        let mut offsets = [0; 6];
+    pub(crate) const fn attribute_descriptions() -> [vk::VertexInputAttributeDescription; 9] {
+        let mut offsets = [0; 9];
         offsets[0] = 0;
         offsets[1] = offsets[0] + mem::size_of::<Vec3>() as u32;
         offsets[2] = offsets[1] + mem::size_of::<Vec3>() as u32;
         offsets[3] = offsets[2] + mem::size_of::<Vec3>() as u32;
         offsets[4] = offsets[3] + mem::size_of::<Vec2>() as u32;
         offsets[5] = offsets[4] + mem::size_of::<Vec4>() as u32;
+        offsets[6] = offsets[5] + mem::size_of::<u32>() as u32;
+        offsets[7] = offsets[6] + mem::size_of::<[u32; 4]>() as u32;
+        offsets[8] = offsets[7] + mem::size_of::<Vec4>() as u32;
 
         [
             // in_local_position
@@ -82,6 +88,27 @@ impl Vertex {
                 format: vk::FORMAT_R32_UINT,
                 offset: offsets[5],
             },
+            // in_joints
+            vk::VertexInputAttributeDescription {
+                location: 6,
+                binding: 0,
+                format: vk::FORMAT_R32G32B32A32_UINT,
+                offset: offsets[6],
+            },
+            // in_weights
+            vk::VertexInputAttributeDescription {
+                location: 7,
+                binding: 0,
+                format: vk::FORMAT_R32G32B32A32_SFLOAT,
+                offset: offsets[7],
+            },
+            // in_second_uv
+            vk::VertexInputAttributeDescription {
+                location: 8,
+                binding: 0,
+                format: vk::FORMAT_R32G32_SFLOAT,
+                offset: offsets[8],
+            },
         ]
     }
 }
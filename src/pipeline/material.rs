@@ -3,7 +3,9 @@
 
 use super::Descriptor;
 use super::ShaderMaterial;
+use super::ShaderUvTransform;
 use super::Uniforms;
+use super::TEXTURE_SLOTS;
 use crate::buffer::Buffer;
 use crate::buffer::BufferUsage;
 use crate::color::Rgbf;
@@ -11,6 +13,7 @@ use crate::device::Device;
 use crate::error::Result;
 use crate::image::Canvas;
 use crate::image::Texture;
+use crate::math::Vec2;
 use crate::math::Vec4;
 use crate::resources::Handle;
 
@@ -51,10 +54,43 @@ pub struct Material {
     /// in the material
     pub textures: Vec<Handle<Texture>>,
 
+    pub(crate) alpha: AlphaMode,
+    pub(crate) double_sided: bool,
+    pub(crate) unshaded: bool,
+
+    slots: Vec<(TextureSlot, Handle<Texture>)>,
+    transforms: [ShaderUvTransform; TEXTURE_SLOTS],
     descriptor: Descriptor,
     buffer: Buffer<ShaderMaterial>,
 }
 
+/// How the alpha of a material is used.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum AlphaMode {
+    /// alpha is ignored
+    Opaque,
+    /// fragments with alpha below the
+    /// cutoff are discarded
+    Mask(f32),
+    /// alpha is blended with the background
+    Blend,
+}
+
+/// Texture slot of the PBR material.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TextureSlot {
+    /// albedo texture
+    Albedo,
+    /// metalness-roughness texture
+    MetalnessRoughness,
+    /// ambient occlusion texture
+    AmbientOcclusion,
+    /// normal texture
+    Normal,
+    /// emissive texture
+    Emissive,
+}
+
 impl Material {
     pub(crate) fn new(device: &Device, uniforms: &mut Uniforms) -> Result<Self> {
         let buffer = Buffer::dynamic(device, BufferUsage::Uniform, 1);
@@ -64,12 +100,18 @@ impl Material {
             a: Vec4::default(),
             b: Vec4::default(),
             c: Vec4::default(),
-            d: Vec4::default(),
+            // emissive strength
+            d: Vec4::new(0.0, 0.0, 0.0, 1.0),
             e: Vec4::default(),
             f: Vec4::default(),
             g: Vec4::default(),
             h: Vec4::default(),
             textures: vec![],
+            slots: vec![],
+            transforms: Default::default(),
+            alpha: AlphaMode::Opaque,
+            double_sided: false,
+            unshaded: false,
             buffer,
             descriptor,
         })
@@ -84,12 +126,12 @@ impl Material {
 
     /// Set albedo texture for the PBR and other various shaders
     pub fn albedo_texture(&mut self, texture: Handle<Texture>) {
-        self.a[3] = texture.read().shader_index() as f32;
-        self.textures.push(texture);
+        self.slot_texture(TextureSlot::Albedo, texture);
     }
 
     /// Set albedo canvas for the PBR and other various shaders
     pub fn albedo_canvas(&mut self, f: &Handle<Canvas>) {
+        self.slots.retain(|(s, _)| *s != TextureSlot::Albedo);
         self.a[3] = f.read().shader_index() as f32;
     }
 
@@ -110,31 +152,165 @@ impl Material {
         self.d[3] = temp;
     }
 
+    /// Set emissive strength for the PBR shader
+    ///
+    /// Multiplies the emissive color, allowing
+    /// values above 1. Defaults to 1.
+    pub fn emissive_strength(&mut self, value: f32) {
+        self.d[3] = value;
+    }
+
+    /// Set clearcoat layer factor and roughness
+    /// for the PBR shader
+    pub fn clearcoat(&mut self, factor: f32, roughness: f32) {
+        self.e[1] = factor;
+        self.e[2] = roughness;
+    }
+
+    /// Set UV offset, rotation in radians and scale
+    /// for a texture slot in the PBR and unshaded shaders
+    ///
+    /// Scale of 0 disables the transform.
+    pub fn texture_transform(
+        &mut self,
+        slot: TextureSlot,
+        offset: impl Into<Vec2>,
+        rotation: f32,
+        scale: impl Into<Vec2>,
+    ) {
+        self.transforms[slot.index()] = ShaderUvTransform {
+            offset: offset.into(),
+            scale: scale.into(),
+            rotation,
+            padding: [0.0; 3],
+        };
+    }
+
     /// Set metalness-roughness texture for the PBR shader
     pub fn metalness_roughness_texture(&mut self, texture: Handle<Texture>) {
-        self.b[2] = texture.read().shader_index() as f32;
-        self.textures.push(texture);
+        self.slot_texture(TextureSlot::MetalnessRoughness, texture);
     }
 
     /// Set ambient occlusion texture for the PBR shader
     pub fn ambient_occlusion_texture(&mut self, texture: Handle<Texture>) {
-        self.b[3] = texture.read().shader_index() as f32;
-        self.textures.push(texture);
+        self.slot_texture(TextureSlot::AmbientOcclusion, texture);
     }
 
     /// Set normal texture for the PBR shader
     pub fn normal_texture(&mut self, texture: Handle<Texture>) {
-        self.c[0] = texture.read().shader_index() as f32;
-        self.textures.push(texture);
+        self.slot_texture(TextureSlot::Normal, texture);
     }
 
     /// Set emissive texture for the PBR shader
     pub fn emissive_texture(&mut self, texture: Handle<Texture>) {
-        self.c[1] = texture.read().shader_index() as f32;
+        self.slot_texture(TextureSlot::Emissive, texture);
+    }
+
+    /// Set alpha mode for the PBR and unshaded shaders
+    ///
+    /// Blended materials are drawn after opaque ones
+    /// without writing to the depth buffer.
+    pub fn alpha_mode(&mut self, mode: AlphaMode) {
+        let (value, cutoff) = match mode {
+            AlphaMode::Opaque => (0.0, 0.0),
+            AlphaMode::Mask(cutoff) => (1.0, cutoff),
+            AlphaMode::Blend => (2.0, 0.0),
+        };
+        self.c[2] = cutoff;
+        self.c[3] = value;
+        self.alpha = mode;
+    }
+
+    /// Set which UV set a texture is sampled with
+    /// in the PBR shader
+    ///
+    /// Set 0 uses the mesh's `uvs`, set 1
+    /// uses the mesh's `second_uvs`.
+    pub fn uv_set(&mut self, slot: TextureSlot, set: u32) {
+        let bit = slot.bit();
+        let mut mask = self.e[0] as u32;
+        if set == 0 {
+            mask &= !bit;
+        } else {
+            mask |= bit;
+        }
+        self.e[0] = mask as f32;
+    }
+
+    #[cfg(feature = "gltf")]
+    pub(crate) fn slot_textures(&self) -> impl Iterator<Item = (TextureSlot, &Handle<Texture>)> {
+        self.slots.iter().map(|(slot, tex)| (*slot, tex))
+    }
+
+    #[cfg(feature = "gltf")]
+    pub(crate) fn uv_set_of(&self, slot: TextureSlot) -> u32 {
+        if self.e[0] as u32 & slot.bit() == 0 {
+            0
+        } else {
+            1
+        }
+    }
+
+    /// Set whether back faces are drawn
+    pub fn double_sided(&mut self, value: bool) {
+        self.double_sided = value;
+    }
+
+    /// Set whether meshes are drawn with the
+    /// unshaded shader, unless a shader is set
+    pub fn unshaded(&mut self, value: bool) {
+        self.unshaded = value;
+    }
+
+    // textures are stored with their slots so
+    // samplers can be set in any order
+    fn slot_texture(&mut self, slot: TextureSlot, texture: Handle<Texture>) {
+        self.set_param(slot, texture.read().material_index());
+        self.slots.retain(|(s, _)| *s != slot);
+        self.slots.push((slot, texture.clone()));
         self.textures.push(texture);
     }
 
+    fn param(&self, slot: TextureSlot) -> u32 {
+        let value = match slot {
+            TextureSlot::Albedo => self.a[3],
+            TextureSlot::MetalnessRoughness => self.b[2],
+            TextureSlot::AmbientOcclusion => self.b[3],
+            TextureSlot::Normal => self.c[0],
+            TextureSlot::Emissive => self.c[1],
+        };
+        value as u32
+    }
+
+    fn set_param(&mut self, slot: TextureSlot, index: u32) {
+        let value = index as f32;
+        match slot {
+            TextureSlot::Albedo => self.a[3] = value,
+            TextureSlot::MetalnessRoughness => self.b[2] = value,
+            TextureSlot::AmbientOcclusion => self.b[3] = value,
+            TextureSlot::Normal => self.c[0] = value,
+            TextureSlot::Emissive => self.c[1] = value,
+        }
+    }
+
+    // checks if a texture's sampler changed
+    // since the material was last updated
+    pub(crate) fn samplers_changed(&self) -> bool {
+        self.slots
+            .iter()
+            .any(|(slot, tex)| self.param(*slot) != tex.read().material_index())
+    }
+
     pub(crate) fn update(&mut self) {
+        let indices: Vec<_> = self
+            .slots
+            .iter()
+            .map(|(slot, tex)| (*slot, tex.read().material_index()))
+            .collect();
+        for (slot, index) in indices {
+            self.set_param(slot, index);
+        }
+
         self.buffer.copy_from_data(&[ShaderMaterial {
             a: self.a,
             b: self.b,
@@ -144,6 +320,7 @@ impl Material {
             f: self.f,
             g: self.g,
             h: self.h,
+            transforms: self.transforms,
         }]);
     }
 
@@ -156,6 +333,24 @@ impl Material {
     }
 }
 
+impl TextureSlot {
+    // index in the material's texture transforms
+    const fn index(&self) -> usize {
+        match *self {
+            Self::Albedo => 0,
+            Self::MetalnessRoughness => 1,
+            Self::AmbientOcclusion => 2,
+            Self::Normal => 3,
+            Self::Emissive => 4,
+        }
+    }
+
+    // bit in the material's UV set mask
+    const fn bit(&self) -> u32 {
+        1 << self.index()
+    }
+}
+
 impl PartialEq for Material {
     fn eq(&self, other: &Self) -> bool {
         self.buffer == other.buffer
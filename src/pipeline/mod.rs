@@ -23,8 +23,15 @@ pub(crate) use uniforms::Descriptor;
 pub(crate) use uniforms::ShaderConstants;
 pub(crate) use uniforms::ShaderLight;
 pub(crate) use uniforms::ShaderMaterial;
+pub(crate) use uniforms::ShaderSkin;
+pub(crate) use uniforms::ShaderUvTransform;
 pub(crate) use uniforms::ShaderWorld;
 pub(crate) use uniforms::Uniforms;
+pub(crate) use uniforms::MAX_JOINTS;
+pub(crate) use uniforms::MAX_TEXTURES;
+pub(crate) use uniforms::TEXTURE_SLOTS;
 
+pub use material::AlphaMode;
 pub use material::Material;
+pub use material::TextureSlot;
 pub use shader::Shader;
@@ -21,6 +21,7 @@ pub(crate) enum ShapeMode {
     LinedTriangles,
     FilledTriangles,
     Lines,
+    Points,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -59,6 +60,7 @@ impl ShapeMode {
             Self::FilledTriangles => vk::POLYGON_MODE_FILL,
             Self::LinedTriangles => vk::POLYGON_MODE_LINE,
             Self::Lines => vk::POLYGON_MODE_LINE,
+            Self::Points => vk::POLYGON_MODE_FILL,
         }
     }
 
@@ -67,6 +69,7 @@ impl ShapeMode {
             Self::FilledTriangles => vk::PRIMITIVE_TOPOLOGY_TRIANGLE_LIST,
             Self::LinedTriangles => vk::PRIMITIVE_TOPOLOGY_TRIANGLE_LIST,
             Self::Lines => vk::PRIMITIVE_TOPOLOGY_LINE_LIST,
+            Self::Points => vk::PRIMITIVE_TOPOLOGY_POINT_LIST,
         }
     }
 }
@@ -128,6 +131,7 @@ impl TryFrom<u8> for ShapeMode {
             0 => Ok(Self::LinedTriangles),
             1 => Ok(Self::FilledTriangles),
             2 => Ok(Self::Lines),
+            3 => Ok(Self::Points),
             _ => Err(error::Error::InvalidSpirv),
         }
     }
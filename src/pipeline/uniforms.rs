@@ -16,14 +16,19 @@ use crate::image::Filter;
 use crate::image::ImageLayout;
 use crate::image::Wrap;
 use crate::math::Mat4;
+use crate::math::Vec2;
 use crate::math::Vec3;
 use crate::math::Vec4;
 use crate::vk;
 
 const MAX_WORLDS: u32 = 100;
 const MAX_MATERIALS: u32 = 100;
-const MAX_TEXTURES: u32 = 100;
+pub(crate) const MAX_TEXTURES: u32 = 100;
 const MAX_CUBEMAPS: u32 = 100;
+const MAX_SKINS: u32 = 100;
+
+pub(crate) const MAX_JOINTS: usize = 128;
+pub(crate) const TEXTURE_SLOTS: usize = 5;
 
 #[derive(Copy, Clone)]
 #[repr(C)]
@@ -55,6 +60,23 @@ pub(crate) struct ShaderMaterial {
     pub(crate) f: Vec4,
     pub(crate) g: Vec4,
     pub(crate) h: Vec4,
+    pub(crate) transforms: [ShaderUvTransform; TEXTURE_SLOTS],
+}
+
+#[derive(Copy, Clone, Default, Debug, PartialEq)]
+#[repr(C)]
+pub(crate) struct ShaderUvTransform {
+    pub(crate) offset: Vec2,
+    // zero scale means no transform
+    pub(crate) scale: Vec2,
+    pub(crate) rotation: f32,
+    pub(crate) padding: [f32; 3],
+}
+
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub(crate) struct ShaderSkin {
+    pub(crate) joints: [Mat4; MAX_JOINTS],
 }
 
 #[derive(Default, Copy, Clone)]
@@ -63,6 +85,12 @@ pub(crate) struct ShaderLight {
     pub(crate) coords: Vec3,
     pub(crate) light_type: i32,
     pub(crate) color: Vec4,
+    pub(crate) direction: Vec3,
+    // cosines of spot light cone angles
+    pub(crate) inner_cone: f32,
+    pub(crate) outer_cone: f32,
+    pub(crate) range: f32,
+    pub(crate) padding: [f32; 2],
 }
 
 #[derive(Copy, Clone)]
@@ -79,9 +107,11 @@ pub(crate) struct Uniforms {
     material_layout: vk::DescriptorSetLayout,
     image_layout: vk::DescriptorSetLayout,
     shadow_map_layout: vk::DescriptorSetLayout,
+    skin_layout: vk::DescriptorSetLayout,
 
     world_count: u32,
     material_count: u32,
+    skin_count: u32,
 
     descriptor_pool: vk::DescriptorPool,
     image_descriptor: Descriptor,
@@ -130,7 +160,7 @@ impl Uniforms {
             vk::DescriptorSetLayoutBinding {
                 binding: 1,
                 descriptor_type: vk::DESCRIPTOR_TYPE_SAMPLER,
-                descriptor_count: 2 * 3,
+                descriptor_count: 2 * 4,
                 stage_flags: vk::SHADER_STAGE_FRAGMENT_BIT,
                 p_immutable_samplers: ptr::null(),
             },
@@ -154,6 +184,15 @@ impl Uniforms {
                 p_immutable_samplers: ptr::null(),
             }]);
 
+        // skin uniform layout
+        let skin_layout = device.create_descriptor_set_layout(&[vk::DescriptorSetLayoutBinding {
+            binding: 0,
+            descriptor_type: vk::DESCRIPTOR_TYPE_UNIFORM_BUFFER,
+            descriptor_count: 1,
+            stage_flags: vk::SHADER_STAGE_VERTEX_BIT,
+            p_immutable_samplers: ptr::null(),
+        }]);
+
         // descriptor pool
         let descriptor_pool = device.create_descriptor_pool(&[
             vk::DescriptorPoolSize {
@@ -164,6 +203,10 @@ impl Uniforms {
                 vk_type: vk::DESCRIPTOR_TYPE_UNIFORM_BUFFER,
                 descriptor_count: MAX_MATERIALS,
             },
+            vk::DescriptorPoolSize {
+                vk_type: vk::DESCRIPTOR_TYPE_UNIFORM_BUFFER,
+                descriptor_count: MAX_SKINS,
+            },
             vk::DescriptorPoolSize {
                 vk_type: vk::DESCRIPTOR_TYPE_COMBINED_IMAGE_SAMPLER,
                 descriptor_count: 1,
@@ -188,6 +231,7 @@ impl Uniforms {
             material_layout,
             image_layout,
             shadow_map_layout,
+            skin_layout,
         ];
         let pipeline_layout_info = vk::PipelineLayoutCreateInfo {
             s_type: vk::STRUCTURE_TYPE_PIPELINE_LAYOUT_CREATE_INFO,
@@ -213,6 +257,14 @@ impl Uniforms {
                 samplers.push(Sampler::new(device, *wrap, *filter, anisotropy));
             }
         }
+        for filter in &[Filter::Linear, Filter::Nearest] {
+            samplers.push(Sampler::new(
+                device,
+                Wrap::MirroredRepeat,
+                *filter,
+                anisotropy,
+            ));
+        }
 
         Self {
             pipeline_layout,
@@ -220,11 +272,13 @@ impl Uniforms {
             material_layout,
             image_layout,
             shadow_map_layout,
+            skin_layout,
             descriptor_pool,
             image_descriptor,
             samplers,
             world_count: 0,
             material_count: 0,
+            skin_count: 0,
             textures: vec![],
             cubemaps: vec![],
             should_update_images: true,
@@ -482,6 +536,42 @@ impl Uniforms {
         Ok(Descriptor(1, set))
     }
 
+    pub(crate) fn skin_set(
+        &mut self,
+        device: &Device,
+        buffer: &Buffer<ShaderSkin>,
+    ) -> Result<Descriptor> {
+        // check limits
+        if self.skin_count == MAX_SKINS {
+            return Err(Error::SkinLimit);
+        }
+        self.skin_count += 1;
+
+        let set = device.allocate_descriptor_set(self.skin_layout, self.descriptor_pool);
+
+        let buffer_info = [vk::DescriptorBufferInfo {
+            buffer: buffer.handle(),
+            offset: 0,
+            range: buffer.size() as u64,
+        }];
+        let write = [vk::WriteDescriptorSet {
+            s_type: vk::STRUCTURE_TYPE_WRITE_DESCRIPTOR_SET,
+            p_next: ptr::null(),
+            dst_set: set,
+            dst_binding: 0,
+            dst_array_element: 0,
+            descriptor_count: 1,
+            descriptor_type: vk::DESCRIPTOR_TYPE_UNIFORM_BUFFER,
+            p_image_info: ptr::null(),
+            p_buffer_info: buffer_info.as_ptr(),
+            p_texel_buffer_view: ptr::null(),
+        }];
+
+        device.update_descriptor_sets(&write);
+
+        Ok(Descriptor(4, set))
+    }
+
     pub(crate) fn shadow_map_set(&self, device: &Device, views: [vk::ImageView; 4]) -> Descriptor {
         let set = device.allocate_descriptor_set(self.shadow_map_layout, self.descriptor_pool);
 
@@ -528,6 +618,7 @@ impl Uniforms {
         device.destroy_descriptor_set_layout(self.material_layout);
         device.destroy_descriptor_set_layout(self.image_layout);
         device.destroy_descriptor_set_layout(self.shadow_map_layout);
+        device.destroy_descriptor_set_layout(self.skin_layout);
         device.destroy_descriptor_pool(self.descriptor_pool);
     }
 }
@@ -8,6 +8,7 @@ use super::Camera;
 use super::CharOrder;
 use super::LightType;
 use super::LineOrder;
+use super::MeshOrder;
 use super::Pcf;
 use super::ShaderOrder;
 use super::ShadowRenderer;
@@ -25,11 +26,14 @@ use crate::math::Mat4;
 use crate::math::Quat;
 use crate::math::Vec3;
 use crate::mesh::Mesh;
+use crate::mesh::Skin;
+use crate::pipeline::AlphaMode;
 use crate::pipeline::Descriptor;
 use crate::pipeline::ShaderConstants;
 use crate::pipeline::ShaderWorld;
 use crate::pipeline::Uniforms;
 use crate::resources::Builtins;
+use crate::resources::Handle;
 
 pub(crate) struct ForwardRenderer {
     target_resources: Vec<TargetResources>,
@@ -46,6 +50,13 @@ struct TargetResources {
     shape_mesh: Mesh,
 }
 
+// consecutive text orders drawn with the same shader
+struct TextRun {
+    sdf: Option<(f32, f32)>,
+    three_d: bool,
+    count: usize,
+}
+
 impl ForwardRenderer {
     pub(crate) fn new(
         device: &Device,
@@ -149,6 +160,7 @@ impl ForwardRenderer {
         cmd.begin_render_pass(canvas, target.background.into());
         cmd.set_view(canvas.width, canvas.height);
         cmd.bind_descriptor(uniforms, target_resources.world_descriptor);
+        cmd.bind_descriptor(uniforms, target.builtins.identity_skin.read().descriptor());
 
         let Target {
             builtins,
@@ -167,7 +179,7 @@ impl ForwardRenderer {
 
         // normal mesh rendering
         if !mesh_orders.is_empty() {
-            record_meshes(cmd, uniforms, mesh_orders);
+            record_meshes(cmd, uniforms, &builtins, camera, mesh_orders);
         }
 
         // tri rendering
@@ -208,6 +220,9 @@ impl ForwardRenderer {
         let mut indices = vec![];
         let mut uvs = vec![];
 
+        // group orders into runs with the same shader settings
+        let mut runs: Vec<TextRun> = vec![];
+
         for order in orders {
             let o = vertices.len() as u32;
             vertices.extend(&[
@@ -220,13 +235,16 @@ impl ForwardRenderer {
             colors.extend(&[order.color.into(); 4]);
             textures.extend(&[order.texture; 4]);
             indices.extend(&[o, o + 1, o + 2, o, o + 2, o + 3]);
-        }
-
-        // bind shader
-        cmd.bind_shader(&builtins.font_shader.read());
 
-        // bind material
-        cmd.bind_material(uniforms, &builtins.white_material.read());
+            match runs.last_mut() {
+                Some(run) if run.sdf == order.sdf && run.three_d == order.depth => run.count += 6,
+                _ => runs.push(TextRun {
+                    sdf: order.sdf,
+                    three_d: order.depth,
+                    count: 6,
+                }),
+            }
+        }
 
         // bind and draw mesh
         let text_mesh = &mut self.target_resources[self.target_index].text_mesh;
@@ -238,16 +256,36 @@ impl ForwardRenderer {
         text_mesh.indices = indices;
         text_mesh.update(device);
 
-        cmd.bind_mesh(text_mesh);
-        cmd.push_constants(
-            uniforms,
-            ShaderConstants {
-                local_to_world: Mat4::identity(),
-                tint_color: Vec3::default(),
-                sampler_index: 0,
-            },
-        );
-        cmd.draw(text_mesh.index_count(), 0);
+        let mut offset = 0;
+        let mut bound = None;
+        for run in runs {
+            // bind shader and material
+            if bound != Some((run.sdf.is_some(), run.three_d)) {
+                let shader = match (run.sdf, run.three_d) {
+                    (Some(_), false) => &builtins.sdf_font_shader,
+                    (Some(_), true) => &builtins.sdf_font_3d_shader,
+                    (None, false) => &builtins.font_shader,
+                    (None, true) => &builtins.font_3d_shader,
+                };
+                cmd.bind_shader(&shader.read());
+                cmd.bind_material(uniforms, &builtins.white_material.read());
+                cmd.bind_mesh(text_mesh);
+                bound = Some((run.sdf.is_some(), run.three_d));
+            }
+
+            // sdf shader reads edge and softness from tint
+            let (edge, softness) = run.sdf.unwrap_or((0.0, 0.0));
+            cmd.push_constants(
+                uniforms,
+                ShaderConstants {
+                    local_to_world: Mat4::identity(),
+                    tint_color: Vec3::new(edge, softness, 0.0),
+                    sampler_index: 0,
+                },
+            );
+            cmd.draw(run.count, offset);
+            offset += run.count;
+        }
     }
 
     fn record_lines(
@@ -316,6 +354,11 @@ impl ForwardRenderer {
 
         // order shape orders
         orders.sort_by(|a, b| {
+            // draw shapes without depth testing last
+            if a.depth != b.depth {
+                return b.depth.cmp(&a.depth);
+            }
+
             // sort by opacity
             if a.opaque && !b.opaque {
                 Ordering::Less
@@ -333,15 +376,20 @@ impl ForwardRenderer {
             }
         });
 
+        let tested = orders.iter().filter(|o| o.depth).count() * 3;
+
         // add opaque shapes
         for order in orders {
             let texture = order.texture;
             let sampler = order.sampler_index;
-            let color = order.color.into();
 
             let o = vertices.len() as u32;
             vertices.extend(&[order.points[0], order.points[1], order.points[2]]);
-            colors.extend(&[color, color, color]);
+            colors.extend(&[
+                order.colors[0].into(),
+                order.colors[1].into(),
+                order.colors[2].into(),
+            ]);
             textures.extend(&[texture, texture, texture]);
             uvs.extend(&[order.uvs[0], order.uvs[1], order.uvs[2]]);
             indices.extend(&[o, o + 1, o + 2]);
@@ -350,12 +398,6 @@ impl ForwardRenderer {
             normals.extend(&[Vec3::new(sampler as f32, 0.0, 0.0); 3]);
         }
 
-        // bind shader
-        cmd.bind_shader(&builtins.shape_shader.read());
-
-        // bind material
-        cmd.bind_material(uniforms, &builtins.white_material.read());
-
         // bind and draw mesh
         let shape_mesh = &mut self.target_resources[self.target_index].shape_mesh;
         shape_mesh.vertices = vertices;
@@ -366,16 +408,31 @@ impl ForwardRenderer {
         shape_mesh.indices = indices;
         shape_mesh.update(device);
 
-        cmd.bind_mesh(shape_mesh);
-        cmd.push_constants(
-            uniforms,
-            ShaderConstants {
-                local_to_world: Mat4::identity(),
-                tint_color: Vec3::default(),
-                sampler_index: 0,
-            },
-        );
-        cmd.draw(shape_mesh.index_count(), 0);
+        let total = shape_mesh.index_count();
+        let runs = [
+            (&builtins.shape_shader, tested, 0),
+            (&builtins.shape_overlay_shader, total - tested, tested),
+        ];
+        for (shader, count, offset) in &runs {
+            if *count == 0 {
+                continue;
+            }
+
+            // bind shader and material
+            cmd.bind_shader(&shader.read());
+            cmd.bind_material(uniforms, &builtins.white_material.read());
+
+            cmd.bind_mesh(shape_mesh);
+            cmd.push_constants(
+                uniforms,
+                ShaderConstants {
+                    local_to_world: Mat4::identity(),
+                    tint_color: Vec3::default(),
+                    sampler_index: 0,
+                },
+            );
+            cmd.draw(*count, *offset);
+        }
     }
 
     pub(crate) fn destroy(&self, device: &Device, uniforms: &mut Uniforms) {
@@ -411,32 +468,100 @@ impl TargetResources {
     }
 }
 
-fn record_meshes(cmd: &Commands, uniforms: &Uniforms, orders: Vec<ShaderOrder>) {
-    for s_order in orders {
-        // bind shader
-        cmd.bind_shader(&s_order.shader.read());
-
+fn record_meshes(
+    cmd: &Commands,
+    uniforms: &Uniforms,
+    builtins: &Builtins,
+    camera: &Camera,
+    orders: Vec<ShaderOrder>,
+) {
+    let mut bound_skin = builtins.identity_skin.clone();
+    let mut bound_shader = None;
+    let mut blended = vec![];
+
+    // opaque and masked materials
+    for s_order in &orders {
         for m_order in &s_order.orders {
+            let material = m_order.material.read();
+            if material.alpha == AlphaMode::Blend {
+                blended.extend(m_order.orders.iter().map(|o| (s_order, m_order, o)));
+                continue;
+            }
+
+            // bind shader
+            let shader = s_order.shader.read();
+            let pipeline = shader.variant(material.double_sided, false);
+            if bound_shader != Some(pipeline) {
+                cmd.bind_shader_variant(&shader, material.double_sided, false);
+                bound_shader = Some(pipeline);
+            }
+
             // bind material
-            cmd.bind_material(uniforms, &m_order.material.read());
+            cmd.bind_material(uniforms, &material);
 
             for order in &m_order.orders {
-                cmd.push_constants(
-                    uniforms,
-                    ShaderConstants {
-                        local_to_world: order.matrix,
-                        tint_color: Rgbf::from(order.color).into(),
-                        sampler_index: order.sampler_index,
-                    },
-                );
-                {
-                    let m = order.mesh.read();
-                    cmd.bind_mesh(&m);
-                    cmd.draw(m.index_count(), 0);
-                }
+                record_mesh(cmd, uniforms, builtins, &mut bound_skin, order);
             }
         }
     }
+
+    // blended materials from back to front
+    let distance = |o: &MeshOrder| (o.matrix.w.xyz() - camera.position).sqr_length();
+    blended.sort_by(|a, b| {
+        distance(b.2)
+            .partial_cmp(&distance(a.2))
+            .unwrap_or(Ordering::Equal)
+    });
+
+    let mut bound_material = None;
+    for (s_order, m_order, order) in blended {
+        let material = m_order.material.read();
+
+        // bind shader
+        let shader = s_order.shader.read();
+        let pipeline = shader.variant(material.double_sided, true);
+        if bound_shader != Some(pipeline) {
+            cmd.bind_shader_variant(&shader, material.double_sided, true);
+            bound_shader = Some(pipeline);
+        }
+
+        // bind material
+        if bound_material != Some(material.descriptor()) {
+            cmd.bind_material(uniforms, &material);
+            bound_material = Some(material.descriptor());
+        }
+
+        record_mesh(cmd, uniforms, builtins, &mut bound_skin, order);
+    }
+}
+
+fn record_mesh(
+    cmd: &Commands,
+    uniforms: &Uniforms,
+    builtins: &Builtins,
+    bound_skin: &mut Handle<Skin>,
+    order: &MeshOrder,
+) {
+    // bind skin
+    let skin = order.skin.as_ref().unwrap_or(&builtins.identity_skin);
+    if skin != bound_skin {
+        cmd.bind_descriptor(uniforms, skin.read().descriptor());
+        *bound_skin = skin.clone();
+    }
+
+    cmd.push_constants(
+        uniforms,
+        ShaderConstants {
+            local_to_world: order.matrix,
+            tint_color: Rgbf::from(order.color).into(),
+            sampler_index: order.sampler_index,
+        },
+    );
+    {
+        let m = order.mesh.read();
+        cmd.bind_mesh(&m);
+        cmd.draw(m.index_count(), 0);
+    }
 }
 
 fn record_skybox(cmd: &Commands, uniforms: &Uniforms, camera: &Camera, builtins: &Builtins) {
@@ -3,6 +3,7 @@
 
 use crate::color::Rgb;
 use crate::color::Rgbf;
+use crate::math::Mat4;
 use crate::math::Vec3;
 use crate::math::Vec4;
 use crate::pipeline::ShaderLight;
@@ -29,9 +30,21 @@ pub struct Light {
     pub color: Rgb,
     /// brightness of the light,
     /// multiplied with the color in shaders
+    ///
+    /// Point and spot lights fade with the
+    /// squared distance, so glTF intensities
+    /// (candela and lux) are used as is.
     pub brightness: f32,
     /// the type of the light
     pub light_type: LightType,
+    /// direction of spot lights
+    pub direction: Vec3,
+    /// inner and outer cone angles of
+    /// spot lights in degrees
+    pub cone: (f32, f32),
+    /// distance where point and spot lights
+    /// fade out, 0 for no limit
+    pub range: f32,
 }
 
 /// Type of a light.
@@ -41,6 +54,8 @@ pub enum LightType {
     Directional,
     /// point light (like from a lightbulb)
     Point,
+    /// spot light (like from a flashlight)
+    Spot,
 }
 
 impl Light {
@@ -51,6 +66,9 @@ impl Light {
             coords: direction.into().unit(),
             color: color.into(),
             brightness: 1.0,
+            direction: Vec3::default(),
+            cone: (0.0, 0.0),
+            range: 0.0,
         }
     }
 
@@ -61,9 +79,50 @@ impl Light {
             coords: position.into(),
             color: color.into(),
             brightness: 1.0,
+            direction: Vec3::default(),
+            cone: (0.0, 0.0),
+            range: 0.0,
         }
     }
 
+    /// Create spot light
+    ///
+    /// Light fades out between the inner
+    /// and outer cone angles. The inner angle
+    /// is kept smaller than the outer angle.
+    pub fn spot(
+        color: impl Into<Rgb>,
+        position: impl Into<Vec3>,
+        direction: impl Into<Vec3>,
+        inner_angle: f32,
+        outer_angle: f32,
+    ) -> Self {
+        Self {
+            light_type: LightType::Spot,
+            coords: position.into(),
+            direction: direction.into().unit(),
+            cone: (inner_angle, outer_angle),
+            color: color.into(),
+            brightness: 1.0,
+            range: 0.0,
+        }
+    }
+
+    /// Transform light's position and direction by a matrix
+    pub fn transformed(&self, matrix: Mat4) -> Self {
+        let direction = |v: Vec3| (matrix * Vec4::from((v, 0.0))).xyz().unit();
+        let mut light = *self;
+        match self.light_type {
+            LightType::Directional => light.coords = direction(self.coords),
+            LightType::Point => light.coords = matrix * self.coords,
+            LightType::Spot => {
+                light.coords = matrix * self.coords;
+                light.direction = direction(self.direction);
+            }
+        }
+        light
+    }
+
     pub(crate) fn none() -> Self {
         Self::point(Rgb::clear(), [0.0, 0.0, 0.0])
     }
@@ -76,12 +135,61 @@ impl Light {
         let light_type = match self.light_type {
             LightType::Directional => 0,
             LightType::Point => 1,
+            LightType::Spot => 2,
         };
 
+        // inner cone needs to be narrower than
+        // the outer cone for a defined fade
+        let outer_cone = self.cone.1.to_radians().cos();
+        let inner_cone = self.cone.0.to_radians().cos().max(outer_cone + 0.001);
+
         ShaderLight {
             coords: self.coords,
             color: Vec4::from(Rgbf::from(self.color)) * self.brightness,
+            direction: self.direction,
+            range: self.range,
+            padding: [0.0; 2],
+            inner_cone,
+            outer_cone,
             light_type,
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::Light;
+    use crate::math::Mat4;
+    use crate::math::Vec3;
+
+    #[test]
+    fn transformed_point() {
+        let light = Light::point("#ffffff", [1.0, 0.0, 0.0]);
+        let moved = light.transformed(Mat4::translation([0.0, 2.0, 0.0]));
+        assert_eq!(moved.coords, Vec3::new(1.0, 2.0, 0.0));
+    }
+
+    #[test]
+    fn transformed_directional() {
+        let light = Light::directional("#ffffff", [0.0, 0.0, 1.0]);
+        let moved =
+            light.transformed(Mat4::translation([0.0, 2.0, 0.0]) * Mat4::scale([2.0, 2.0, 2.0]));
+        assert_eq_delta!(moved.coords.z, 1.0);
+        assert_eq_delta!(moved.coords.y, 0.0);
+    }
+
+    #[test]
+    fn transformed_spot() {
+        let light = Light::spot("#ffffff", [0.0, 0.0, 0.0], [0.0, 0.0, 1.0], 20.0, 30.0);
+        let moved = light.transformed(Mat4::euler_rotation(0.0, 90.0, 0.0));
+        assert_eq_delta!(moved.direction.length(), 1.0);
+        assert_eq_delta!(moved.direction.z, 0.0);
+    }
+
+    #[test]
+    fn equal_spot_cones() {
+        let light = Light::spot("#ffffff", [0.0, 0.0, 0.0], [0.0, 0.0, 1.0], 30.0, 30.0);
+        let shader = light.shader();
+        assert!(shader.inner_cone > shader.outer_cone);
+    }
+}
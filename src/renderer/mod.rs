@@ -10,8 +10,11 @@ mod target;
 pub(crate) use forward::ForwardRenderer;
 pub(crate) use shadow::ShadowRenderer;
 pub(crate) use shadow::ShadowSplitParams;
+#[cfg(feature = "svg")]
+pub(crate) use target::subdivide;
 pub(crate) use target::CharOrder;
 pub(crate) use target::LineOrder;
+pub(crate) use target::MeshOrder;
 pub(crate) use target::ShaderOrder;
 pub(crate) use target::TriOrder;
 
@@ -19,6 +22,8 @@ pub use camera::Camera;
 pub use camera::Projection;
 pub use light::Light;
 pub use light::LightType;
+pub use target::BillboardMode;
+pub use target::BillboardSize;
 pub use target::BorderMode;
 pub use target::Pcf;
 pub use target::ShapeMode;
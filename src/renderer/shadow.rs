@@ -175,10 +175,20 @@ impl ShadowRenderer {
             cmd.bind_descriptor(uniforms, target_resources.world_descriptors[i]);
             cmd.bind_shader(&self.shader);
 
+            let identity_skin = &target.builtins.identity_skin;
+            let mut bound_skin = identity_skin.clone();
+            cmd.bind_descriptor(uniforms, identity_skin.read().descriptor());
+
             for s_order in &target.mesh_orders {
                 for m_order in &s_order.orders {
                     for order in &m_order.orders {
                         if order.shadows {
+                            let skin = order.skin.as_ref().unwrap_or(identity_skin);
+                            if skin != &bound_skin {
+                                cmd.bind_descriptor(uniforms, skin.read().descriptor());
+                                bound_skin = skin.clone();
+                            }
+
                             cmd.push_constants(
                                 uniforms,
                                 ShaderConstants {
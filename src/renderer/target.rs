@@ -3,22 +3,40 @@
 
 use std::f32::consts::PI;
 
+use super::Camera;
 use super::Light;
+use super::Projection;
 use crate::color::Rgb;
 use crate::color::Rgbf;
+use crate::color::ShapeGradient;
+#[cfg(feature = "svg")]
+use crate::features::svg::Svg;
+use crate::font::font_runs;
 use crate::font::Font;
+use crate::font::LayoutPart;
+use crate::font::Span;
+use crate::font::TextLayout;
+use crate::font::TextStyle;
+use crate::font::TextWrap;
+use crate::image;
+use crate::image::Animation;
 use crate::image::Canvas;
 use crate::image::Cubemap;
 use crate::image::Filter;
 use crate::image::Texture;
+use crate::image::TextureAtlas;
 use crate::image::Wrap;
 use crate::math::Mat4;
 use crate::math::Quat;
 use crate::math::Vec2;
 use crate::math::Vec3;
+use crate::math::Vec4;
 use crate::mesh::Mesh;
 use crate::mesh::Model;
 use crate::mesh::ModelNode;
+use crate::mesh::Primitive;
+use crate::mesh::Skin;
+use crate::mesh::Tilemap;
 use crate::pipeline::Material;
 use crate::pipeline::Shader;
 use crate::resources::Builtins;
@@ -35,6 +53,8 @@ pub struct Target {
     fill: Rgb,
     stroke: Rgb,
     tint: Rgb,
+    fill_gradient: Option<ShapeGradient>,
+    stroke_gradient: Option<ShapeGradient>,
     pub(crate) background: Rgb,
 
     // shadows
@@ -58,10 +78,22 @@ pub struct Target {
     filter: Filter,
     wrap: Wrap,
 
+    // text effects
+    text_outline: Option<(Rgb, f32)>,
+    text_glow: Option<(Rgb, f32)>,
+    text_shadow: Option<(Rgb, Vec2)>,
+
+    // 3D text and billboards
+    depth_test: bool,
+    camera: Camera,
+    view_height: f32,
+
     // resources
     shader: Option<Handle<Shader>>,
     material: Option<Handle<Material>>,
+    skin: Option<Handle<Skin>>,
     font: Option<Handle<Font>>,
+    font_fallbacks: Vec<Handle<Font>>,
     pub(crate) skybox: Option<Handle<Cubemap>>,
     pub(crate) builtins: Builtins,
 
@@ -89,6 +121,27 @@ pub enum ShapeMode {
     Center,
 }
 
+/// Billboard orientation mode.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BillboardMode {
+    /// use the target's transform
+    None,
+    /// always face the camera
+    Spherical,
+    /// face the camera rotating
+    /// only around the y axis
+    Cylindrical,
+}
+
+/// Size of 3D text and billboards.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum BillboardSize {
+    /// height in world units
+    World(f32),
+    /// height in pixels on screen
+    Pixels(f32),
+}
+
 /// Border positioning mode.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum BorderMode {
@@ -128,6 +181,7 @@ pub(crate) struct MeshOrder {
     pub(crate) color: Rgb,
     pub(crate) shadows: bool,
     pub(crate) sampler_index: u32,
+    pub(crate) skin: Option<Handle<Skin>>,
 }
 
 pub(crate) struct CharOrder {
@@ -135,6 +189,9 @@ pub(crate) struct CharOrder {
     pub(crate) uvs: [Vec2; 4],
     pub(crate) color: Rgb,
     pub(crate) texture: u32,
+    // edge and softness for sdf fonts
+    pub(crate) sdf: Option<(f32, f32)>,
+    pub(crate) depth: bool,
 }
 
 pub(crate) struct LineOrder {
@@ -145,22 +202,34 @@ pub(crate) struct LineOrder {
 pub(crate) struct TriOrder {
     pub(crate) points: [Vec3; 3],
     pub(crate) uvs: [Vec2; 3],
-    pub(crate) color: Rgb,
+    pub(crate) colors: [Rgb; 3],
     pub(crate) texture: u32,
     pub(crate) sampler_index: u32,
     pub(crate) opaque: bool,
+    pub(crate) depth: bool,
+}
+
+#[derive(Copy, Clone)]
+enum TextPass {
+    Fill,
+    Glow(f32),
+    Outline(f32),
 }
 
 struct Cache {
     shader: Option<Handle<Shader>>,
     material: Option<Handle<Material>>,
+    skin: Option<Handle<Skin>>,
     font: Option<Handle<Font>>,
+    font_fallbacks: Vec<Handle<Font>>,
 
     // colors
     background: Rgb,
     fill: Rgb,
     stroke: Rgb,
     tint: Rgb,
+    fill_gradient: Option<ShapeGradient>,
+    stroke_gradient: Option<ShapeGradient>,
 
     // shadows
     shadows: bool,
@@ -173,15 +242,23 @@ struct Cache {
     border_mode: BorderMode,
     filter: Filter,
     wrap: Wrap,
+
+    // text effects
+    text_outline: Option<(Rgb, f32)>,
+    text_glow: Option<(Rgb, f32)>,
+    text_shadow: Option<(Rgb, Vec2)>,
+    depth_test: bool,
 }
 
 impl Target {
-    pub(crate) fn new(builtins: &Builtins) -> Self {
+    pub(crate) fn new(builtins: &Builtins, camera: &Camera, view_height: u32) -> Self {
         Self {
             background: Rgb::gray(255),
             fill: Rgb::gray(255),
             stroke: Rgb::gray(0),
             tint: Rgb::gray(255),
+            fill_gradient: None,
+            stroke_gradient: None,
 
             shadows: false,
             shadow_depth: 50.0,
@@ -201,9 +278,19 @@ impl Target {
             filter: Filter::Linear,
             wrap: Wrap::Repeat,
 
+            text_outline: None,
+            text_glow: None,
+            text_shadow: None,
+
+            depth_test: true,
+            camera: camera.clone(),
+            view_height: view_height as f32,
+
             font: None,
+            font_fallbacks: vec![],
             shader: None,
             material: None,
+            skin: None,
             skybox: None,
             builtins: builtins.clone(),
 
@@ -224,11 +311,31 @@ impl Target {
     /// Set fill color for shapes and text
     pub fn fill(&mut self, color: impl Into<Rgb>) {
         self.fill = color.into();
+        self.fill_gradient = None;
     }
 
     /// Set stroke color for borders and lines
     pub fn stroke(&mut self, color: impl Into<Rgb>) {
         self.stroke = color.into();
+        self.stroke_gradient = None;
+    }
+
+    /// Set gradient fill for shapes
+    ///
+    /// Gradient positions are in the same space as
+    /// the shape points. Stays active until the next
+    /// [fill](crate::renderer::Target::fill) call.
+    pub fn fill_gradient(&mut self, gradient: impl Into<ShapeGradient>) {
+        self.fill_gradient = Some(gradient.into());
+    }
+
+    /// Set gradient stroke for borders and lines
+    ///
+    /// Gradient positions are in the same space as
+    /// the shape points. Stays active until the next
+    /// [stroke](crate::renderer::Target::stroke) call.
+    pub fn stroke_gradient(&mut self, gradient: impl Into<ShapeGradient>) {
+        self.stroke_gradient = Some(gradient.into());
     }
 
     /// Set tint color for meshes and textures
@@ -246,6 +353,16 @@ impl Target {
         self.material = None;
     }
 
+    /// Set skin for deforming meshes
+    pub fn skin(&mut self, s: &Handle<Skin>) {
+        self.skin = Some(s.clone());
+    }
+
+    /// Draw meshes without a skin
+    pub fn no_skin(&mut self) {
+        self.skin = None;
+    }
+
     /// Set shader for meshes
     pub fn shader(&mut self, s: &Handle<Shader>) {
         self.shader = Some(s.clone());
@@ -271,6 +388,19 @@ impl Target {
         self.font = None;
     }
 
+    /// Set fonts for characters missing from the font
+    ///
+    /// Each character is drawn with the first
+    /// font that contains it.
+    pub fn font_fallbacks(&mut self, fonts: &[&Handle<Font>]) {
+        self.font_fallbacks = fonts.iter().map(|f| (*f).clone()).collect();
+    }
+
+    /// Disable fallback fonts
+    pub fn no_font_fallbacks(&mut self) {
+        self.font_fallbacks.clear();
+    }
+
     /// Enable casting shadows for meshes
     ///
     /// Uses the first directional light for
@@ -331,6 +461,54 @@ impl Target {
         self.font_size = s;
     }
 
+    /// Set outline for text
+    ///
+    /// Only drawn for SDF fonts.
+    /// Width is in pixels.
+    pub fn text_outline(&mut self, color: impl Into<Rgb>, width: f32) {
+        self.text_outline = Some((color.into(), width));
+    }
+
+    /// Disable outline for text
+    pub fn no_text_outline(&mut self) {
+        self.text_outline = None;
+    }
+
+    /// Set glow for text
+    ///
+    /// Only drawn for SDF fonts.
+    /// Width is in pixels.
+    pub fn text_glow(&mut self, color: impl Into<Rgb>, width: f32) {
+        self.text_glow = Some((color.into(), width));
+    }
+
+    /// Disable glow for text
+    pub fn no_text_glow(&mut self) {
+        self.text_glow = None;
+    }
+
+    /// Set drop shadow for text
+    ///
+    /// Offset is in pixels
+    pub fn text_shadow(&mut self, color: impl Into<Rgb>, offset: impl Into<Vec2>) {
+        self.text_shadow = Some((color.into(), offset.into()));
+    }
+
+    /// Disable drop shadow for text
+    pub fn no_text_shadow(&mut self) {
+        self.text_shadow = None;
+    }
+
+    /// Enable depth testing for 3D text and billboards
+    pub fn depth_test(&mut self) {
+        self.depth_test = true;
+    }
+
+    /// Draw 3D text and billboards on top of meshes
+    pub fn no_depth_test(&mut self) {
+        self.depth_test = false;
+    }
+
     /// Set border mode
     pub fn border_mode(&mut self, mode: BorderMode) {
         self.border_mode = mode;
@@ -427,19 +605,16 @@ impl Target {
     /// Draw a custom 3D mesh
     pub fn mesh(&mut self, mesh: &Handle<Mesh>) {
         let unshaded = self.lights.iter().all(|l| l.is_none());
+        let primitive = mesh.read().primitive;
 
         let order = MeshOrder {
             mesh: mesh.clone(),
             matrix: self.matrix,
             color: self.tint,
-            shadows: self.shadows,
+            // only triangles cast shadows
+            shadows: self.shadows && primitive == Primitive::Triangles,
             sampler_index: self.sampler_index(),
-        };
-
-        let shader = match &self.shader {
-            Some(s) => s,
-            None if unshaded => &self.builtins.unshaded_shader,
-            None => &self.builtins.pbr_shader,
+            skin: self.skin.clone(),
         };
 
         let material = match &self.material {
@@ -447,6 +622,14 @@ impl Target {
             None => &self.builtins.white_material,
         };
 
+        let shader = match &self.shader {
+            Some(s) => s,
+            None if primitive == Primitive::Lines => &self.builtins.line_shader,
+            None if primitive == Primitive::Points => &self.builtins.point_shader,
+            None if unshaded || material.read().unshaded => &self.builtins.unshaded_shader,
+            None => &self.builtins.pbr_shader,
+        };
+
         match self.mesh_orders.iter_mut().find(|so| &so.shader == shader) {
             Some(so) => match so.orders.iter_mut().find(|mo| &mo.material == material) {
                 Some(mo) => mo.orders.push(order),
@@ -531,13 +714,33 @@ impl Target {
 
     /// Draw all of the meshes of a model
     pub fn model(&mut self, model: &Handle<Model>) {
+        let root = self.matrix;
         for node in &model.read().nodes {
             self.push();
-            self.model_node(node, self.matrix);
+            self.model_subtree(node, root, root);
             self.pop();
         }
     }
 
+    /// Draw the meshes of a model node and its children
+    ///
+    /// The node is placed relative to the current
+    /// transform, ignoring the transforms of its parents.
+    pub fn model_node(&mut self, node: &ModelNode) {
+        let root = self.matrix;
+        self.push();
+        self.model_subtree(node, root, root);
+        self.pop();
+    }
+
+    /// Add lights from a model, transformed
+    /// by the current transform
+    pub fn model_lights(&mut self, model: &Handle<Model>) {
+        for light in model.read().lights() {
+            self.light(light.transformed(self.matrix));
+        }
+    }
+
     /// Draw a 3D debug line that isn't controlled by
     /// [stroke_weight](crate::renderer::Target::stroke_weight)
     pub fn debug_line<V: Into<Vec3>>(&mut self, p1: V, p2: V) {
@@ -585,72 +788,154 @@ impl Target {
 
     /// Draw text string
     pub fn text(&mut self, text: impl AsRef<str>, pos: impl Into<Vec2>) {
-        let mut position = pos.into();
-        let t = text.as_ref();
-        let font = self
-            .font
-            .as_ref()
-            .unwrap_or(&self.builtins.fira_font)
-            .read();
+        self.rich_text(&[Span::new(text.as_ref())], pos);
+    }
+
+    /// Draw text wrapped to fit into `max_width`
+    pub fn text_box(
+        &mut self,
+        text: impl AsRef<str>,
+        pos: impl Into<Vec2>,
+        max_width: f32,
+        style: TextStyle,
+    ) {
+        self.rich_text_box(&[Span::new(text.as_ref())], pos, max_width, style);
+    }
+
+    /// Draw text made of styled spans
+    pub fn rich_text(&mut self, spans: &[Span<'_>], pos: impl Into<Vec2>) {
+        let style = TextStyle {
+            wrap: TextWrap::None,
+            ..Default::default()
+        };
+        let layout = self.rich_text_layout(spans, None, style);
+        let w = layout.width();
+        let h = layout.height();
+        let pos = pos.into();
+        let matrix = self.matrix;
+        let place = move |p: Vec2| matrix * Vec3::from((p + pos, 0.0));
+        self.draw_text_layout(&layout, spans, w, h, place, false);
+    }
+
+    /// Draw text made of styled spans
+    /// wrapped to fit into `max_width`
+    pub fn rich_text_box(
+        &mut self,
+        spans: &[Span<'_>],
+        pos: impl Into<Vec2>,
+        max_width: f32,
+        style: TextStyle,
+    ) {
+        let layout = self.rich_text_layout(spans, Some(max_width), style);
+        let h = layout.height();
+        let pos = pos.into();
+        let matrix = self.matrix;
+        let place = move |p: Vec2| matrix * Vec3::from((p + pos, 0.0));
+        self.draw_text_layout(&layout, spans, max_width, h, place, false);
+    }
+
+    /// Draw text in 3D space
+    ///
+    /// `size` is the font size in world units or
+    /// pixels. Text is depth tested unless
+    /// disabled with [no_depth_test](Self::no_depth_test).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use duku::Duku;
+    /// # use duku::Camera;
+    /// # use duku::BillboardMode;
+    /// # use duku::BillboardSize;
+    /// # let (mut d, _) = Duku::windowed(1, 1).unwrap();
+    /// # let camera = Camera::perspective(90);
+    /// d.draw(Some(&camera), |t| {
+    ///     t.text_3d(
+    ///         "Player",
+    ///         [0.0, 2.0, 0.0],
+    ///         BillboardMode::Spherical,
+    ///         BillboardSize::Pixels(16.0),
+    ///     );
+    /// });
+    /// ```
+    pub fn text_3d(
+        &mut self,
+        text: impl AsRef<str>,
+        pos: impl Into<Vec3>,
+        mode: BillboardMode,
+        size: BillboardSize,
+    ) {
+        let style = TextStyle {
+            wrap: TextWrap::None,
+            ..Default::default()
+        };
+        let spans = [Span::new(text.as_ref())];
+        let layout = self.rich_text_layout(&spans, None, style);
+        let w = layout.width();
+        let h = layout.height();
 
-        let w = self.text_width(t);
-        let h = self.text_height(t);
+        // layout is scaled from font size
+        let (origin, right, up) = self.billboard_axes(pos.into(), mode);
+        let scale = self.world_height(origin, size) / self.font_size as f32;
+        let place = move |p: Vec2| origin + right * (p.x * scale) + up * (p.y * scale);
+        self.draw_text_layout(&layout, &spans, w, h, place, self.depth_test);
+    }
 
-        position.y += h - self.text_height("a");
+    /// Draw a texture in 3D space
+    ///
+    /// `size` is the height of the texture in world
+    /// units or pixels. The width keeps the
+    /// texture's aspect ratio.
+    pub fn billboard(
+        &mut self,
+        texture: &Handle<Texture>,
+        pos: impl Into<Vec3>,
+        mode: BillboardMode,
+        size: BillboardSize,
+    ) {
+        let (origin, right, up) = self.billboard_axes(pos.into(), mode);
+        let tex = texture.read();
+        let h = self.world_height(origin, size);
+        let w = h * tex.width() as f32 / tex.height() as f32;
+        let opaque = tex.opaque() && self.tint.a == 255;
 
-        position += match self.shape_mode {
+        let offset = match self.shape_mode {
             ShapeMode::BottomLeft => Vec2::new(0.0, 0.0),
             ShapeMode::BottomRight => Vec2::new(-w, 0.0),
             ShapeMode::TopLeft => Vec2::new(0.0, -h),
             ShapeMode::TopRight => Vec2::new(-w, -h),
             ShapeMode::Center => Vec2::new(-w / 2.0, -h / 2.0),
         };
+        let to_world = |x: f32, y: f32| origin + right * (x + offset.x) + up * (y + offset.y);
 
-        let metrics = font.metrics().scaled(self.font_size);
-        let start_x = position.x;
-
-        for c in t.chars() {
-            // handle whitespace
-            if c == ' ' {
-                position.x += metrics.space_width;
-                continue;
-            }
-            if c == '\n' {
-                position.x = start_x;
-                position.y -= metrics.height + metrics.line_gap;
-                continue;
-            }
-
-            // calculate positions
-            let data = font.char_data(c).scaled(self.font_size);
-            let bx = data.bounds.x;
-            let by = data.bounds.y;
-            let mut cp = position;
-            cp.x += data.bearing.x;
-            cp.y -= metrics.descender;
-            cp.y -= data.bearing.y;
+        let p1 = to_world(0.0, h);
+        let p2 = to_world(w, h);
+        let p3 = to_world(w, 0.0);
+        let p4 = to_world(0.0, 0.0);
 
-            // calculate points
-            let p1 = self.matrix * Vec3::new(cp.x, cp.y + by, 0.0);
-            let p2 = self.matrix * Vec3::new(cp.x + bx, cp.y + by, 0.0);
-            let p3 = self.matrix * Vec3::new(cp.x + bx, cp.y, 0.0);
-            let p4 = self.matrix * Vec3::new(cp.x, cp.y, 0.0);
-
-            let uv1 = Vec2::new(data.uvs.x, data.uvs.y);
-            let uv2 = Vec2::new(data.uvs.z, data.uvs.y);
-            let uv3 = Vec2::new(data.uvs.z, data.uvs.w);
-            let uv4 = Vec2::new(data.uvs.x, data.uvs.w);
-
-            // add order
-            self.char_orders.push(CharOrder {
-                points: [p1, p2, p3, p4],
-                uvs: [uv1, uv2, uv3, uv4],
-                color: self.stroke,
-                texture: font.texture().shader_index(),
-            });
+        let uv1 = Vec2::new(0.0, 0.0);
+        let uv2 = Vec2::new(1.0, 0.0);
+        let uv3 = Vec2::new(1.0, 1.0);
+        let uv4 = Vec2::new(0.0, 1.0);
 
-            position.x += data.advance;
-        }
+        self.tri_orders.push(TriOrder {
+            points: [p1, p2, p3],
+            colors: [self.tint; 3],
+            uvs: [uv1, uv2, uv3],
+            texture: tex.shader_index(),
+            sampler_index: self.sampler_index(),
+            depth: self.depth_test,
+            opaque,
+        });
+        self.tri_orders.push(TriOrder {
+            points: [p1, p3, p4],
+            colors: [self.tint; 3],
+            uvs: [uv1, uv3, uv4],
+            texture: tex.shader_index(),
+            sampler_index: self.sampler_index(),
+            depth: self.depth_test,
+            opaque,
+        });
     }
 
     /// Draw a custom shape from points
@@ -663,7 +948,12 @@ impl Target {
         }
 
         // check if should draw shape
-        if self.fill.a > 0 {
+        if let Some(gradient) = self.fill_gradient.take() {
+            if gradient.visible() {
+                self.gradient_polygon(points, &gradient, 0.0);
+            }
+            self.fill_gradient = Some(gradient);
+        } else if self.fill.a > 0 {
             let texture = self.builtins.white_texture.read().shader_index();
             let opaque = self.fill.a == 255;
 
@@ -677,16 +967,21 @@ impl Target {
                         self.matrix * Vec3::from((points[i], 0.0)),
                     ],
                     uvs: [Vec2::default(); 3],
-                    color: self.fill,
+                    colors: [self.fill; 3],
                     sampler_index: 0,
                     texture,
                     opaque,
+                    depth: true,
                 });
             }
         }
 
         // check if should draw borders
-        if self.stroke.a > 0 {
+        let has_stroke = match &self.stroke_gradient {
+            Some(gradient) => gradient.visible(),
+            None => self.stroke.a > 0,
+        };
+        if has_stroke {
             let outer_weight = match self.border_mode {
                 BorderMode::Center => self.stroke_weight / 2.0,
                 BorderMode::Outside => self.stroke_weight,
@@ -728,6 +1023,69 @@ impl Target {
         self.rect(pos, Vec2::new(size, size));
     }
 
+    /// Draw a rectangle with rounded corners
+    ///
+    /// Radii are for the top-left, top-right,
+    /// bottom-right and bottom-left corners.
+    pub fn rounded_rect(
+        &mut self,
+        pos: impl Into<Vec2>,
+        size: impl Into<Vec2>,
+        radii: impl Into<Vec4>,
+    ) {
+        let s = size.into();
+        let p = pos.into();
+        let r = radii.into();
+
+        let offset = match self.shape_mode {
+            ShapeMode::BottomLeft => Vec2::new(0.0, 0.0),
+            ShapeMode::BottomRight => Vec2::new(-s.x, 0.0),
+            ShapeMode::TopLeft => Vec2::new(0.0, -s.y),
+            ShapeMode::TopRight => Vec2::new(-s.x, -s.y),
+            ShapeMode::Center => Vec2::new(-s.x / 2.0, -s.y / 2.0),
+        };
+
+        let left = p.x + offset.x;
+        let bottom = p.y + offset.y;
+        let right = left + s.x;
+        let top = bottom + s.y;
+
+        // radii can't be bigger than half the rect
+        let max_radius = s.x.abs().min(s.y.abs()) / 2.0;
+        let corners = [
+            (Vec2::new(left, top), Vec2::new(1.0, -1.0), 180.0, r.x),
+            (Vec2::new(right, top), Vec2::new(-1.0, -1.0), 90.0, r.y),
+            (Vec2::new(right, bottom), Vec2::new(-1.0, 1.0), 0.0, r.z),
+            (Vec2::new(left, bottom), Vec2::new(1.0, 1.0), -90.0, r.w),
+        ];
+
+        let mut points: Vec<Vec2> = vec![];
+        for (corner, dir, start, radius) in &corners {
+            let radius = radius.max(0.0).min(max_radius);
+
+            if radius == 0.0 {
+                points.push(*corner);
+                continue;
+            }
+
+            // go around the corner arc clockwise
+            let center = *corner + *dir * radius;
+            let side_count = (Vec2::new(radius, radius).length() * 3.0 / 4.0).ceil() as u32;
+            for i in 0..=side_count {
+                let q = (start - 90.0 * (i as f32 / side_count as f32)).to_radians();
+                points.push(center + Vec2::new(q.cos(), q.sin()) * radius);
+            }
+        }
+
+        // remove points that touch
+        points.dedup_by(|a, b| (*a - *b).sqr_length() < 0.0001);
+        while points.len() > 1 && (points[0] - points[points.len() - 1]).sqr_length() < 0.0001 {
+            points.pop();
+        }
+
+        self.shape(&points);
+    }
+
     /// Draw an ellipse
     pub fn ellipse(&mut self, pos: impl Into<Vec2>, size: impl Into<Vec2>) {
         let s = size.into() / 2.0;
@@ -812,62 +1170,252 @@ impl Target {
 
         self.tri_orders.push(TriOrder {
             points: [p1, p2, p3],
-            color: self.tint,
+            colors: [self.tint; 3],
             uvs: [uv1, uv2, uv3],
             texture: tex.shader_index(),
             sampler_index: self.sampler_index(),
             opaque,
+            depth: true,
         });
         self.tri_orders.push(TriOrder {
             points: [p1, p3, p4],
-            color: self.tint,
+            colors: [self.tint; 3],
             uvs: [uv1, uv3, uv4],
             texture: tex.shader_index(),
             sampler_index: self.sampler_index(),
             opaque,
+            depth: true,
         });
     }
 
-    /// Get text width for current font
-    pub fn text_width(&self, text: impl AsRef<str>) -> f32 {
-        let font = self
-            .font
-            .as_ref()
-            .unwrap_or(&self.builtins.fira_font)
-            .read();
-        let metrics = font.metrics().scaled(self.font_size);
-        let mut max_width = 0.0;
-
-        // get the width of the longest line
-        for line in text.as_ref().lines() {
-            let mut width = 0.0;
-            for c in line.chars() {
-                // handle whitespace
-                if c == ' ' {
-                    width += metrics.space_width;
+    /// Draw a quad from a texture split into 9 parts
+    ///
+    /// Margins are in pixels for the top, right,
+    /// bottom and left sides of the texture.
+    /// Corners keep their size, while the edges
+    /// and center get stretched.
+    pub fn nine_slice(
+        &mut self,
+        texture: &Handle<Texture>,
+        pos: impl Into<Vec2>,
+        size: impl Into<Vec2>,
+        margins: impl Into<Vec4>,
+    ) {
+        let s = size.into();
+        let p = pos.into();
+        let m = margins.into();
+        let (tw, th) = {
+            let tex = texture.read();
+            (tex.width() as f32, tex.height() as f32)
+        };
+
+        let offset = match self.shape_mode {
+            ShapeMode::BottomLeft => Vec2::new(0.0, 0.0),
+            ShapeMode::BottomRight => Vec2::new(-s.x, 0.0),
+            ShapeMode::TopLeft => Vec2::new(0.0, -s.y),
+            ShapeMode::TopRight => Vec2::new(-s.x, -s.y),
+            ShapeMode::Center => Vec2::new(-s.x / 2.0, -s.y / 2.0),
+        };
+        let left = p.x + offset.x;
+        let top = p.y + offset.y + s.y;
+
+        // shrink corners if they don't fit
+        let x_scale = (s.x / (m.w + m.y)).min(1.0);
+        let y_scale = (s.y / (m.x + m.z)).min(1.0);
+
+        // columns from left and rows from top
+        let xs = [
+            left,
+            left + m.w * x_scale,
+            left + s.x - m.y * x_scale,
+            left + s.x,
+        ];
+        let ys = [
+            top,
+            top - m.x * y_scale,
+            top - s.y + m.z * y_scale,
+            top - s.y,
+        ];
+        let us = [0.0, m.w, tw - m.y, tw];
+        let vs = [0.0, m.x, th - m.z, th];
+
+        self.push();
+        self.shape_mode = ShapeMode::BottomLeft;
+        for row in 0..3 {
+            for col in 0..3 {
+                let width = xs[col + 1] - xs[col];
+                let height = ys[row] - ys[row + 1];
+                if width <= 0.0 || height <= 0.0 {
                     continue;
                 }
-                let data = font.char_data(c).scaled(self.font_size);
-                width += data.advance;
-            }
-            if width > max_width {
-                max_width = width;
+
+                self.texture_part(
+                    texture,
+                    [xs[col], ys[row + 1]],
+                    [width, height],
+                    [us[col], vs[row]],
+                    [us[col + 1] - us[col], vs[row + 1] - vs[row]],
+                );
             }
         }
+        self.pop();
+    }
+
+    /// Draw a sprite from a texture atlas
+    ///
+    /// Sprite is drawn at its size in pixels
+    pub fn sprite(&mut self, atlas: &TextureAtlas, name: impl AsRef<str>, pos: impl Into<Vec2>) {
+        if let Some(sprite) = atlas.sprite(name) {
+            let size = [sprite.width as f32, sprite.height as f32];
+            self.texture_part(
+                atlas.texture(sprite),
+                pos,
+                size,
+                [sprite.x as f32, sprite.y as f32],
+                size,
+            );
+        }
+    }
+
+    /// Draw the current frame of an animation
+    ///
+    /// Frame is drawn at its size in pixels
+    pub fn animation(&mut self, animation: &Animation, pos: impl Into<Vec2>) {
+        if let Some(frame) = animation.frames().get(animation.frame_index()) {
+            self.texture_part(animation.texture(), pos, frame.size, frame.pos, frame.size);
+        }
+    }
+
+    /// Draw a tilemap
+    ///
+    /// Tiles are drawn at their size in pixels
+    pub fn tilemap(&mut self, tilemap: &Handle<Tilemap>, pos: impl Into<Vec2>) {
+        let map = tilemap.read();
+        let s = map.size();
+        let p = pos.into();
+
+        let offset = match self.shape_mode {
+            ShapeMode::BottomLeft => Vec3::new(0.0, 0.0, 0.0),
+            ShapeMode::BottomRight => Vec3::new(-s.x, 0.0, 0.0),
+            ShapeMode::TopLeft => Vec3::new(0.0, -s.y, 0.0),
+            ShapeMode::TopRight => Vec3::new(-s.x, -s.y, 0.0),
+            ShapeMode::Center => Vec3::new(-s.x / 2.0, -s.y / 2.0, 0.0),
+        };
+
+        self.push();
+        self.matrix *= Mat4::translation(Vec3::new(p.x, p.y, 0.0) + offset);
+        self.shader = Some(self.builtins.shape_shader.clone());
+        self.material = None;
+        self.shadows = false;
+        for chunk in map.visible_chunks() {
+            self.mesh(&chunk.mesh);
+        }
+        self.pop();
+    }
+
+    /// Draw an SVG image
+    ///
+    /// The image is stretched to fit `size`
+    #[cfg(feature = "svg")]
+    pub fn svg(&mut self, svg: &Handle<Svg>, pos: impl Into<Vec2>, size: impl Into<Vec2>) {
+        let s = size.into();
+        let p = pos.into();
+        let image = svg.read();
+        let texture = self.builtins.white_texture.read().shader_index();
+
+        if image.width() == 0.0 || image.height() == 0.0 {
+            return;
+        }
+
+        let offset = match self.shape_mode {
+            ShapeMode::BottomLeft => Vec3::new(0.0, 0.0, 0.0),
+            ShapeMode::BottomRight => Vec3::new(-s.x, 0.0, 0.0),
+            ShapeMode::TopLeft => Vec3::new(0.0, -s.y, 0.0),
+            ShapeMode::TopRight => Vec3::new(-s.x, -s.y, 0.0),
+            ShapeMode::Center => Vec3::new(-s.x / 2.0, -s.y / 2.0, 0.0),
+        };
+
+        // SVG coordinates go from top to bottom
+        let sx = s.x / image.width();
+        let sy = s.y / image.height();
+        let matrix = self.matrix;
+        let to_target =
+            |v: Vec2| matrix * (Vec3::new(p.x + v.x * sx, p.y + s.y - v.y * sy, 0.0) + offset);
+
+        for (points, colors) in image.points.chunks(3).zip(image.colors.chunks(3)) {
+            self.tri_orders.push(TriOrder {
+                points: [
+                    to_target(points[0]),
+                    to_target(points[1]),
+                    to_target(points[2]),
+                ],
+                uvs: [Vec2::default(); 3],
+                colors: [colors[0], colors[1], colors[2]],
+                sampler_index: 0,
+                texture,
+                opaque: colors.iter().all(|c| c.a == 255),
+                depth: true,
+            });
+        }
+    }
+
+    /// Lay out text with current font
+    ///
+    /// If `max_width` is `None`, lines are
+    /// only broken on new lines.
+    pub fn text_layout(
+        &self,
+        text: impl AsRef<str>,
+        max_width: Option<f32>,
+        style: TextStyle,
+    ) -> TextLayout {
+        self.rich_text_layout(&[Span::new(text.as_ref())], max_width, style)
+    }
+
+    /// Lay out text made of styled spans
+    ///
+    /// If `max_width` is `None`, lines are
+    /// only broken on new lines.
+    pub fn rich_text_layout(
+        &self,
+        spans: &[Span<'_>],
+        max_width: Option<f32>,
+        style: TextStyle,
+    ) -> TextLayout {
+        let (handles, chains) = self.span_fonts(spans);
+
+        // load missing glyphs for dynamic fonts
+        for (span, chain) in spans.iter().zip(&chains) {
+            let fonts: Vec<_> = chain.iter().map(|i| &handles[*i]).collect();
+            load_glyphs(&fonts, span.text);
+        }
 
-        max_width
+        let guards: Vec<_> = handles.iter().map(|f| f.read()).collect();
+        let fonts: Vec<Vec<_>> = chains
+            .iter()
+            .map(|c| c.iter().map(|i| &*guards[*i]).collect())
+            .collect();
+        let parts: Vec<_> = spans
+            .iter()
+            .zip(&fonts)
+            .map(|(span, fonts)| LayoutPart {
+                text: span.text,
+                font_size: span.size.unwrap_or(self.font_size),
+                fonts,
+            })
+            .collect();
+        TextLayout::from_parts(&parts, max_width, style)
+    }
+
+    /// Get text width for current font
+    pub fn text_width(&self, text: impl AsRef<str>) -> f32 {
+        self.text_layout(text, None, TextStyle::default())
+            .advance_width()
     }
 
     /// Get text height for current font
     pub fn text_height(&self, text: impl AsRef<str>) -> f32 {
-        let font = self
-            .font
-            .as_ref()
-            .unwrap_or(&self.builtins.fira_font)
-            .read();
-        let metrics = font.metrics().scaled(self.font_size);
-        let lines = text.as_ref().lines().count();
-        (metrics.height + metrics.line_gap) * lines as f32
+        self.text_layout(text, None, TextStyle::default()).height()
     }
 
     /// Save target settings to stack
@@ -875,12 +1423,16 @@ impl Target {
         self.cache.push(Cache {
             shader: self.shader.clone(),
             material: self.material.clone(),
+            skin: self.skin.clone(),
             font: self.font.clone(),
+            font_fallbacks: self.font_fallbacks.clone(),
 
             background: self.background,
             fill: self.fill,
             stroke: self.stroke,
             tint: self.tint,
+            fill_gradient: self.fill_gradient.clone(),
+            stroke_gradient: self.stroke_gradient.clone(),
 
             shadows: self.shadows,
 
@@ -891,6 +1443,11 @@ impl Target {
             border_mode: self.border_mode,
             filter: self.filter,
             wrap: self.wrap,
+
+            text_outline: self.text_outline,
+            text_glow: self.text_glow,
+            text_shadow: self.text_shadow,
+            depth_test: self.depth_test,
         });
     }
 
@@ -899,12 +1456,16 @@ impl Target {
         if let Some(cache) = self.cache.pop() {
             self.shader = cache.shader;
             self.material = cache.material;
+            self.skin = cache.skin;
             self.font = cache.font;
+            self.font_fallbacks = cache.font_fallbacks;
 
             self.background = cache.background;
             self.fill = cache.fill;
             self.stroke = cache.stroke;
             self.tint = cache.tint;
+            self.fill_gradient = cache.fill_gradient;
+            self.stroke_gradient = cache.stroke_gradient;
 
             self.shadows = cache.shadows;
 
@@ -915,12 +1476,30 @@ impl Target {
             self.border_mode = cache.border_mode;
             self.filter = cache.filter;
             self.wrap = cache.wrap;
+
+            self.text_outline = cache.text_outline;
+            self.text_glow = cache.text_glow;
+            self.text_shadow = cache.text_shadow;
+            self.depth_test = cache.depth_test;
         }
     }
 
-    fn model_node(&mut self, node: &ModelNode, parent: Mat4) {
-        self.matrix = parent * node.matrix;
-        // self.matrix = node.matrix * parent;
+    fn model_subtree(&mut self, node: &ModelNode, parent: Mat4, root: Mat4) {
+        if !node.visible {
+            return;
+        }
+
+        self.matrix = parent * node.matrix();
+        let node_matrix = self.matrix;
+
+        // skinned meshes are positioned by their joints
+        match &node.skin {
+            Some(skin) => {
+                self.matrix = root;
+                self.skin(skin);
+            }
+            None => self.no_skin(),
+        }
 
         for (mesh, material) in node.orders() {
             self.material(material);
@@ -929,7 +1508,7 @@ impl Target {
 
         for child in &node.children {
             self.push();
-            self.model_node(child, self.matrix);
+            self.model_subtree(child, node_matrix, root);
             self.pop();
         }
     }
@@ -979,50 +1558,441 @@ impl Target {
         }
 
         // draw tris
+        let gradient = self.stroke_gradient.take();
         for i in 0..(normals.len() - 1) {
             let curr_norm = normals[i];
             let next_norm = normals[i + 1];
             let curr_point = points[i];
             let next_point = *points.get(i + 1).unwrap_or(&points[0]);
 
-            let p1 = self.matrix * Vec3::from((curr_point + curr_norm * outer_weight, -0.00001));
-            let p2 = self.matrix * Vec3::from((next_point + next_norm * outer_weight, -0.00001));
-            let p3 = self.matrix * Vec3::from((next_point - next_norm * inner_weight, -0.00001));
-            let p4 = self.matrix * Vec3::from((curr_point - curr_norm * inner_weight, -0.00001));
+            let quad = [
+                curr_point + curr_norm * outer_weight,
+                next_point + next_norm * outer_weight,
+                next_point - next_norm * inner_weight,
+                curr_point - curr_norm * inner_weight,
+            ];
+
+            if let Some(g) = &gradient {
+                self.gradient_polygon(&quad, g, -0.00001);
+                continue;
+            }
+
+            let p1 = self.matrix * Vec3::from((quad[0], -0.00001));
+            let p2 = self.matrix * Vec3::from((quad[1], -0.00001));
+            let p3 = self.matrix * Vec3::from((quad[2], -0.00001));
+            let p4 = self.matrix * Vec3::from((quad[3], -0.00001));
 
             let texture = self.builtins.white_texture.read().shader_index();
 
             self.tri_orders.push(TriOrder {
                 points: [p1, p2, p3],
-                color: self.stroke,
+                colors: [self.stroke; 3],
                 uvs: [Vec2::default(); 3],
                 opaque: self.stroke.a == 255,
+                depth: true,
                 sampler_index: 0,
                 texture,
             });
             self.tri_orders.push(TriOrder {
                 points: [p1, p3, p4],
-                color: self.stroke,
+                colors: [self.stroke; 3],
                 uvs: [Vec2::default(); 3],
                 opaque: self.stroke.a == 255,
+                depth: true,
                 sampler_index: 0,
                 texture,
             });
         }
+        self.stroke_gradient = gradient;
+    }
+
+    fn gradient_polygon(&mut self, points: &[Vec2], gradient: &ShapeGradient, z: f32) {
+        // colors are interpolated between vertices, so
+        // the polygon is split up where interpolation
+        // would differ from the gradient
+        let mut tris = vec![];
+        match gradient {
+            ShapeGradient::Linear(g) => {
+                // cut polygon into bands at each gradient stop
+                let count = g.stops.len();
+                let mut rest = points.to_vec();
+                for i in 0..count {
+                    let cut = i as f32 / (count - 1) as f32;
+                    let (band, above) = split_polygon(&rest, |p| g.progress(p) - cut);
+                    triangulate(&band, &mut tris);
+                    rest = above;
+                }
+                triangulate(&rest, &mut tris);
+            }
+            ShapeGradient::Radial(g) => {
+                // subdivide triangles until they are small
+                // enough to approximate the rings
+                let mut fan = vec![];
+                triangulate(points, &mut fan);
+                for tri in fan {
+                    subdivide(tri, g.radius / 8.0, 8, &mut tris);
+                }
+            }
+        }
+
+        let texture = self.builtins.white_texture.read().shader_index();
+        let opaque = gradient.opaque();
+
+        for tri in tris {
+            self.tri_orders.push(TriOrder {
+                points: [
+                    self.matrix * Vec3::from((tri[0], z)),
+                    self.matrix * Vec3::from((tri[1], z)),
+                    self.matrix * Vec3::from((tri[2], z)),
+                ],
+                colors: [
+                    gradient.color_at(tri[0]),
+                    gradient.color_at(tri[1]),
+                    gradient.color_at(tri[2]),
+                ],
+                uvs: [Vec2::default(); 3],
+                sampler_index: 0,
+                texture,
+                opaque,
+                depth: true,
+            });
+        }
+    }
+
+    // draws layout with quads mapped to the
+    // target by `place`
+    fn draw_text_layout(
+        &mut self,
+        layout: &TextLayout,
+        spans: &[Span<'_>],
+        w: f32,
+        h: f32,
+        place: impl Fn(Vec2) -> Vec3,
+        depth: bool,
+    ) {
+        let (handles, chains) = self.span_fonts(spans);
+        let guards: Vec<_> = handles.iter().map(|f| f.read()).collect();
+
+        // top-left corner of the text
+        let origin = match self.shape_mode {
+            ShapeMode::BottomLeft => Vec2::new(0.0, h),
+            ShapeMode::BottomRight => Vec2::new(-w, h),
+            ShapeMode::TopLeft => Vec2::new(0.0, 0.0),
+            ShapeMode::TopRight => Vec2::new(-w, 0.0),
+            ShapeMode::Center => Vec2::new(-w / 2.0, h / 2.0),
+        };
+
+        // glyph quads relative to the origin
+        let mut quads = vec![];
+        for glyph in layout.glyphs() {
+            // handle whitespace
+            if glyph.c == ' ' {
+                continue;
+            }
+
+            // calculate positions
+            let span = &spans[glyph.span];
+            let size = span.size.unwrap_or(self.font_size);
+            let color = span.color.unwrap_or(self.stroke);
+            let font = &guards[chains[glyph.span][glyph.font]];
+            let data = match font.glyph_data(glyph.glyph) {
+                Ok(d) => d.scaled(size),
+                // skip glyphs the font cannot draw
+                Err(_) => continue,
+            };
+            let bx = data.bounds.x;
+            let by = data.bounds.y;
+            let mut cp = origin + glyph.pos;
+            cp.x += data.bearing.x;
+            cp.y -= data.bearing.y;
+
+            let points = [
+                Vec2::new(cp.x, cp.y + by),
+                Vec2::new(cp.x + bx, cp.y + by),
+                Vec2::new(cp.x + bx, cp.y),
+                Vec2::new(cp.x, cp.y),
+            ];
+            let uvs = [
+                Vec2::new(data.uvs.x, data.uvs.y),
+                Vec2::new(data.uvs.z, data.uvs.y),
+                Vec2::new(data.uvs.z, data.uvs.w),
+                Vec2::new(data.uvs.x, data.uvs.w),
+            ];
+            let texture = font.texture(data.page).shader_index();
+            quads.push((points, uvs, texture, font.sdf_range(), color, size));
+        }
+
+        // decorations of consecutive glyphs
+        // in the same line and span
+        let white = self.builtins.white_texture.read().shader_index();
+        let glyphs = layout.glyphs();
+        let mut start = 0;
+        while start < glyphs.len() {
+            let first = glyphs[start];
+            let end = glyphs[start..]
+                .iter()
+                .position(|g| g.line != first.line || g.span != first.span)
+                .map_or(glyphs.len(), |i| start + i);
+            let group = &glyphs[start..end];
+            start = end;
+
+            let span = &spans[first.span];
+            let last = match group.iter().rev().find(|g| g.c != ' ') {
+                Some(g) => g,
+                None => continue,
+            };
+            let size = span.size.unwrap_or(self.font_size);
+            let color = span.color.unwrap_or(self.stroke);
+            let metrics = guards[chains[first.span][0]].metrics().scaled(size);
+            let thickness = (size as f32 / 16.0).max(1.0);
+            let x0 = origin.x + first.pos.x;
+            let x1 = origin.x + last.pos.x + last.advance;
+            let baseline = origin.y + first.pos.y;
+
+            let mut lines = vec![];
+            if span.underline {
+                lines.push(baseline + metrics.descender / 2.0);
+            }
+            if span.strikethrough {
+                lines.push(baseline + metrics.ascender * 0.3);
+            }
+            for y in lines {
+                let top = y + thickness / 2.0;
+                let bottom = y - thickness / 2.0;
+                let points = [
+                    Vec2::new(x0, top),
+                    Vec2::new(x1, top),
+                    Vec2::new(x1, bottom),
+                    Vec2::new(x0, bottom),
+                ];
+                quads.push((points, [Vec2::default(); 4], white, None, color, size));
+            }
+        }
+
+        // passes are drawn from back to front
+        let mut passes = vec![];
+        if let Some((color, offset)) = self.text_shadow {
+            passes.push((color, offset, TextPass::Fill));
+        }
+        if let Some((color, width)) = self.text_glow {
+            passes.push((color, Vec2::default(), TextPass::Glow(width)));
+        }
+        if let Some((color, width)) = self.text_outline {
+            passes.push((color, Vec2::default(), TextPass::Outline(width)));
+        }
+        passes.push((self.stroke, Vec2::default(), TextPass::Fill));
+        let last_pass = passes.len() - 1;
+
+        // effect widths are converted to distance values
+        let to_distance = |w: f32, range: f32, size: u32| (w / (range * size as f32)).min(0.5);
+
+        for (i, (effect_color, offset, pass)) in passes.into_iter().enumerate() {
+            for (points, uvs, texture, range, quad_color, size) in &quads {
+                // effects are only drawn for sdf fonts
+                let sdf = match (pass, *range) {
+                    (TextPass::Fill, r) => r.map(|_| (0.5, 0.0)),
+                    (TextPass::Glow(width), Some(r)) => {
+                        let d = to_distance(width, r, *size);
+                        Some((0.5 - d / 2.0, d / 2.0))
+                    }
+                    (TextPass::Outline(width), Some(r)) => {
+                        Some((0.5 - to_distance(width, r, *size), 0.0))
+                    }
+                    (_, None) => continue,
+                };
+                let to_target = |p: Vec2| place(p + offset);
+
+                // text is filled with span colors
+                let color = if i == last_pass {
+                    *quad_color
+                } else {
+                    effect_color
+                };
+
+                // add order
+                self.char_orders.push(CharOrder {
+                    points: [
+                        to_target(points[0]),
+                        to_target(points[1]),
+                        to_target(points[2]),
+                        to_target(points[3]),
+                    ],
+                    uvs: *uvs,
+                    texture: *texture,
+                    color,
+                    depth,
+                    sdf,
+                });
+            }
+        }
+    }
+
+    // origin, right and up directions of a billboard
+    fn billboard_axes(&self, pos: Vec3, mode: BillboardMode) -> (Vec3, Vec3, Vec3) {
+        let origin = self.matrix * pos;
+        let view_to_world = self.camera.rotation.inverse();
+
+        match mode {
+            BillboardMode::None => (
+                origin,
+                self.matrix * (pos + Vec3::right()) - origin,
+                self.matrix * (pos + Vec3::up()) - origin,
+            ),
+            BillboardMode::Spherical => (
+                origin,
+                view_to_world * Vec3::right(),
+                view_to_world * Vec3::up(),
+            ),
+            BillboardMode::Cylindrical => {
+                let mut right = view_to_world * Vec3::right();
+                right.y = 0.0;
+                if right.length() < f32::EPSILON {
+                    right = Vec3::right();
+                }
+                (origin, right.unit(), Vec3::up())
+            }
+        }
+    }
+
+    // converts billboard size to world units
+    fn world_height(&self, origin: Vec3, size: BillboardSize) -> f32 {
+        let pixels = match size {
+            BillboardSize::World(h) => return h,
+            BillboardSize::Pixels(p) => p,
+        };
+
+        let camera = &self.camera;
+        let units_per_pixel = match camera.projection {
+            Projection::Orthographic => {
+                camera.height.unwrap_or(self.view_height) / self.view_height
+            }
+            Projection::Perspective => {
+                let forward = camera.rotation.inverse() * Vec3::forward();
+                let distance = (origin - camera.position).dot(forward);
+                let half_fov = (camera.fov as f32 / 2.0).to_radians();
+                2.0 * distance * half_fov.tan() / self.view_height
+            }
+        };
+
+        pixels * units_per_pixel
+    }
+
+    // unique fonts used by spans and each span's font
+    // followed by fallback fonts as indices into them
+    fn span_fonts(&self, spans: &[Span<'_>]) -> (Vec<Handle<Font>>, Vec<Vec<usize>>) {
+        let default = self.font.as_ref().unwrap_or(&self.builtins.fira_font);
+        let mut handles: Vec<Handle<Font>> = vec![];
+        let mut chains = vec![];
+
+        for span in spans {
+            let mut chain = vec![];
+            let fonts = span
+                .font
+                .or(Some(default))
+                .into_iter()
+                .chain(&self.font_fallbacks);
+            for font in fonts {
+                let index = match handles.iter().position(|h| h == font) {
+                    Some(i) => i,
+                    None => {
+                        handles.push(font.clone());
+                        handles.len() - 1
+                    }
+                };
+                if !chain.contains(&index) {
+                    chain.push(index);
+                }
+            }
+            chains.push(chain);
+        }
+
+        (handles, chains)
     }
 
     const fn sampler_index(&self) -> u32 {
-        match (self.filter, self.wrap) {
-            (Filter::Linear, Wrap::Repeat) => 0,
-            (Filter::Linear, Wrap::ClampBorder) => 1,
-            (Filter::Linear, Wrap::ClampEdge) => 2,
-            (Filter::Nearest, Wrap::Repeat) => 3,
-            (Filter::Nearest, Wrap::ClampBorder) => 4,
-            (Filter::Nearest, Wrap::ClampEdge) => 5,
+        image::sampler_index(self.filter, self.wrap)
+    }
+}
+
+// loads missing glyphs of text for dynamic fonts
+fn load_glyphs(fonts: &[&Handle<Font>], text: &str) {
+    let runs = {
+        let guards: Vec<_> = fonts.iter().map(|f| f.read()).collect();
+        let refs: Vec<_> = guards.iter().map(|f| &**f).collect();
+        font_runs(&refs, text)
+    };
+    for (i, range) in runs {
+        if fonts[i].read().is_lazy() {
+            fonts[i].write().load_text(&text[range]);
+        }
+    }
+}
+
+// splits convex polygon into the parts where
+// the side function is negative and positive
+fn split_polygon(points: &[Vec2], side: impl Fn(Vec2) -> f32) -> (Vec<Vec2>, Vec<Vec2>) {
+    let mut below = vec![];
+    let mut above = vec![];
+
+    for (i, a) in points.iter().enumerate() {
+        let b = points[(i + 1) % points.len()];
+        let sa = side(*a);
+        let sb = side(b);
+
+        if sa <= 0.0 {
+            below.push(*a);
+        }
+        if sa >= 0.0 {
+            above.push(*a);
         }
+
+        // add intersection point to both parts
+        if (sa < 0.0 && sb > 0.0) || (sa > 0.0 && sb < 0.0) {
+            let p = *a + (b - *a) * (sa / (sa - sb));
+            below.push(p);
+            above.push(p);
+        }
+    }
+
+    (below, above)
+}
+
+fn triangulate(points: &[Vec2], tris: &mut Vec<[Vec2; 3]>) {
+    for i in 2..points.len() {
+        tris.push([points[0], points[i - 1], points[i]]);
     }
 }
 
+// splits triangle on the longest side
+// until all sides are shorter than max
+pub(crate) fn subdivide(tri: [Vec2; 3], max: f32, depth: u32, tris: &mut Vec<[Vec2; 3]>) {
+    let lengths = [
+        (tri[1] - tri[0]).sqr_length(),
+        (tri[2] - tri[1]).sqr_length(),
+        (tri[0] - tri[2]).sqr_length(),
+    ];
+    let longest = if lengths[0] >= lengths[1] && lengths[0] >= lengths[2] {
+        0
+    } else if lengths[1] >= lengths[2] {
+        1
+    } else {
+        2
+    };
+
+    if depth == 0 || lengths[longest] <= max * max {
+        tris.push(tri);
+        return;
+    }
+
+    let a = tri[longest];
+    let b = tri[(longest + 1) % 3];
+    let c = tri[(longest + 2) % 3];
+    let mid = (a + b) / 2.0;
+
+    subdivide([a, mid, c], max, depth - 1, tris);
+    subdivide([mid, b, c], max, depth - 1, tris);
+}
+
 fn miter(line_a: Vec2, line_b: Vec2) -> Vec2 {
     let tangent = (line_a + line_b).unit();
     let miter = tangent.normal();
@@ -18,6 +18,7 @@ use crate::image::Texture;
 use crate::math::Vec2;
 use crate::math::Vec3;
 use crate::mesh::Mesh;
+use crate::mesh::Skin;
 use crate::pipeline::Material;
 use crate::pipeline::Shader;
 use crate::pipeline::Uniforms;
@@ -34,6 +35,9 @@ pub struct Builtins {
     // materials
     pub white_material: Handle<Material>,
 
+    // skins
+    pub identity_skin: Handle<Skin>,
+
     // meshes
     pub surface_mesh: Handle<Mesh>,
     pub plane_mesh: Handle<Mesh>,
@@ -44,9 +48,14 @@ pub struct Builtins {
     // shaders
     pub pbr_shader: Handle<Shader>,
     pub font_shader: Handle<Shader>,
+    pub sdf_font_shader: Handle<Shader>,
+    pub font_3d_shader: Handle<Shader>,
+    pub sdf_font_3d_shader: Handle<Shader>,
     pub wireframe_shader: Handle<Shader>,
     pub line_shader: Handle<Shader>,
+    pub point_shader: Handle<Shader>,
     pub shape_shader: Handle<Shader>,
+    pub shape_overlay_shader: Handle<Shader>,
     pub unshaded_shader: Handle<Shader>,
     pub skybox_shader: Handle<Shader>,
     pub fullscreen_shader: Handle<Shader>,
@@ -117,6 +126,9 @@ impl Builtins {
             resources.add_material(mat)
         };
 
+        // skins
+        let identity_skin = resources.add_skin(Skin::new(device, uniforms)?);
+
         // meshes
         let surface_mesh = resources.add_mesh(create_surface(device));
         let plane_mesh = resources.add_mesh(create_plane(device));
@@ -147,6 +159,39 @@ impl Builtins {
             resources.add_shader(shader)
         };
 
+        let sdf_font_shader = {
+            let shader = Shader::from_spirv_bytes(
+                device,
+                uniforms,
+                msaa,
+                include_bytes!("../../shaders/sdf.spirv"),
+            )
+            .expect("bad shader");
+            resources.add_shader(shader)
+        };
+
+        let font_3d_shader = {
+            let shader = Shader::from_spirv_bytes(
+                device,
+                uniforms,
+                msaa,
+                include_bytes!("../../shaders/font-3d.spirv"),
+            )
+            .expect("bad shader");
+            resources.add_shader(shader)
+        };
+
+        let sdf_font_3d_shader = {
+            let shader = Shader::from_spirv_bytes(
+                device,
+                uniforms,
+                msaa,
+                include_bytes!("../../shaders/sdf-3d.spirv"),
+            )
+            .expect("bad shader");
+            resources.add_shader(shader)
+        };
+
         let wireframe_shader = {
             let shader = Shader::from_spirv_bytes(
                 device,
@@ -169,6 +214,17 @@ impl Builtins {
             resources.add_shader(shader)
         };
 
+        let point_shader = {
+            let shader = Shader::points_from_spirv_bytes(
+                device,
+                uniforms,
+                msaa,
+                include_bytes!("../../shaders/lines.spirv"),
+            )
+            .expect("bad shader");
+            resources.add_shader(shader)
+        };
+
         let shape_shader = {
             let shader = Shader::from_spirv_bytes(
                 device,
@@ -180,6 +236,17 @@ impl Builtins {
             resources.add_shader(shader)
         };
 
+        let shape_overlay_shader = {
+            let shader = Shader::from_spirv_bytes(
+                device,
+                uniforms,
+                msaa,
+                include_bytes!("../../shaders/shape-overlay.spirv"),
+            )
+            .expect("bad shader");
+            resources.add_shader(shader)
+        };
+
         let unshaded_shader = {
             let shader = Shader::from_spirv_bytes(
                 device,
@@ -224,6 +291,7 @@ impl Builtins {
             black_texture,
             white_cubemap,
             white_material,
+            identity_skin,
             surface_mesh,
             plane_mesh,
             cube_mesh,
@@ -231,9 +299,14 @@ impl Builtins {
             uv_sphere_mesh,
             pbr_shader,
             font_shader,
+            sdf_font_shader,
+            font_3d_shader,
+            sdf_font_3d_shader,
             wireframe_shader,
             line_shader,
+            point_shader,
             shape_shader,
+            shape_overlay_shader,
             unshaded_shader,
             skybox_shader,
             fullscreen_shader,
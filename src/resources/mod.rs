@@ -11,6 +11,8 @@ use crate::image::Cubemap;
 use crate::image::Texture;
 use crate::mesh::Mesh;
 use crate::mesh::Model;
+use crate::mesh::Skin;
+use crate::mesh::Tilemap;
 use crate::pipeline::Material;
 use crate::pipeline::Shader;
 use crate::pipeline::Uniforms;
@@ -33,6 +35,8 @@ pub(crate) struct Resources {
     materials: Vec<Handle<Material>>,
     meshes: Vec<Handle<Mesh>>,
     models: Vec<Handle<Model>>,
+    skins: Vec<Handle<Skin>>,
+    tilemaps: Vec<Handle<Tilemap>>,
 }
 
 impl Resources {
@@ -68,8 +72,17 @@ impl Resources {
         add(&mut self.models, value)
     }
 
+    pub(crate) fn add_skin(&mut self, value: Skin) -> Handle<Skin> {
+        add(&mut self.skins, value)
+    }
+
+    pub(crate) fn add_tilemap(&mut self, value: Tilemap) -> Handle<Tilemap> {
+        add(&mut self.tilemaps, value)
+    }
+
     pub(crate) fn clear_unused(&mut self, device: &Device, uniforms: &mut Uniforms) {
         clear_unused(&mut self.models, |_| {});
+        clear_unused(&mut self.tilemaps, |_| {});
         clear_unused(&mut self.fonts, |v| v.destroy(device, uniforms));
         clear_unused(&mut self.shaders, |v| v.destroy(device));
         clear_unused(&mut self.textures, |v| v.destroy(device, uniforms));
@@ -77,10 +90,12 @@ impl Resources {
         clear_unused(&mut self.canvases, |v| v.destroy(device, uniforms));
         clear_unused(&mut self.materials, |v| v.destroy(device));
         clear_unused(&mut self.meshes, |v| v.destroy(device));
+        clear_unused(&mut self.skins, |v| v.destroy(device));
     }
 
     pub(crate) fn clear(&mut self, device: &Device, uniforms: &mut Uniforms) {
         clear(&mut self.models, |_| {});
+        clear(&mut self.tilemaps, |_| {});
         clear(&mut self.fonts, |v| v.destroy(device, uniforms));
         clear(&mut self.shaders, |v| v.destroy(device));
         clear(&mut self.textures, |v| v.destroy(device, uniforms));
@@ -88,9 +103,24 @@ impl Resources {
         clear(&mut self.canvases, |v| v.destroy(device, uniforms));
         clear(&mut self.materials, |v| v.destroy(device));
         clear(&mut self.meshes, |v| v.destroy(device));
+        clear(&mut self.skins, |v| v.destroy(device));
     }
 
     pub(crate) fn update_if_needed(&mut self, device: &Device, uniforms: &mut Uniforms) {
+        // update tilemap chunk meshes
+        for h in &mut self.tilemaps {
+            if h.mutated() {
+                h.get_mut().update();
+            }
+        }
+
+        // update dynamic font atlases
+        for h in &mut self.fonts {
+            if h.mutated() {
+                h.get_mut().update(device, uniforms);
+            }
+        }
+
         // update meshes
         for h in &mut self.meshes {
             if h.mutated() {
@@ -100,6 +130,13 @@ impl Resources {
 
         // update materials
         for h in &mut self.materials {
+            if h.mutated() || h.read().samplers_changed() {
+                h.get_mut().update();
+            }
+        }
+
+        // update skins
+        for h in &mut self.skins {
             if h.mutated() {
                 h.get_mut().update();
             }
@@ -119,6 +156,13 @@ impl Resources {
             }
         }
     }
+
+    // uploads glyphs loaded while recording
+    pub(crate) fn upload_fonts(&self, device: &Device) {
+        for h in &self.fonts {
+            h.get_mut().upload(device);
+        }
+    }
 }
 
 fn add<T>(handles: &mut Vec<Handle<T>>, value: T) -> Handle<T> {